@@ -68,6 +68,10 @@ pub struct MerchantAccountCreate {
     #[schema(default = false, example = true)]
     pub redirect_to_merchant_with_http_post: Option<bool>,
 
+    /// A boolean value to indicate if the raw connector requests and responses of the payments of the merchant need to be stored for debugging. The payloads are stored redacted
+    #[schema(default = false, example = false)]
+    pub debug_connector_io: Option<bool>,
+
     /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
     pub metadata: Option<MerchantAccountMetadata>,
@@ -157,6 +161,10 @@ pub struct MerchantAccountUpdate {
     #[schema(default = false, example = true)]
     pub redirect_to_merchant_with_http_post: Option<bool>,
 
+    /// A boolean value to indicate if the raw connector requests and responses of the payments of the merchant need to be stored for debugging. The payloads are stored redacted
+    #[schema(default = false, example = false)]
+    pub debug_connector_io: Option<bool>,
+
     /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
     pub metadata: Option<pii::SecretSerdeValue>,
@@ -212,6 +220,10 @@ pub struct MerchantAccountResponse {
     #[schema(default = false, example = true)]
     pub redirect_to_merchant_with_http_post: bool,
 
+    /// A boolean value to indicate if the raw connector requests and responses of the payments of the merchant need to be stored for debugging. The payloads are stored redacted
+    #[schema(default = false, example = false)]
+    pub debug_connector_io: bool,
+
     /// Merchant related details
     #[schema(value_type = Option<MerchantDetails>)]
     pub merchant_details: Option<Encryptable<pii::SecretSerdeValue>>,
@@ -3,7 +3,7 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::payments;
+use crate::{enums, payments};
 
 /// The customer details
 #[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
@@ -96,6 +96,50 @@ pub struct CustomerDeleteResponse {
     pub payment_methods_deleted: bool,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerPaymentsListConstraints {
+    /// The maximum number of payments to be returned. The default limit is 10 and max limit is 100
+    #[serde(default = "default_payments_limit")]
+    pub limit: u32,
+    /// The number of payments to skip, the payments are ordered by their creation time
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerPaymentSummary {
+    /// The identifier for the payment
+    #[schema(max_length = 255, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+    /// The status of the payment
+    #[schema(value_type = IntentStatus, example = "succeeded")]
+    pub status: enums::IntentStatus,
+    /// The payment amount in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub amount: i64,
+    /// The three letter ISO currency code of the payment
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+    /// A description of the payment
+    #[schema(example = "It's my first payment request")]
+    pub description: Option<String>,
+    /// A timestamp (ISO 8601 code) that determines when the payment was created
+    #[schema(value_type = PrimitiveDateTime, example = "2023-01-18T11:04:09.922Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerPaymentsListResponse {
+    /// The identifier for the customer object
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+    /// The number of payments included in the list
+    pub size: usize,
+    /// The payments of the customer, the most recent payment comes first
+    pub data: Vec<CustomerPaymentSummary>,
+}
+
 pub fn generate_customer_id() -> String {
     common_utils::generate_id(consts::ID_LENGTH, "cus")
 }
@@ -103,3 +147,7 @@ pub fn generate_customer_id() -> String {
 fn unknown_merchant() -> String {
     String::from("merchant_unknown")
 }
+
+fn default_payments_limit() -> u32 {
+    10
+}
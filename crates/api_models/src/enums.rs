@@ -492,6 +492,26 @@ pub enum FrmPreferredFlowTypes {
     Pre,
     Post,
 }
+
+/// Status of a payment based on the amount successfully refunded out of the captured amount
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentRefundStatus {
+    PartiallyRefunded,
+    Refunded,
+}
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UnresolvedResponseReason {
     pub code: String,
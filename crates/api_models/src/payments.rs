@@ -120,6 +120,7 @@ pub struct PaymentsRequest {
 
     /// The currency of the payment request can be specified here
     #[schema(value_type = Option<Currency>, example = "USD")]
+    #[serde(default, deserialize_with = "currency_code::deserialize_option")]
     #[mandatory_in(PaymentsCreateRequest)]
     pub currency: Option<api_enums::Currency>,
 
@@ -129,6 +130,7 @@ pub struct PaymentsRequest {
 
     /// The Amount to be captured/ debited from the users payment method. It shall be in lowest denomination of the currency. (i.e) in cents for USD denomination, in paisa for INR denomination etc.,
     /// If not provided, the default amount_to_capture will be the payment amount.
+    /// For automatic capture the full amount is always captured, so a different amount_to_capture is only allowed for manual or scheduled capture.
     #[schema(example = 6540)]
     pub amount_to_capture: Option<i64>,
 
@@ -183,6 +185,14 @@ pub struct PaymentsRequest {
     /// The URL to redirect after the completion of the operation
     #[schema(value_type = Option<String>, example = "https://hyperswitch.io")]
     pub return_url: Option<Url>,
+
+    /// The URL to redirect to when the payment succeeds, takes precedence over `return_url`
+    #[schema(value_type = Option<String>, example = "https://hyperswitch.io/success")]
+    pub success_url: Option<Url>,
+
+    /// The URL to redirect to when the payment fails or is cancelled, takes precedence over `return_url`
+    #[schema(value_type = Option<String>, example = "https://hyperswitch.io/failure")]
+    pub failure_url: Option<Url>,
     /// Indicates that you intend to make future payments with this Payment’s payment method. Providing this parameter will attach the payment method to the Customer, if present, after the Payment is confirmed and any required actions from the user are complete.
     #[schema(value_type = Option<FutureUsage>, example = "off_session")]
     pub setup_future_usage: Option<api_enums::FutureUsage>,
@@ -221,6 +231,10 @@ pub struct PaymentsRequest {
     #[schema(max_length = 255, example = "Payment for shoes purchase")]
     pub statement_descriptor_suffix: Option<String>,
 
+    /// The city of the merchant that customers see on their statements, supported only by some connectors. Maximum 13 characters.
+    #[schema(max_length = 13, example = "San Francisco")]
+    pub statement_descriptor_city: Option<String>,
+
     /// Information about the product , quantity and amount for connectors. (e.g. Klarna)
     #[schema(value_type = Option<Vec<OrderDetailsWithAmount>>, example = r#"[{
         "product_name": "gillete creme",
@@ -240,7 +254,7 @@ pub struct PaymentsRequest {
     #[schema(max_length = 255, example = "mandate_iwer89rnjef349dni3")]
     pub mandate_id: Option<String>,
 
-    /// Additional details required by 3DS 2.0
+    /// Additional details required by 3DS 2.0. user_agent, accept_header, language and java_script_enabled are mandatory when a card payment is confirmed with three_ds authentication, color_depth, screen_height, screen_width, time_zone and java_enabled are also mandatory when java_script_enabled is true
     #[schema(value_type = Option<Object>, example = r#"{
         "user_agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/70.0.3538.110 Safari/537.36",
         "accept_header": "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,image/apng,*/*;q=0.8",
@@ -305,9 +319,67 @@ pub struct PaymentsRequest {
     #[schema(value_type = Option<RequestSurchargeDetails>)]
     pub surcharge_details: Option<RequestSurchargeDetails>,
 
+    /// The shipping cost of the order, included in the payment amount. Amount in the lowest denomination of the currency
+    #[schema(example = 500)]
+    pub shipping_amount: Option<i64>,
+
+    /// The handling cost of the order, included in the payment amount. Amount in the lowest denomination of the currency
+    #[schema(example = 200)]
+    pub handling_amount: Option<i64>,
+
     /// The type of the payment that differentiates between normal and various types of mandate payments
     #[schema(value_type = Option<PaymentType>)]
     pub payment_type: Option<api_enums::PaymentType>,
+
+    /// Details of the installments the payment should be split into. This is only supported for the connectors which support installment payments
+    pub installments: Option<InstallmentData>,
+
+    /// Risk score or decision from an external fraud tool, forwarded as is to the connectors which support risk data passthrough. Must be a JSON object
+    #[schema(value_type = Option<Object>, example = r#"{ "risk_score": "12", "decision": "approve" }"#)]
+    pub risk_data: Option<serde_json::Value>,
+
+    /// Details of the 3DS SDK used by the app of the merchant, required for 3DS authentication of card payments made in an app
+    pub sdk_information: Option<SdkInfo>,
+
+    /// The channel through which the customer is making the payment. Defaults to web
+    #[schema(value_type = Option<PaymentChannel>, example = "pos")]
+    pub payment_channel: Option<api_enums::PaymentChannel>,
+}
+
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema, PartialEq, Eq)]
+pub struct SdkInfo {
+    /// Unique identifier of the installation of the 3DS SDK on the device of the customer
+    #[schema(example = "9063b12c-fcde-43c7-b28e-8d0af5520e8a")]
+    pub sdk_app_id: Option<String>,
+
+    /// Data about the device of the customer, collected and encrypted by the 3DS SDK
+    #[schema(value_type = Option<String>)]
+    pub sdk_enc_data: Option<Secret<String>>,
+
+    /// Public key of the ephemeral key pair generated by the 3DS SDK, as a JSON web key
+    #[schema(value_type = Option<Object>, example = r#"{ "kty": "EC", "crv": "P-256", "x": "...", "y": "..." }"#)]
+    pub sdk_ephem_pub_key: Option<std::collections::HashMap<String, String>>,
+
+    /// Unique identifier of the transaction, assigned by the 3DS SDK
+    #[schema(example = "b60c9879-ac77-4918-a317-7b01c4317053")]
+    pub sdk_trans_id: Option<String>,
+
+    /// Identifier of the 3DS SDK, assigned by EMVCo when the SDK is approved
+    #[schema(example = "3DS_LOA_SDK_ADBV_020200_00245")]
+    pub sdk_reference_number: Option<String>,
+
+    /// Maximum time in minutes the customer has to complete the authentication, between 5 and 99
+    #[schema(example = 60)]
+    pub sdk_max_timeout: Option<u8>,
+}
+
+#[derive(
+    Default, Debug, Clone, serde::Serialize, serde::Deserialize, Copy, ToSchema, PartialEq, Eq,
+)]
+pub struct InstallmentData {
+    /// The number of installments the payment amount should be split into
+    #[schema(example = 3)]
+    pub number_of_installments: u8,
 }
 
 #[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, Copy, ToSchema)]
@@ -402,6 +474,14 @@ pub struct CaptureResponse {
     pub error_reason: Option<String>,
     /// reference to the capture at connector side
     pub reference_id: Option<String>,
+    /// Time when the capture was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    /// Time when the capture was last updated
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub modified_at: Option<PrimitiveDateTime>,
 }
 
 impl PaymentsRequest {
@@ -1634,6 +1714,8 @@ pub struct Address {
     /// Provide the address details
     pub address: Option<AddressDetails>,
 
+    /// The phone number for the address. For the shipping address this is the phone number of
+    /// the recipient, which can be different from the phone number of the customer
     pub phone: Option<PhoneDetails>,
 }
 
@@ -1669,11 +1751,13 @@ pub struct AddressDetails {
     #[schema(value_type = Option<String>, example = "New York")]
     pub state: Option<Secret<String>>,
 
-    /// The first name for the address
+    /// The first name for the address. For the shipping address this is the name of the
+    /// recipient, which can be different from the name of the customer
     #[schema(value_type = Option<String>, max_length = 255, example = "John")]
     pub first_name: Option<Secret<String>>,
 
-    /// The last name for the address
+    /// The last name for the address. For the shipping address this is the name of the
+    /// recipient, which can be different from the name of the customer
     #[schema(value_type = Option<String>, max_length = 255, example = "Doe")]
     pub last_name: Option<Secret<String>>,
 }
@@ -1688,7 +1772,7 @@ pub struct PhoneDetails {
     pub country_code: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct PaymentsCaptureRequest {
     /// The unique identifier for the payment
     #[serde(skip_deserializing)]
@@ -1706,6 +1790,28 @@ pub struct PaymentsCaptureRequest {
     /// Merchant connector details used to make payments.
     #[schema(value_type = Option<MerchantConnectorDetailsWrap>)]
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
+    /// Dynamic currency conversion offer accepted by the customer, the amount is captured in the currency of the offer. This is only supported for the connectors which support dynamic currency conversion
+    pub dcc_offer: Option<DccOffer>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct DccOffer {
+    /// The currency in which the customer chose to pay
+    #[schema(value_type = Currency, example = "EUR")]
+    pub target_currency: api_enums::Currency,
+    /// The rate at which the amount in the payment currency is converted to the target currency
+    #[schema(example = 0.92)]
+    pub exchange_rate: f64,
+}
+
+/// Dynamic currency conversion applied to a capture, stored against the payment attempt
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct DccDetails {
+    pub exchange_rate: f64,
+    pub original_currency: api_enums::Currency,
+    pub original_amount: i64,
+    pub target_currency: api_enums::Currency,
+    pub converted_amount: i64,
 }
 
 #[derive(Default, Clone, Debug, Eq, PartialEq, serde::Serialize)]
@@ -1744,6 +1850,8 @@ pub enum NextActionData {
         #[schema(value_type = String)]
         image_data_url: Url,
         display_to_timestamp: Option<i64>,
+        /// The data encoded in the Qr code, which can be used to render the Qr code or be copied by the customer
+        qr_code_data: Option<String>,
     },
     /// Contains the download url and the reference number for transaction
     DisplayVoucherInformation {
@@ -1782,6 +1890,7 @@ pub struct VoucherNextStepData {
 pub struct QrCodeNextStepsInstruction {
     pub image_data_url: Url,
     pub display_to_timestamp: Option<i64>,
+    pub qr_code_data: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -1865,6 +1974,41 @@ pub struct ReceiverDetails {
     amount_remaining: Option<i64>,
 }
 
+/// Broad reason for the failure of a payment, which can be used to decide whether the payment
+/// can be retried
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclineCategory {
+    /// The payment was declined by the connector or the issuer
+    Declined,
+    /// The customer failed to authenticate the payment
+    AuthenticationFailed,
+    /// The connector did not respond in time, the payment may have been processed by the
+    /// connector
+    ConnectorTimeout,
+    /// The payment failed while it was being processed, like a failed capture or cancellation
+    ProcessingError,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct ErrorDetails {
+    /// Error code returned by the connector
+    #[schema(example = "card_declined")]
+    pub code: Option<String>,
+
+    /// Error message returned by the connector
+    #[schema(example = "Your card was declined")]
+    pub message: Option<String>,
+
+    /// Broad reason for the failure of the payment
+    #[schema(example = "declined")]
+    pub decline_category: DeclineCategory,
+
+    /// The connector which returned the error
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+}
+
 #[derive(Setter, Clone, Default, Debug, PartialEq, serde::Serialize, ToSchema)]
 pub struct PaymentsResponse {
     /// Unique identifier for the payment. This ensures idempotency for multiple payments
@@ -1889,6 +2033,10 @@ pub struct PaymentsResponse {
     #[schema(example = 100)]
     pub amount: i64,
 
+    /// The payment amount in the base unit of the currency, formatted with the number of decimal places of the currency. For example 6540 is "65.40" for USD and "6540" for JPY
+    #[schema(example = "65.40")]
+    pub formatted_amount: Option<String>,
+
     /// The maximum amount that could be captured from the payment
     #[schema(minimum = 100, example = 6540)]
     pub amount_capturable: Option<i64>,
@@ -1897,6 +2045,26 @@ pub struct PaymentsResponse {
     #[schema(minimum = 100, example = 6540)]
     pub amount_received: Option<i64>,
 
+    /// The total amount captured from the payment, which is less than the payment amount when the payment is partially captured
+    #[schema(example = 6540)]
+    pub amount_captured: Option<i64>,
+
+    /// The total amount successfully refunded for the payment, which is less than the captured amount when the payment is partially refunded
+    #[schema(example = 6540)]
+    pub amount_refunded: Option<i64>,
+
+    /// Whether the captured amount of the payment is partially or fully refunded, only refunds which succeeded are considered
+    #[schema(value_type = Option<PaymentRefundStatus>, example = "partially_refunded")]
+    pub refund_status: Option<api_enums::PaymentRefundStatus>,
+
+    /// The shipping cost of the order, included in the payment amount
+    #[schema(example = 500)]
+    pub shipping_amount: Option<i64>,
+
+    /// The handling cost of the order, included in the payment amount
+    #[schema(example = 200)]
+    pub handling_amount: Option<i64>,
+
     /// The connector used for the payment
     #[schema(example = "stripe")]
     pub connector: Option<String>,
@@ -1924,20 +2092,25 @@ pub struct PaymentsResponse {
 
     /// List of refund that happened on this intent
     #[schema(value_type = Option<Vec<RefundResponse>>)]
+    #[auth_based]
     pub refunds: Option<Vec<refunds::RefundResponse>>,
 
     /// List of dispute that happened on this intent
     #[schema(value_type = Option<Vec<DisputeResponsePaymentsRetrieve>>)]
+    #[auth_based]
     pub disputes: Option<Vec<disputes::DisputeResponsePaymentsRetrieve>>,
 
     /// List of attempts that happened on this intent
     #[schema(value_type = Option<Vec<PaymentAttemptResponse>>)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[auth_based]
     pub attempts: Option<Vec<PaymentAttemptResponse>>,
 
-    /// List of captures done on latest attempt
+    /// List of captures done on latest attempt, in the order they were made. Returned by default
+    /// for payments captured in multiple parts, unless `expand_captures` is set to false
     #[schema(value_type = Option<Vec<CaptureResponse>>)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[auth_based]
     pub captures: Option<Vec<CaptureResponse>>,
 
     /// A unique identifier to link the payment to a mandate, can be use instead of payment_method_data
@@ -2010,6 +2183,14 @@ pub struct PaymentsResponse {
     #[schema(example = "https://hyperswitch.io")]
     pub return_url: Option<String>,
 
+    /// The URL to redirect to when the payment succeeds
+    #[schema(example = "https://hyperswitch.io/success")]
+    pub success_url: Option<String>,
+
+    /// The URL to redirect to when the payment fails or is cancelled
+    #[schema(example = "https://hyperswitch.io/failure")]
+    pub failure_url: Option<String>,
+
     /// The transaction authentication can be set to undergo payer authentication. By default, the authentication will be marked as NO_THREE_DS
     #[schema(value_type = Option<AuthenticationType>, example = "no_three_ds", default = "three_ds")]
     pub authentication_type: Option<api_enums::AuthenticationType>,
@@ -2022,9 +2203,18 @@ pub struct PaymentsResponse {
     #[schema(max_length = 255, example = "Payment for shoes purchase")]
     pub statement_descriptor_suffix: Option<String>,
 
+    /// The city of the merchant that customers see on their statements
+    #[schema(max_length = 13, example = "San Francisco")]
+    pub statement_descriptor_city: Option<String>,
+
     /// Additional information required for redirection
     pub next_action: Option<NextActionData>,
 
+    /// Estimated time at which the payment is expected to move on, either because the next action expires or because the payment is synced with the connector. Clients can poll the payment at this time
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub next_action_at: Option<PrimitiveDateTime>,
+
     /// If the payment was cancelled the reason provided here
     pub cancellation_reason: Option<String>,
 
@@ -2036,6 +2226,9 @@ pub struct PaymentsResponse {
     #[schema(example = "Failed while verifying the card")]
     pub error_message: Option<String>,
 
+    /// Details of the error returned by the connector, if the payment failed
+    pub error: Option<ErrorDetails>,
+
     /// Payment Experience for the current payment
     #[schema(value_type = Option<PaymentExperience>, example = "redirect_to_url")]
     pub payment_experience: Option<api_enums::PaymentExperience>,
@@ -2046,16 +2239,20 @@ pub struct PaymentsResponse {
 
     /// The connector used for this payment along with the country and business details
     #[schema(example = "stripe_US_food")]
+    #[auth_based]
     pub connector_label: Option<String>,
 
     /// The business country of merchant for this payment
     #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    #[auth_based]
     pub business_country: Option<api_enums::CountryAlpha2>,
 
     /// The business label of merchant for this payment
+    #[auth_based]
     pub business_label: Option<String>,
 
     /// The business_sub_label for this payment
+    #[auth_based]
     pub business_sub_label: Option<String>,
 
     /// Allowed Payment Method Types for a given PaymentIntent
@@ -2070,36 +2267,91 @@ pub struct PaymentsResponse {
 
     /// A unique identifier for a payment provided by the connector
     #[schema(value_type = Option<String>, example = "993672945374576J")]
+    #[auth_based]
     pub connector_transaction_id: Option<String>,
 
     /// Frm message contains information about the frm response
+    #[auth_based]
     pub frm_message: Option<FrmMessage>,
 
     /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>, example = r#"{ "udf1": "some-value", "udf2": "some-value" }"#)]
+    #[auth_based]
     pub metadata: Option<pii::SecretSerdeValue>,
 
     /// additional data related to some connectors
     #[schema(value_type = Option<ConnectorMetadata>)]
+    #[auth_based]
     pub connector_metadata: Option<serde_json::Value>, // This is Value because it is fetched from DB and before putting in DB the type is validated
 
     /// additional data that might be required by hyperswitch
     #[schema(value_type = Option<FeatureMetadata>)]
+    #[auth_based]
     pub feature_metadata: Option<serde_json::Value>, // This is Value because it is fetched from DB and before putting in DB the type is validated
 
     /// reference to the payment at connector side
     #[schema(value_type = Option<String>, example = "993672945374576J")]
+    #[auth_based]
     pub reference_id: Option<String>,
 
     pub payment_link: Option<PaymentLinkResponse>,
     /// The business profile that is associated with this payment
+    #[auth_based]
     pub profile_id: Option<String>,
 
     /// total number of attempts associated with this payment
     pub attempt_count: i16,
 
     /// Denotes the action(approve or reject) taken by merchant in case of manual review. Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
+    #[auth_based]
     pub merchant_decision: Option<String>,
+
+    /// Indicates whether a saved payment method, referred by a payment token or a mandate, was used for the payment
+    pub used_saved_method: bool,
+
+    /// The ordered list of connectors which were attempted for the payment. Only returned to the merchant
+    #[auth_based]
+    pub connector_routing_trail: Option<Vec<String>>,
+
+    /// The redacted raw request sent to and response received from the connector, stored only if `debug_connector_io` is enabled for the merchant. Only returned to the merchant
+    #[schema(value_type = Option<Object>)]
+    #[auth_based]
+    pub connector_io_log: Option<serde_json::Value>,
+
+    /// The 3DS protocol version negotiated with the issuer for authenticating the payment. Only returned to the merchant
+    #[schema(example = "2.2.0")]
+    #[auth_based]
+    pub three_ds_version: Option<String>,
+
+    /// Whether the liability for fraud chargebacks has shifted to the issuer after 3DS authentication. Only returned to the merchant
+    #[auth_based]
+    pub liability_shift: Option<bool>,
+
+    /// The authorization code issued by the issuer on approving the payment, also known as the approval code. Only returned to the merchant
+    #[schema(example = "A1B2C3")]
+    #[auth_based]
+    pub authorization_code: Option<String>,
+
+    /// The time at which the connector settled the payment, for connectors which report it. Only returned to the merchant
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    #[auth_based]
+    pub settled_at: Option<PrimitiveDateTime>,
+
+    /// The result of the address verification of the card as returned by the issuer, for connectors which report it. Only returned to the merchant
+    #[schema(example = "Y")]
+    #[auth_based]
+    pub avs_result: Option<String>,
+
+    /// The result of the verification of the card security code as returned by the issuer, for connectors which report it. Only returned to the merchant
+    #[schema(example = "M")]
+    #[auth_based]
+    pub cvv_result: Option<String>,
+
+    /// The channel through which the customer made the payment. Only returned to the merchant
+    #[schema(value_type = Option<PaymentChannel>, example = "web")]
+    #[auth_based]
+    pub payment_channel: Option<api_enums::PaymentChannel>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema)]
@@ -2168,6 +2420,16 @@ pub struct PaymentListResponse {
     // The list of payments response objects
     pub data: Vec<PaymentsResponse>,
 }
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, ToSchema)]
+pub struct HostedPaymentPageResponse {
+    /// The identifier for the payment
+    pub payment_id: String,
+    /// Url of the hosted checkout page where the customer can complete the payment
+    pub hosted_page_url: String,
+    /// The customer has to be redirected to the hosted checkout page
+    pub next_action: NextActionData,
+}
+
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct PaymentListResponseV2 {
     /// The number of payments included in the list for given constraints
@@ -2393,6 +2655,8 @@ pub struct PaymentsRetrieveRequest {
     pub merchant_id: Option<String>,
     /// Decider to enable or disable the connector call for retrieve request
     pub force_sync: bool,
+    /// Decider to call the connector even if a recent sync result of the payment is cached
+    pub force_refresh: bool,
     /// The parameters passed to a retrieve request
     pub param: Option<String>,
     /// The name of the connector
@@ -2835,12 +3099,46 @@ pub struct PaymentRetrieveBody {
     pub merchant_id: Option<String>,
     /// Decider to enable or disable the connector call for retrieve request
     pub force_sync: Option<bool>,
+    /// Decider to call the connector even if a recent sync result of the payment is cached
+    pub force_refresh: Option<bool>,
     /// This is a token which expires after 15 minutes, used from the client to authenticate and create sessions from the SDK
     pub client_secret: Option<String>,
     /// If enabled provides list of captures linked to latest attempt
     pub expand_captures: Option<bool>,
     /// If enabled provides list of attempts linked to payment intent
     pub expand_attempts: Option<bool>,
+    /// The related objects to be listed in the response, `expand=attempts` is the same as
+    /// `expand_attempts=true` and `expand=captures` is the same as `expand_captures=true`
+    #[schema(value_type = Option<PaymentRetrieveExpand>, example = "attempts")]
+    pub expand: Option<PaymentRetrieveExpand>,
+}
+
+/// The related objects of a payment which can be listed in the retrieve response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentRetrieveExpand {
+    /// All the attempts of the payment, not just the latest one
+    Attempts,
+    /// The captures of the latest attempt
+    Captures,
+}
+
+impl PaymentRetrieveBody {
+    /// The `expand_attempts` flag takes precedence over `expand`
+    pub fn get_expand_attempts(&self) -> Option<bool> {
+        self.expand_attempts.or_else(|| {
+            self.expand
+                .map(|expand| expand == PaymentRetrieveExpand::Attempts)
+        })
+    }
+
+    /// The `expand_captures` flag takes precedence over `expand`
+    pub fn get_expand_captures(&self) -> Option<bool> {
+        self.expand_captures.or_else(|| {
+            self.expand
+                .map(|expand| expand == PaymentRetrieveExpand::Captures)
+        })
+    }
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2881,6 +3179,27 @@ pub struct PaymentsRejectRequest {
     pub payment_id: String,
 }
 
+#[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentsRetryRequest {
+    /// The identifier for the payment
+    #[serde(skip)]
+    pub payment_id: String,
+}
+
+#[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentsStatusPollingRequest {
+    /// The identifier for the payment
+    #[serde(skip)]
+    pub payment_id: String,
+    /// Maximum number of times the payment is synced with the connector, between 1 and 10
+    #[schema(example = 5)]
+    pub max_polls: Option<u32>,
+    /// Delay before the second sync with the connector in milliseconds, between 100 and 5000. The
+    /// delay doubles after every subsequent sync.
+    #[schema(example = 500)]
+    pub interval_in_milliseconds: Option<u64>,
+}
+
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, ToSchema, Clone)]
 pub struct PaymentsStartRequest {
     /// Unique identifier for the payment. This ensures idempotency for multiple payments
@@ -3065,11 +3384,71 @@ pub mod amount {
     }
 }
 
+pub mod currency_code {
+    use std::str::FromStr;
+
+    use serde::{de, Deserialize};
+
+    use super::api_enums;
+
+    // Currency codes are accepted irrespective of their case, the parsed currency is always
+    // serialized as the canonical uppercase ISO 4217 code
+    pub(crate) fn deserialize_option<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<api_enums::Currency>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|currency| {
+                api_enums::Currency::from_str(&currency.to_uppercase()).map_err(|_| {
+                    de::Error::custom(format!(
+                        "invalid value `{currency}`, expected an ISO 4217 currency code"
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
     use super::*;
 
+    #[test]
+    fn test_currency_code_is_normalized_to_uppercase() {
+        let payments_request: PaymentsRequest =
+            serde_json::from_str(r#"{"amount": 6540, "currency": "usd"}"#).unwrap();
+        assert_eq!(payments_request.currency, Some(api_enums::Currency::USD));
+        assert_eq!(
+            payments_request
+                .currency
+                .map(|currency| currency.to_string()),
+            Some("USD".to_string())
+        );
+
+        assert!(serde_json::from_str::<PaymentsRequest>(
+            r#"{"amount": 6540, "currency": "usdollar"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_attempts_are_expanded_with_expand_param() {
+        let retrieve_body: PaymentRetrieveBody =
+            serde_json::from_str(r#"{"expand": "attempts"}"#).unwrap();
+        assert_eq!(retrieve_body.get_expand_attempts(), Some(true));
+        assert_eq!(retrieve_body.get_expand_captures(), Some(false));
+
+        let retrieve_body: PaymentRetrieveBody =
+            serde_json::from_str(r#"{"expand": "attempts", "expand_attempts": false}"#).unwrap();
+        assert_eq!(retrieve_body.get_expand_attempts(), Some(false));
+
+        let retrieve_body: PaymentRetrieveBody = serde_json::from_str("{}").unwrap();
+        assert_eq!(retrieve_body.get_expand_attempts(), None);
+    }
+
     #[test]
     fn test_mandate_type() {
         let mandate_type = MandateType::default();
@@ -374,6 +374,18 @@ impl Currency {
         Ok(amount)
     }
 
+    /// Number of digits after the decimal point of the amount in the base denomination, the
+    /// exponent of the lowest denomination of the currency
+    pub fn number_of_digits_after_decimal_point(self) -> u8 {
+        if self.is_zero_decimal_currency() {
+            0
+        } else if self.is_three_decimal_currency() {
+            3
+        } else {
+            2
+        }
+    }
+
     ///Convert the higher decimal amount to its base absolute units
     pub fn to_currency_lower_unit(&self, amount: String) -> Result<String, ParseFloatError> {
         let amount_f64 = amount.parse::<f64>()?;
@@ -840,6 +852,15 @@ pub enum IntentStatus {
     PartiallyCaptured,
 }
 
+impl IntentStatus {
+    /// Statuses in which no further action can be taken on the payment
+    pub const TERMINAL_STATUSES: [Self; 3] = [Self::Succeeded, Self::Failed, Self::Cancelled];
+
+    pub fn is_terminal_status(self) -> bool {
+        Self::TERMINAL_STATUSES.contains(&self)
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -1778,6 +1799,55 @@ pub enum MerchantDecision {
     AutoRefunded,
 }
 
+/// Status of the manual review of a payment attempt which was flagged by a fraud workflow
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReviewStatus {
+    PendingReview,
+    Approved,
+    Rejected,
+}
+
+/// The channel through which the customer made the payment
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentChannel {
+    /// The payment was made on a website
+    #[default]
+    Web,
+    /// The payment was made in a mobile app
+    Mobile,
+    /// The payment was made at a point of sale terminal
+    Pos,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -47,4 +47,11 @@ pub struct PaymentIntent {
     // Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    pub connector_routing_trail: Option<Vec<String>>,
+    pub statement_descriptor_city: Option<String>,
+    pub shipping_amount: Option<i64>,
+    pub handling_amount: Option<i64>,
+    pub client_secret_expiry: Option<PrimitiveDateTime>,
+    pub success_url: Option<String>,
+    pub failure_url: Option<String>,
 }
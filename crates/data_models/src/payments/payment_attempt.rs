@@ -1,5 +1,6 @@
 use api_models::enums::Connector;
 use common_enums as storage_enums;
+use common_utils::pii;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
@@ -90,6 +91,41 @@ pub trait PaymentAttemptInterface {
         authentication_type: Option<Vec<storage_enums::AuthenticationType>>,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<i64, errors::StorageError>;
+
+    async fn get_total_count_of_attempts_by_payment_method_fingerprint(
+        &self,
+        merchant_id: &str,
+        payment_method_fingerprint: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<i64, errors::StorageError>;
+
+    /// Lists the attempts of the merchant which are flagged for manual review and are yet to be
+    /// reviewed, in the order in which they were made
+    async fn find_attempts_pending_review_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Vec<PaymentAttempt>, errors::StorageError>;
+
+    /// Reserves `amount_to_capture` from the amount capturable of the attempt. The amount is
+    /// decremented only if enough of it remains, so that of the concurrent captures which
+    /// together exceed the authorized amount only the ones which fit are let through. The others
+    /// fail with `ValueNotFound`.
+    async fn decrement_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_capture: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError>;
+
+    /// Releases an amount reserved with `decrement_amount_capturable_with_attempt_id`, when the
+    /// capture it was reserved for could not be made.
+    async fn increment_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_release: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError>;
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -142,6 +178,20 @@ pub struct PaymentAttempt {
     pub connector_response_reference_id: Option<String>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub dcc_details: Option<serde_json::Value>,
+    pub latency_ms: Option<i64>,
+    pub connector_amount: Option<i64>,
+    pub amount_mismatch: Option<bool>,
+    pub avs_result: Option<String>,
+    pub cvv_result: Option<String>,
+    pub payment_method_fingerprint: Option<String>,
+    pub review_status: Option<storage_enums::ReviewStatus>,
+    pub sdk_information: Option<pii::SecretSerdeValue>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    /// Reference of the attempt which is sent to the connector
+    pub connector_request_reference_id: Option<String>,
+    pub installments: Option<serde_json::Value>,
+    pub used_saved_method: Option<bool>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -201,6 +251,20 @@ pub struct PaymentAttemptNew {
     pub multiple_capture_count: Option<i16>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub dcc_details: Option<serde_json::Value>,
+    pub latency_ms: Option<i64>,
+    pub connector_amount: Option<i64>,
+    pub amount_mismatch: Option<bool>,
+    pub avs_result: Option<String>,
+    pub cvv_result: Option<String>,
+    pub payment_method_fingerprint: Option<String>,
+    pub review_status: Option<storage_enums::ReviewStatus>,
+    pub sdk_information: Option<pii::SecretSerdeValue>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    /// Reference of the attempt which is sent to the connector
+    pub connector_request_reference_id: Option<String>,
+    pub installments: Option<serde_json::Value>,
+    pub used_saved_method: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -218,12 +282,16 @@ pub enum PaymentAttemptUpdate {
         business_sub_label: Option<String>,
         amount_to_capture: Option<i64>,
         capture_method: Option<storage_enums::CaptureMethod>,
+        capture_on: Option<PrimitiveDateTime>,
+        installments: Option<serde_json::Value>,
+        used_saved_method: Option<bool>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
         connector: Option<String>,
         straight_through_algorithm: Option<serde_json::Value>,
         amount_capturable: Option<i64>,
+        payment_method_fingerprint: Option<String>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -245,6 +313,13 @@ pub enum PaymentAttemptUpdate {
         error_code: Option<Option<String>>,
         error_message: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        capture_method: Option<storage_enums::CaptureMethod>,
+        capture_on: Option<PrimitiveDateTime>,
+        payment_method_fingerprint: Option<String>,
+        sdk_information: Option<pii::SecretSerdeValue>,
+        payment_channel: Option<storage_enums::PaymentChannel>,
+        installments: Option<serde_json::Value>,
+        used_saved_method: Option<bool>,
     },
     RejectUpdate {
         status: storage_enums::AttemptStatus,
@@ -269,6 +344,11 @@ pub enum PaymentAttemptUpdate {
         error_reason: Option<Option<String>>,
         connector_response_reference_id: Option<String>,
         amount_capturable: Option<i64>,
+        latency_ms: Option<i64>,
+        connector_amount: Option<i64>,
+        amount_mismatch: Option<bool>,
+        avs_result: Option<String>,
+        cvv_result: Option<String>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -290,6 +370,7 @@ pub enum PaymentAttemptUpdate {
         error_message: Option<Option<String>>,
         error_reason: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        latency_ms: Option<i64>,
     },
     MultipleCaptureCountUpdate {
         multiple_capture_count: i16,
@@ -313,6 +394,70 @@ pub enum PaymentAttemptUpdate {
     SurchargeMetadataUpdate {
         surcharge_metadata: Option<serde_json::Value>,
     },
+    DccDetailsUpdate {
+        dcc_details: Option<serde_json::Value>,
+    },
+    ReviewStatusUpdate {
+        review_status: storage_enums::ReviewStatus,
+    },
+}
+
+impl PaymentAttemptUpdate {
+    /// Status the payment attempt is moved to by the update, if the update changes the status
+    pub fn get_status(&self) -> Option<storage_enums::AttemptStatus> {
+        match self {
+            Self::Update { status, .. }
+            | Self::ConfirmUpdate { status, .. }
+            | Self::RejectUpdate { status, .. }
+            | Self::VoidUpdate { status, .. }
+            | Self::ResponseUpdate { status, .. }
+            | Self::UnresolvedResponseUpdate { status, .. }
+            | Self::StatusUpdate { status }
+            | Self::ErrorUpdate { status, .. }
+            | Self::AmountToCaptureUpdate { status, .. }
+            | Self::PreprocessingUpdate { status, .. } => Some(*status),
+            Self::UpdateTrackers { .. }
+            | Self::AuthenticationTypeUpdate { .. }
+            | Self::MultipleCaptureCountUpdate { .. }
+            | Self::SurchargeAmountUpdate { .. }
+            | Self::SurchargeMetadataUpdate { .. }
+            | Self::DccDetailsUpdate { .. }
+            | Self::ReviewStatusUpdate { .. } => None,
+        }
+    }
+
+    /// Amount capturable the payment attempt is updated to, if the update changes it
+    pub fn get_amount_capturable(&self) -> Option<i64> {
+        match self {
+            Self::UpdateTrackers {
+                amount_capturable, ..
+            }
+            | Self::ConfirmUpdate {
+                amount_capturable, ..
+            }
+            | Self::ResponseUpdate {
+                amount_capturable, ..
+            }
+            | Self::ErrorUpdate {
+                amount_capturable, ..
+            } => *amount_capturable,
+            Self::AmountToCaptureUpdate {
+                amount_capturable, ..
+            } => Some(*amount_capturable),
+            Self::Update { .. }
+            | Self::AuthenticationTypeUpdate { .. }
+            | Self::RejectUpdate { .. }
+            | Self::VoidUpdate { .. }
+            | Self::UnresolvedResponseUpdate { .. }
+            | Self::StatusUpdate { .. }
+            | Self::MultipleCaptureCountUpdate { .. }
+            | Self::SurchargeAmountUpdate { .. }
+            | Self::PreprocessingUpdate { .. }
+            | Self::SurchargeMetadataUpdate { .. }
+            | Self::DccDetailsUpdate { .. }
+            | Self::ReviewStatusUpdate { .. } => None,
+        }
+    }
 }
 
 impl ForeignIDRef for PaymentAttempt {
@@ -36,6 +36,33 @@ pub trait PaymentIntentInterface {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError>;
 
+    async fn find_payment_intents_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Vec<PaymentIntent>, errors::StorageError>;
+
+    /// Counts the payment intents of the customer which are not in a terminal status and were
+    /// created at or after `created_after`
+    async fn get_open_payment_intents_count_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<i64, errors::StorageError>;
+
+    /// Removes the client secret of payment intents whose client secret expired before
+    /// `expired_before`, so that they can no longer be authenticated with it. Returns the payment
+    /// intents which were updated.
+    async fn invalidate_expired_client_secrets(
+        &self,
+        expired_before: PrimitiveDateTime,
+    ) -> error_stack::Result<Vec<PaymentIntent>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -104,6 +131,13 @@ pub struct PaymentIntentNew {
     pub merchant_decision: Option<String>,
     pub payment_link_id: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    pub connector_routing_trail: Option<Vec<String>>,
+    pub statement_descriptor_city: Option<String>,
+    pub shipping_amount: Option<i64>,
+    pub handling_amount: Option<i64>,
+    pub client_secret_expiry: Option<PrimitiveDateTime>,
+    pub success_url: Option<String>,
+    pub failure_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +146,7 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         amount_captured: Option<i64>,
         return_url: Option<String>,
+        metadata: Option<pii::SecretSerdeValue>,
     },
     MetadataUpdate {
         metadata: pii::SecretSerdeValue,
@@ -148,6 +183,12 @@ pub enum PaymentIntentUpdate {
         order_details: Option<Vec<pii::SecretSerdeValue>>,
         metadata: Option<pii::SecretSerdeValue>,
         payment_confirm_source: Option<storage_enums::PaymentSource>,
+        connector_routing_trail: Option<Vec<String>>,
+        statement_descriptor_city: Option<String>,
+        shipping_amount: Option<i64>,
+        handling_amount: Option<i64>,
+        success_url: Option<String>,
+        failure_url: Option<String>,
     },
     PaymentAttemptAndAttemptCountUpdate {
         active_attempt_id: String,
@@ -165,6 +206,9 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         merchant_decision: Option<String>,
     },
+    RoutingTrailUpdate {
+        connector_routing_trail: Vec<String>,
+    },
 }
 
 #[derive(Clone, Debug, Default)]
@@ -193,6 +237,12 @@ pub struct PaymentIntentUpdateInternal {
     // Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    pub connector_routing_trail: Option<Vec<String>>,
+    pub statement_descriptor_city: Option<String>,
+    pub shipping_amount: Option<i64>,
+    pub handling_amount: Option<i64>,
+    pub success_url: Option<String>,
+    pub failure_url: Option<String>,
 }
 
 impl PaymentIntentUpdate {
@@ -218,9 +268,36 @@ impl PaymentIntentUpdate {
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
             order_details: internal_update.order_details.or(source.order_details),
+            connector_routing_trail: internal_update
+                .connector_routing_trail
+                .or(source.connector_routing_trail),
+            statement_descriptor_city: internal_update
+                .statement_descriptor_city
+                .or(source.statement_descriptor_city),
+            shipping_amount: internal_update.shipping_amount.or(source.shipping_amount),
+            handling_amount: internal_update.handling_amount.or(source.handling_amount),
+            success_url: internal_update.success_url.or(source.success_url),
+            failure_url: internal_update.failure_url.or(source.failure_url),
             ..source
         }
     }
+
+    /// Status the payment intent is moved to by the update, if the update changes the status
+    pub fn get_status(&self) -> Option<storage_enums::IntentStatus> {
+        match self {
+            Self::ResponseUpdate { status, .. }
+            | Self::MerchantStatusUpdate { status, .. }
+            | Self::PGStatusUpdate { status }
+            | Self::Update { status, .. }
+            | Self::StatusAndAttemptUpdate { status, .. }
+            | Self::RejectUpdate { status, .. } => Some(*status),
+            Self::ReturnUrlUpdate { status, .. } => *status,
+            Self::MetadataUpdate { .. }
+            | Self::PaymentAttemptAndAttemptCountUpdate { .. }
+            | Self::ApproveUpdate { .. }
+            | Self::RoutingTrailUpdate { .. } => None,
+        }
+    }
 }
 
 impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
@@ -243,6 +320,12 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -261,6 +344,12 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
                 ..Default::default()
             },
             PaymentIntentUpdate::MetadataUpdate { metadata } => Self {
@@ -306,6 +395,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 amount_captured,
                 // customer_id,
                 return_url,
+                metadata,
             } => Self {
                 // amount,
                 // currency: Some(currency),
@@ -313,6 +403,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 amount_captured,
                 // customer_id,
                 return_url,
+                metadata,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
@@ -346,6 +437,13 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 merchant_decision,
                 ..Default::default()
             },
+            PaymentIntentUpdate::RoutingTrailUpdate {
+                connector_routing_trail,
+            } => Self {
+                connector_routing_trail: Some(connector_routing_trail),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
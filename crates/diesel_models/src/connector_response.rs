@@ -19,6 +19,12 @@ pub struct ConnectorResponseNew {
     pub connector_transaction_id: Option<String>,
     pub authentication_data: Option<serde_json::Value>,
     pub encoded_data: Option<String>,
+    pub connector_io_log: Option<serde_json::Value>,
+    pub three_ds_version: Option<String>,
+    pub liability_shift: Option<bool>,
+    pub authorization_code: Option<String>,
+    pub settled_at: Option<PrimitiveDateTime>,
+    pub challenge_required: Option<bool>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
@@ -36,6 +42,12 @@ pub struct ConnectorResponse {
     pub connector_transaction_id: Option<String>,
     pub authentication_data: Option<serde_json::Value>,
     pub encoded_data: Option<String>,
+    pub connector_io_log: Option<serde_json::Value>,
+    pub three_ds_version: Option<String>,
+    pub liability_shift: Option<bool>,
+    pub authorization_code: Option<String>,
+    pub settled_at: Option<PrimitiveDateTime>,
+    pub challenge_required: Option<bool>,
 }
 
 #[derive(Clone, Default, Debug, Deserialize, AsChangeset, Serialize)]
@@ -46,6 +58,12 @@ pub struct ConnectorResponseUpdateInternal {
     pub modified_at: Option<PrimitiveDateTime>,
     pub encoded_data: Option<String>,
     pub connector_name: Option<String>,
+    pub connector_io_log: Option<serde_json::Value>,
+    pub three_ds_version: Option<String>,
+    pub liability_shift: Option<bool>,
+    pub authorization_code: Option<String>,
+    pub settled_at: Option<PrimitiveDateTime>,
+    pub challenge_required: Option<bool>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -55,9 +73,16 @@ pub enum ConnectorResponseUpdate {
         authentication_data: Option<serde_json::Value>,
         encoded_data: Option<String>,
         connector_name: Option<String>,
+        connector_io_log: Option<serde_json::Value>,
+        three_ds_version: Option<String>,
+        liability_shift: Option<bool>,
+        authorization_code: Option<String>,
+        settled_at: Option<PrimitiveDateTime>,
+        challenge_required: Option<bool>,
     },
     ErrorUpdate {
         connector_name: Option<String>,
+        connector_io_log: Option<serde_json::Value>,
     },
 }
 
@@ -80,6 +105,22 @@ impl ConnectorResponseUpdate {
             encoded_data: connector_response_update
                 .encoded_data
                 .or(source.encoded_data),
+            connector_io_log: connector_response_update
+                .connector_io_log
+                .or(source.connector_io_log),
+            three_ds_version: connector_response_update
+                .three_ds_version
+                .or(source.three_ds_version),
+            liability_shift: connector_response_update
+                .liability_shift
+                .or(source.liability_shift),
+            authorization_code: connector_response_update
+                .authorization_code
+                .or(source.authorization_code),
+            settled_at: connector_response_update.settled_at.or(source.settled_at),
+            challenge_required: connector_response_update
+                .challenge_required
+                .or(source.challenge_required),
             ..source
         }
     }
@@ -93,15 +134,31 @@ impl From<ConnectorResponseUpdate> for ConnectorResponseUpdateInternal {
                 authentication_data,
                 encoded_data,
                 connector_name,
+                connector_io_log,
+                three_ds_version,
+                liability_shift,
+                authorization_code,
+                settled_at,
+                challenge_required,
             } => Self {
                 connector_transaction_id,
                 authentication_data,
                 encoded_data,
                 modified_at: Some(common_utils::date_time::now()),
                 connector_name,
+                connector_io_log,
+                three_ds_version,
+                liability_shift,
+                authorization_code,
+                settled_at,
+                challenge_required,
             },
-            ConnectorResponseUpdate::ErrorUpdate { connector_name } => Self {
+            ConnectorResponseUpdate::ErrorUpdate {
                 connector_name,
+                connector_io_log,
+            } => Self {
+                connector_name,
+                connector_io_log,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Self::default()
             },
@@ -40,6 +40,7 @@ pub struct MerchantAccount {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
+    pub debug_connector_io: bool,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -69,6 +70,7 @@ pub struct MerchantAccountNew {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
+    pub debug_connector_io: bool,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -97,4 +99,5 @@ pub struct MerchantAccountUpdateInternal {
     pub is_recon_enabled: bool,
     pub default_profile: Option<Option<String>>,
     pub recon_status: storage_enums::ReconStatus,
+    pub debug_connector_io: Option<bool>,
 }
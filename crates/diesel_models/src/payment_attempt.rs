@@ -1,3 +1,4 @@
+use common_utils::pii;
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
@@ -58,6 +59,19 @@ pub struct PaymentAttempt {
     pub connector_response_reference_id: Option<String>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub latency_ms: Option<i64>,
+    pub connector_amount: Option<i64>,
+    pub amount_mismatch: Option<bool>,
+    pub dcc_details: Option<serde_json::Value>,
+    pub connector_request_reference_id: Option<String>,
+    pub avs_result: Option<String>,
+    pub cvv_result: Option<String>,
+    pub payment_method_fingerprint: Option<String>,
+    pub review_status: Option<storage_enums::ReviewStatus>,
+    pub sdk_information: Option<pii::SecretSerdeValue>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub installments: Option<serde_json::Value>,
+    pub used_saved_method: Option<bool>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Queryable, Serialize, Deserialize)]
@@ -117,6 +131,19 @@ pub struct PaymentAttemptNew {
     pub multiple_capture_count: Option<i16>,
     pub amount_capturable: i64,
     pub surcharge_metadata: Option<serde_json::Value>,
+    pub latency_ms: Option<i64>,
+    pub connector_amount: Option<i64>,
+    pub amount_mismatch: Option<bool>,
+    pub dcc_details: Option<serde_json::Value>,
+    pub connector_request_reference_id: Option<String>,
+    pub avs_result: Option<String>,
+    pub cvv_result: Option<String>,
+    pub payment_method_fingerprint: Option<String>,
+    pub review_status: Option<storage_enums::ReviewStatus>,
+    pub sdk_information: Option<pii::SecretSerdeValue>,
+    pub payment_channel: Option<storage_enums::PaymentChannel>,
+    pub installments: Option<serde_json::Value>,
+    pub used_saved_method: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,12 +161,16 @@ pub enum PaymentAttemptUpdate {
         business_sub_label: Option<String>,
         amount_to_capture: Option<i64>,
         capture_method: Option<storage_enums::CaptureMethod>,
+        capture_on: Option<PrimitiveDateTime>,
+        installments: Option<serde_json::Value>,
+        used_saved_method: Option<bool>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
         connector: Option<String>,
         straight_through_algorithm: Option<serde_json::Value>,
         amount_capturable: Option<i64>,
+        payment_method_fingerprint: Option<String>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -161,6 +192,13 @@ pub enum PaymentAttemptUpdate {
         error_code: Option<Option<String>>,
         error_message: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        capture_method: Option<storage_enums::CaptureMethod>,
+        capture_on: Option<PrimitiveDateTime>,
+        payment_method_fingerprint: Option<String>,
+        sdk_information: Option<pii::SecretSerdeValue>,
+        payment_channel: Option<storage_enums::PaymentChannel>,
+        installments: Option<serde_json::Value>,
+        used_saved_method: Option<bool>,
     },
     VoidUpdate {
         status: storage_enums::AttemptStatus,
@@ -185,6 +223,11 @@ pub enum PaymentAttemptUpdate {
         error_reason: Option<Option<String>>,
         connector_response_reference_id: Option<String>,
         amount_capturable: Option<i64>,
+        latency_ms: Option<i64>,
+        connector_amount: Option<i64>,
+        amount_mismatch: Option<bool>,
+        avs_result: Option<String>,
+        cvv_result: Option<String>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -206,6 +249,7 @@ pub enum PaymentAttemptUpdate {
         error_message: Option<Option<String>>,
         error_reason: Option<Option<String>>,
         amount_capturable: Option<i64>,
+        latency_ms: Option<i64>,
     },
     MultipleCaptureCountUpdate {
         multiple_capture_count: i16,
@@ -229,6 +273,12 @@ pub enum PaymentAttemptUpdate {
     SurchargeMetadataUpdate {
         surcharge_metadata: Option<serde_json::Value>,
     },
+    DccDetailsUpdate {
+        dcc_details: Option<serde_json::Value>,
+    },
+    ReviewStatusUpdate {
+        review_status: storage_enums::ReviewStatus,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -259,12 +309,25 @@ pub struct PaymentAttemptUpdateInternal {
     preprocessing_step_id: Option<String>,
     error_reason: Option<Option<String>>,
     capture_method: Option<storage_enums::CaptureMethod>,
+    capture_on: Option<PrimitiveDateTime>,
     connector_response_reference_id: Option<String>,
     multiple_capture_count: Option<i16>,
     surcharge_amount: Option<i64>,
     tax_amount: Option<i64>,
     amount_capturable: Option<i64>,
     surcharge_metadata: Option<serde_json::Value>,
+    latency_ms: Option<i64>,
+    connector_amount: Option<i64>,
+    amount_mismatch: Option<bool>,
+    avs_result: Option<String>,
+    cvv_result: Option<String>,
+    payment_method_fingerprint: Option<String>,
+    dcc_details: Option<serde_json::Value>,
+    review_status: Option<storage_enums::ReviewStatus>,
+    sdk_information: Option<pii::SecretSerdeValue>,
+    payment_channel: Option<storage_enums::PaymentChannel>,
+    installments: Option<serde_json::Value>,
+    used_saved_method: Option<bool>,
 }
 
 impl PaymentAttemptUpdate {
@@ -280,6 +343,7 @@ impl PaymentAttemptUpdate {
                 .or(pa_update.connector_transaction_id),
             authentication_type: pa_update.authentication_type.or(source.authentication_type),
             payment_method: pa_update.payment_method.or(source.payment_method),
+            capture_method: pa_update.capture_method.or(source.capture_method),
             error_message: pa_update.error_message.unwrap_or(source.error_message),
             payment_method_id: pa_update
                 .payment_method_id
@@ -292,6 +356,21 @@ impl PaymentAttemptUpdate {
                 .preprocessing_step_id
                 .or(source.preprocessing_step_id),
             surcharge_metadata: pa_update.surcharge_metadata.or(source.surcharge_metadata),
+            dcc_details: pa_update.dcc_details.or(source.dcc_details),
+            latency_ms: pa_update.latency_ms.or(source.latency_ms),
+            connector_amount: pa_update.connector_amount.or(source.connector_amount),
+            amount_mismatch: pa_update.amount_mismatch.or(source.amount_mismatch),
+            avs_result: pa_update.avs_result.or(source.avs_result),
+            cvv_result: pa_update.cvv_result.or(source.cvv_result),
+            capture_on: pa_update.capture_on.or(source.capture_on),
+            payment_method_fingerprint: pa_update
+                .payment_method_fingerprint
+                .or(source.payment_method_fingerprint),
+            review_status: pa_update.review_status.or(source.review_status),
+            sdk_information: pa_update.sdk_information.or(source.sdk_information),
+            payment_channel: pa_update.payment_channel.or(source.payment_channel),
+            installments: pa_update.installments.or(source.installments),
+            used_saved_method: pa_update.used_saved_method.or(source.used_saved_method),
             ..source
         }
     }
@@ -314,6 +393,9 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -329,6 +411,9 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
                 ..Default::default()
             },
             PaymentAttemptUpdate::AuthenticationTypeUpdate {
@@ -355,6 +440,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -373,6 +465,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
                 ..Default::default()
             },
             PaymentAttemptUpdate::VoidUpdate {
@@ -407,6 +506,11 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
             } => Self {
                 status: Some(status),
                 connector,
@@ -422,6 +526,11 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
                 ..Default::default()
             },
             PaymentAttemptUpdate::ErrorUpdate {
@@ -431,6 +540,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 error_message,
                 error_reason,
                 amount_capturable,
+                latency_ms,
             } => Self {
                 connector,
                 status: Some(status),
@@ -439,6 +549,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 modified_at: Some(common_utils::date_time::now()),
                 error_reason,
                 amount_capturable,
+                latency_ms,
                 ..Default::default()
             },
             PaymentAttemptUpdate::StatusUpdate { status } => Self {
@@ -450,11 +561,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
             } => Self {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
                 ..Default::default()
             },
             PaymentAttemptUpdate::UnresolvedResponseUpdate {
@@ -513,6 +626,15 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 surcharge_metadata,
                 ..Default::default()
             },
+            PaymentAttemptUpdate::DccDetailsUpdate { dcc_details } => Self {
+                dcc_details,
+                ..Default::default()
+            },
+            PaymentAttemptUpdate::ReviewStatusUpdate { review_status } => Self {
+                review_status: Some(review_status),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
             PaymentAttemptUpdate::SurchargeAmountUpdate {
                 surcharge_amount,
                 tax_amount,
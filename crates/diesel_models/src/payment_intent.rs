@@ -48,6 +48,14 @@ pub struct PaymentIntent {
     pub merchant_decision: Option<String>,
     pub payment_link_id: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub connector_routing_trail: Option<Vec<String>>,
+    pub statement_descriptor_city: Option<String>,
+    pub shipping_amount: Option<i64>,
+    pub handling_amount: Option<i64>,
+    pub client_secret_expiry: Option<PrimitiveDateTime>,
+    pub success_url: Option<String>,
+    pub failure_url: Option<String>,
 }
 
 #[derive(
@@ -100,6 +108,14 @@ pub struct PaymentIntentNew {
     pub merchant_decision: Option<String>,
     pub payment_link_id: Option<String>,
     pub payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub connector_routing_trail: Option<Vec<String>>,
+    pub statement_descriptor_city: Option<String>,
+    pub shipping_amount: Option<i64>,
+    pub handling_amount: Option<i64>,
+    pub client_secret_expiry: Option<PrimitiveDateTime>,
+    pub success_url: Option<String>,
+    pub failure_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,6 +124,7 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         amount_captured: Option<i64>,
         return_url: Option<String>,
+        metadata: Option<pii::SecretSerdeValue>,
     },
     MetadataUpdate {
         metadata: pii::SecretSerdeValue,
@@ -144,6 +161,12 @@ pub enum PaymentIntentUpdate {
         order_details: Option<Vec<pii::SecretSerdeValue>>,
         metadata: Option<pii::SecretSerdeValue>,
         payment_confirm_source: Option<storage_enums::PaymentSource>,
+        connector_routing_trail: Option<Vec<String>>,
+        statement_descriptor_city: Option<String>,
+        shipping_amount: Option<i64>,
+        handling_amount: Option<i64>,
+        success_url: Option<String>,
+        failure_url: Option<String>,
     },
     PaymentAttemptAndAttemptCountUpdate {
         active_attempt_id: String,
@@ -161,6 +184,9 @@ pub enum PaymentIntentUpdate {
         status: storage_enums::IntentStatus,
         merchant_decision: Option<String>,
     },
+    RoutingTrailUpdate {
+        connector_routing_trail: Vec<String>,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -190,6 +216,13 @@ pub struct PaymentIntentUpdateInternal {
     pub profile_id: Option<String>,
     merchant_decision: Option<String>,
     payment_confirm_source: Option<storage_enums::PaymentSource>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    connector_routing_trail: Option<Vec<String>>,
+    statement_descriptor_city: Option<String>,
+    shipping_amount: Option<i64>,
+    handling_amount: Option<i64>,
+    success_url: Option<String>,
+    failure_url: Option<String>,
 }
 
 impl PaymentIntentUpdate {
@@ -215,6 +248,16 @@ impl PaymentIntentUpdate {
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
             order_details: internal_update.order_details.or(source.order_details),
+            connector_routing_trail: internal_update
+                .connector_routing_trail
+                .or(source.connector_routing_trail),
+            statement_descriptor_city: internal_update
+                .statement_descriptor_city
+                .or(source.statement_descriptor_city),
+            shipping_amount: internal_update.shipping_amount.or(source.shipping_amount),
+            handling_amount: internal_update.handling_amount.or(source.handling_amount),
+            success_url: internal_update.success_url.or(source.success_url),
+            failure_url: internal_update.failure_url.or(source.failure_url),
             ..source
         }
     }
@@ -240,6 +283,12 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -258,6 +307,12 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
                 ..Default::default()
             },
             PaymentIntentUpdate::MetadataUpdate { metadata } => Self {
@@ -303,6 +358,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 amount_captured,
                 // customer_id,
                 return_url,
+                metadata,
             } => Self {
                 // amount,
                 // currency: Some(currency),
@@ -310,6 +366,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 amount_captured,
                 // customer_id,
                 return_url,
+                metadata,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
@@ -343,6 +400,13 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 merchant_decision,
                 ..Default::default()
             },
+            PaymentIntentUpdate::RoutingTrailUpdate {
+                connector_routing_trail,
+            } => Self {
+                connector_routing_trail: Some(connector_routing_trail),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
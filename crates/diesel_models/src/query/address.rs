@@ -127,10 +127,14 @@ impl Address {
         .await
         {
             Err(error) => match error.current_context() {
+                // Addresses created before the payment_id was stored are looked up without it,
+                // but still only among the addresses of the merchant
                 errors::DatabaseError::NotFound => {
-                    generics::generic_find_by_id::<<Self as HasTable>::Table, _, _>(
+                    generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
                         conn,
-                        address_id.to_owned(),
+                        dsl::merchant_id
+                            .eq(merchant_id.to_owned())
+                            .and(dsl::address_id.eq(address_id.to_owned())),
                     )
                     .await
                 }
@@ -104,7 +104,10 @@ impl PaymentAttempt {
             dsl::payment_id
                 .eq(payment_id.to_owned())
                 .and(dsl::merchant_id.eq(merchant_id.to_owned()))
-                .and(dsl::status.eq(enums::AttemptStatus::Charged)),
+                .and(dsl::status.eq_any(vec![
+                    enums::AttemptStatus::Charged,
+                    enums::AttemptStatus::PartialCharged,
+                ])),
             None,
             None,
             None,
@@ -189,19 +192,16 @@ impl PaymentAttempt {
         merchant_id: &str,
         payment_id: &str,
     ) -> StorageResult<Vec<Self>> {
-        generics::generic_filter::<
-            <Self as HasTable>::Table,
-            _,
-            <<Self as HasTable>::Table as Table>::PrimaryKey,
-            _,
-        >(
+        // Attempts are returned in the order in which they were made, so that the history of the
+        // payment can be followed
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
             conn,
             dsl::merchant_id
                 .eq(merchant_id.to_owned())
                 .and(dsl::payment_id.eq(payment_id.to_owned())),
             None,
             None,
-            None,
+            Some(dsl::created_at.asc()),
         )
         .await
     }
@@ -347,4 +347,90 @@ impl PaymentAttempt {
         .change_context(errors::DatabaseError::Others)
         .attach_printable("Error filtering count of payments")
     }
+
+    pub async fn get_total_count_of_attempts_by_payment_method_fingerprint(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_method_fingerprint: &str,
+    ) -> StorageResult<i64> {
+        let filter = <Self as HasTable>::table()
+            .count()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::payment_method_fingerprint.eq(payment_method_fingerprint.to_owned()));
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_result_async::<i64>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error counting payment attempts by payment method fingerprint")
+    }
+
+    pub async fn find_pending_review_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::review_status.eq(enums::ReviewStatus::PendingReview)),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn decrement_amount_capturable_with_attempt_id(
+        self,
+        conn: &PgPooledConn,
+        amount_to_capture: i64,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::attempt_id
+                .eq(self.attempt_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned()))
+                .and(dsl::amount_capturable.ge(amount_to_capture)),
+            (
+                dsl::amount_capturable.eq(dsl::amount_capturable - amount_to_capture),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn increment_amount_capturable_with_attempt_id(
+        self,
+        conn: &PgPooledConn,
+        amount_to_release: i64,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::attempt_id
+                .eq(self.attempt_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned())),
+            (
+                dsl::amount_capturable.eq(dsl::amount_capturable + amount_to_release),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
 }
@@ -1,8 +1,12 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
-use super::generics;
+use super::generics::{self, db_metrics};
 use crate::{
+    enums::IntentStatus,
     errors,
     payment_intent::{
         PaymentIntent, PaymentIntentNew, PaymentIntentUpdate, PaymentIntentUpdateInternal,
@@ -73,4 +77,68 @@ impl PaymentIntent {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_customer_id_merchant_id(
+        conn: &PgPooledConn,
+        customer_id: &str,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::customer_id.eq(customer_id.to_owned())),
+            limit,
+            offset,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn count_open_by_customer_id_merchant_id(
+        conn: &PgPooledConn,
+        customer_id: &str,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+    ) -> StorageResult<i64> {
+        let filter = <Self as HasTable>::table()
+            .count()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::customer_id.eq(customer_id.to_owned()))
+            .filter(dsl::created_at.ge(created_after))
+            .filter(dsl::status.ne_all(IntentStatus::TERMINAL_STATUSES.to_vec()));
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_result_async::<i64>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error counting open payment intents of the customer")
+    }
+
+    /// Removes the client secret of every payment intent whose client secret expired before
+    /// `expired_before`, returns the payment intents updated
+    #[instrument(skip(conn))]
+    pub async fn invalidate_expired_client_secrets(
+        conn: &PgPooledConn,
+        expired_before: PrimitiveDateTime,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::client_secret_expiry
+                .lt(expired_before)
+                .and(dsl::client_secret.is_not_null()),
+            (
+                dsl::client_secret.eq(None::<String>),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
 }
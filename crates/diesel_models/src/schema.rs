@@ -175,6 +175,14 @@ diesel::table! {
         connector_transaction_id -> Nullable<Varchar>,
         authentication_data -> Nullable<Json>,
         encoded_data -> Nullable<Text>,
+        connector_io_log -> Nullable<Json>,
+        #[max_length = 16]
+        three_ds_version -> Nullable<Varchar>,
+        liability_shift -> Nullable<Bool>,
+        #[max_length = 64]
+        authorization_code -> Nullable<Varchar>,
+        settled_at -> Nullable<Timestamp>,
+        challenge_required -> Nullable<Bool>,
     }
 }
 
@@ -442,6 +450,7 @@ diesel::table! {
         #[max_length = 64]
         default_profile -> Nullable<Varchar>,
         recon_status -> ReconStatus,
+        debug_connector_io -> Bool,
     }
 }
 
@@ -555,6 +564,25 @@ diesel::table! {
         connector_response_reference_id -> Nullable<Varchar>,
         amount_capturable -> Int8,
         surcharge_metadata -> Nullable<Jsonb>,
+        latency_ms -> Nullable<Int8>,
+        connector_amount -> Nullable<Int8>,
+        amount_mismatch -> Nullable<Bool>,
+        dcc_details -> Nullable<Jsonb>,
+        #[max_length = 255]
+        connector_request_reference_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        avs_result -> Nullable<Varchar>,
+        #[max_length = 64]
+        cvv_result -> Nullable<Varchar>,
+        #[max_length = 64]
+        payment_method_fingerprint -> Nullable<Varchar>,
+        #[max_length = 32]
+        review_status -> Nullable<Varchar>,
+        sdk_information -> Nullable<Jsonb>,
+        #[max_length = 32]
+        payment_channel -> Nullable<Varchar>,
+        installments -> Nullable<Jsonb>,
+        used_saved_method -> Nullable<Bool>,
     }
 }
 
@@ -613,6 +641,16 @@ diesel::table! {
         #[max_length = 255]
         payment_link_id -> Nullable<Varchar>,
         payment_confirm_source -> Nullable<PaymentSource>,
+        connector_routing_trail -> Nullable<Array<Nullable<Text>>>,
+        #[max_length = 255]
+        statement_descriptor_city -> Nullable<Varchar>,
+        shipping_amount -> Nullable<Int8>,
+        handling_amount -> Nullable<Int8>,
+        client_secret_expiry -> Nullable<Timestamp>,
+        #[max_length = 255]
+        success_url -> Nullable<Varchar>,
+        #[max_length = 255]
+        failure_url -> Nullable<Varchar>,
     }
 }
 
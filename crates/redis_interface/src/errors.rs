@@ -16,6 +16,8 @@ pub enum RedisError {
     GetFailed,
     #[error("Failed to delete key value in Redis")]
     DeleteFailed,
+    #[error("Failed to increment key value in Redis")]
+    IncrementFailed,
     #[error("Failed to append entry to Redis stream")]
     StreamAppendFailed,
     #[error("Failed to read entries from Redis stream")]
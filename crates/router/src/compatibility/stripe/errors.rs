@@ -239,6 +239,14 @@ pub enum StripeErrorCode {
     PaymentLinkNotFound,
     #[error(error_type = StripeErrorType::HyperswitchError, code = "", message = "Resource Busy. Please try again later")]
     LockTimeout,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "rate_limit", message = "Too many requests hit the API too quickly. Please try again later")]
+    RateLimited,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "mandate_revoked", message = "The mandate has been revoked and cannot be used for payments")]
+    MandateRevoked,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "mandate_expired", message = "The mandate has expired and cannot be used for payments")]
+    MandateExpired,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "Keys for idempotent requests can only be used with the same parameters they were first used with")]
+    IdempotencyKeyReused,
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -590,6 +598,10 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 Self::PaymentMethodUnactivated
             }
             errors::ApiErrorResponse::ResourceBusy => Self::PaymentMethodUnactivated,
+            errors::ApiErrorResponse::RateLimited => Self::RateLimited,
+            errors::ApiErrorResponse::MandateRevoked => Self::MandateRevoked, // not a stripe code
+            errors::ApiErrorResponse::MandateExpired => Self::MandateExpired, // not a stripe code
+            errors::ApiErrorResponse::IdempotencyKeyReused => Self::IdempotencyKeyReused,
         }
     }
 }
@@ -656,6 +668,8 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::FileProviderNotSupported
             | Self::CurrencyNotSupported { .. }
             | Self::DuplicateCustomer
+            | Self::MandateRevoked
+            | Self::MandateExpired
             | Self::PaymentMethodUnactivated => StatusCode::BAD_REQUEST,
             Self::RefundFailed
             | Self::PayoutFailed
@@ -669,6 +683,8 @@ impl actix_web::ResponseError for StripeErrorCode {
                 StatusCode::from_u16(*status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
             Self::LockTimeout => StatusCode::LOCKED,
+            Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Self::IdempotencyKeyReused => StatusCode::CONFLICT,
         }
     }
 
@@ -77,6 +77,7 @@ pub async fn payment_intents_retrieve(
         resource_id: api_types::PaymentIdType::PaymentIntentId(path.to_string()),
         merchant_id: None,
         force_sync: true,
+        force_refresh: false,
         connector: None,
         param: None,
         merchant_connector_details: None,
@@ -84,6 +84,42 @@ impl Default for super::settings::Refund {
     }
 }
 
+impl Default for super::settings::ConnectorRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_in_milliseconds: 100,
+        }
+    }
+}
+
+impl Default for super::settings::RateLimit {
+    fn default() -> Self {
+        Self {
+            max_requests: 100,
+            window_in_seconds: 10,
+        }
+    }
+}
+
+impl Default for super::settings::PsyncCache {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_in_seconds: 5,
+        }
+    }
+}
+
+impl Default for super::settings::OpenPaymentIntentsLimit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_open_intents_per_customer: 10,
+        }
+    }
+}
+
 impl Default for super::settings::EphemeralConfig {
     fn default() -> Self {
         Self { validity: 1 }
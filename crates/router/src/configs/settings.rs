@@ -69,6 +69,10 @@ pub struct Settings {
     pub locker: Locker,
     pub connectors: Connectors,
     pub refund: Refund,
+    pub connector_retry: ConnectorRetry,
+    pub payment_create_rate_limit: PaymentCreateRateLimit,
+    pub psync_cache: PsyncCache,
+    pub open_payment_intents_limit: OpenPaymentIntentsLimit,
     pub eph_key: EphemeralConfig,
     pub scheduler: Option<SchedulerSettings>,
     #[cfg(feature = "kv_store")]
@@ -97,6 +101,20 @@ pub struct Settings {
     pub payouts: Payouts,
     pub applepay_decrypt_keys: ApplePayDecryptConifg,
     pub multiple_api_version_supported_connectors: MultipleApiVersionSupportedConnectors,
+    pub date_only_capture_on_connectors: DateOnlyCaptureOnConnectors,
+    pub installment_supported_connectors: InstallmentSupportedConnectors,
+    pub capture_reversal_supported_connectors: CaptureReversalSupportedConnectors,
+    pub card_holder_name_required_connectors: CardHolderNameRequiredConnectors,
+    pub dcc_supported_connectors: DccSupportedConnectors,
+    pub connector_auth_hold_window: ConnectorAuthHoldWindow,
+    pub connector_amount_limits: ConnectorAmountLimits,
+    pub minimum_three_ds_version: MinimumThreeDsVersion,
+    pub connector_default_authentication_type: ConnectorDefaultAuthenticationType,
+    pub risk_data_supported_connectors: RiskDataSupportedConnectors,
+    pub response_masking_policy: ResponseMaskingPolicyConfig,
+    pub amount_rounding: AmountRounding,
+    pub hosted_payment_page: HostedPaymentPage,
+    pub card_fingerprint: CardFingerprint,
     pub applepay_merchant_configs: ApplepayMerchantConfigs,
     pub lock_settings: LockSettings,
     pub temp_locker_enable_config: TempLockerEnableConfig,
@@ -108,6 +126,20 @@ pub struct PaymentLink {
     pub sdk_url: String,
 }
 
+/// Checkout page hosted for merchants who do not have their own payment page
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct HostedPaymentPage {
+    pub base_url: String,
+}
+
+/// Fingerprints identify the same card across payments and customers without storing the card
+/// number
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CardFingerprint {
+    /// Key used for calculating the fingerprints of cards
+    pub hash_key: Password,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct ApplepayMerchantConfigs {
@@ -123,6 +155,145 @@ pub struct MultipleApiVersionSupportedConnectors {
     pub supported_connectors: HashSet<api_models::enums::Connector>,
 }
 
+/// Connectors which schedule delayed captures by date only, `capture_on` is truncated to the
+/// start of the day for these connectors
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DateOnlyCaptureOnConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Connectors which support splitting the payment amount into installments
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct InstallmentSupportedConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Connectors which support reversing a capture before it is settled
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CaptureReversalSupportedConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Connectors which require the card holder name for address verification (AVS)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CardHolderNameRequiredConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Connectors which support capturing the amount in the customer's currency through dynamic
+/// currency conversion (DCC)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DccSupportedConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Connectors which accept risk data from an external fraud tool along with the payment, risk
+/// data is not forwarded to any other connector
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RiskDataSupportedConnectors {
+    #[serde(deserialize_with = "connector_deser")]
+    pub connector_list: HashSet<api_models::enums::Connector>,
+}
+
+/// Maximum number of days an authorization is held by the connector, keyed by the connector
+/// name. Captures cannot be scheduled beyond this window as the authorization would have expired.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct ConnectorAuthHoldWindow(pub HashMap<String, u16>);
+
+/// Per transaction amount limits of connectors, keyed by the connector name and the currency
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct ConnectorAmountLimits(
+    pub HashMap<String, HashMap<api_models::enums::Currency, AmountLimit>>,
+);
+
+/// Authentication type applied to payments which do not specify one, keyed by the connector name.
+/// Payments routed to connectors which are not configured default to `no_three_ds`.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct ConnectorDefaultAuthenticationType(
+    pub HashMap<String, api_models::enums::AuthenticationType>,
+);
+
+/// Minimum 3DS protocol version accepted for payments authenticated through a connector, keyed by
+/// the connector name
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct MinimumThreeDsVersion(pub HashMap<String, ConnectorMinimumThreeDsVersion>);
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorMinimumThreeDsVersion {
+    /// Version applied to the business countries which are not configured in `countries`
+    pub default: Option<String>,
+    /// Versions keyed by the business country of the payment
+    pub countries: HashMap<api_models::enums::CountryAlpha2, String>,
+}
+
+/// Minimum and maximum amount of a payment, in the lowest denomination of the currency
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct AmountLimit {
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+}
+
+/// Card details returned in the payments response of a merchant
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ResponseMaskingPolicyConfig {
+    /// Policy applied to the merchants which are not configured in `merchant_policies`
+    pub default_policy: ResponseMaskingPolicy,
+    /// Policies of merchants, keyed by merchant_id
+    pub merchant_policies: HashMap<String, ResponseMaskingPolicy>,
+}
+
+impl ResponseMaskingPolicyConfig {
+    pub fn get_policy(&self, merchant_id: &str) -> ResponseMaskingPolicy {
+        self.merchant_policies
+            .get(merchant_id)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseMaskingPolicy {
+    /// No card details are returned
+    #[default]
+    Minimal,
+    /// Only the last four digits and the network of the card are returned
+    Last4,
+    /// Every card detail stored for the payment is returned
+    Full,
+}
+
+/// Rounding applied when an amount in the lowest denomination of the currency is computed from a
+/// fractional value, like when an amount is converted with an exchange rate
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AmountRounding {
+    pub mode: RoundingMode,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Values halfway between two amounts are rounded away from zero
+    #[default]
+    HalfUp,
+    /// Values halfway between two amounts are rounded to the even amount, also known as banker's
+    /// rounding
+    HalfEven,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 #[serde(transparent)]
 pub struct TokenizationConfig(pub HashMap<String, PaymentMethodTokenFilter>);
@@ -421,6 +592,60 @@ pub struct Refund {
     pub max_age: i64,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorRetry {
+    pub max_attempts: u32,
+    pub base_delay_in_milliseconds: u64,
+}
+
+/// Rate limit applied per merchant on payment create, counted in Redis across all the
+/// application instances
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PaymentCreateRateLimit {
+    pub enabled: bool,
+    pub default_limit: RateLimit,
+    /// Limits overriding the default limit, keyed by merchant_id
+    pub merchant_limits: HashMap<String, RateLimit>,
+}
+
+impl PaymentCreateRateLimit {
+    pub fn get_limit(&self, merchant_id: &str) -> &RateLimit {
+        self.merchant_limits
+            .get(merchant_id)
+            .unwrap_or(&self.default_limit)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct RateLimit {
+    /// Maximum number of requests which can be made in a window
+    pub max_requests: u32,
+    /// Duration of the window in which the requests are counted
+    pub window_in_seconds: u32,
+}
+
+/// Short lived cache of the payment sync results returned by the connector, keyed by
+/// connector_transaction_id. The cache is maintained per application instance.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PsyncCache {
+    pub enabled: bool,
+    /// Duration for which a sync result is reused without calling the connector
+    pub ttl_in_seconds: u64,
+}
+
+/// Limit on the number of payments in a non terminal status a single customer can have open
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct OpenPaymentIntentsLimit {
+    pub enabled: bool,
+    /// Payments cannot be created for a customer who already has this many open payments
+    pub max_open_intents_per_customer: u32,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct EphemeralConfig {
@@ -684,6 +909,10 @@ pub struct ApplePayDecryptConifg {
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorRequestReferenceIdConfig {
     pub merchant_ids_send_payment_id_as_connector_request_id: HashSet<String>,
+    /// Prefixes added to the attempt ids generated for the payments of a merchant, keyed by
+    /// merchant_id
+    #[serde(default)]
+    pub merchant_attempt_id_prefixes: HashMap<String, String>,
 }
 
 fn deser_to_get_connectors<'a, D>(
@@ -785,6 +1014,8 @@ impl Settings {
         #[cfg(feature = "s3")]
         self.file_upload_config.validate()?;
         self.lock_settings.validate()?;
+        self.connector_request_reference_id_config.validate()?;
+        self.card_fingerprint.validate()?;
         Ok(())
     }
 }
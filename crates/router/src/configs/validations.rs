@@ -166,6 +166,18 @@ impl super::settings::ApiKeys {
     }
 }
 
+impl super::settings::CardFingerprint {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.hash_key.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "card fingerprint hashing key must not be empty".into(),
+            ))
+        })
+    }
+}
+
 impl super::settings::LockSettings {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
@@ -193,3 +205,25 @@ impl super::settings::LockSettings {
         })
     }
 }
+
+impl super::settings::ConnectorRequestReferenceIdConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        self.merchant_attempt_id_prefixes
+            .iter()
+            .try_for_each(|(merchant_id, prefix)| {
+                when(
+                    prefix.is_empty()
+                        || !prefix
+                            .chars()
+                            .all(|character| character.is_ascii_alphanumeric()),
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                            "attempt id prefix of merchant {merchant_id} must be a non empty alphanumeric string"
+                        )))
+                    },
+                )
+            })
+    }
+}
@@ -26,7 +26,7 @@ use crate::{
         api::{self, enums as api_enums},
         storage::enums as storage_enums,
         transformers::ForeignFrom,
-        PaymentsAuthorizeData,
+        FromConnectorResponse, PaymentsAuthorizeData,
     },
     utils as crate_utils,
 };
@@ -100,6 +100,14 @@ pub struct AdditionalData {
     #[serde(rename = "recurring.shopperReference")]
     recurring_shopper_reference: Option<String>,
     network_tx_reference: Option<String>,
+    auth_code: Option<String>,
+    avs_result_raw: Option<String>,
+    cvc_result_raw: Option<String>,
+    #[serde(rename = "threeDSVersion")]
+    three_ds_version: Option<String>,
+    liability_shift: Option<String>,
+    /// Raw 3DS authentication result of the issuer, `C` if the issuer requires a challenge
+    three_d_authenticated_response: Option<String>,
     #[cfg(feature = "payouts")]
     payout_eligible: Option<PayoutEligibility>,
     funds_availability: Option<String>,
@@ -159,6 +167,52 @@ pub struct AdyenPaymentRequest<'a> {
     country_code: Option<api_enums::CountryAlpha2>,
     line_items: Option<Vec<LineItem>>,
     channel: Option<Channel>,
+    risk_data: Option<RiskData>,
+    #[serde(rename = "threeDS2RequestData")]
+    three_ds2_request_data: Option<AdyenThreeDS2RequestData>,
+    authentication_data: Option<AdyenAuthenticationData>,
+}
+
+/// Restricts the 3DS authentication of the payment to the minimum version configured for Adyen
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct AdyenAuthenticationData {
+    #[serde(rename = "threeDSRequestData")]
+    three_ds_request_data: AdyenThreeDSRequestData,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct AdyenThreeDSRequestData {
+    #[serde(rename = "threeDSVersion")]
+    three_ds_version: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AdyenDeviceChannel {
+    App,
+}
+
+/// Details of the 3DS SDK in the customer's app, required by Adyen for app based 3DS2
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenThreeDS2RequestData {
+    device_channel: AdyenDeviceChannel,
+    #[serde(rename = "sdkAppID")]
+    sdk_app_id: Option<String>,
+    sdk_enc_data: Option<Secret<String>>,
+    sdk_ephem_pub_key: Option<std::collections::HashMap<String, String>>,
+    sdk_max_timeout: Option<u8>,
+    sdk_reference_number: Option<String>,
+    #[serde(rename = "sdkTransID")]
+    sdk_trans_id: Option<String>,
+}
+
+/// Risk data from an external fraud tool, sent to Adyen as custom risk fields
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskData {
+    custom_fields: std::collections::HashMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1500,6 +1554,45 @@ fn get_additional_data(item: &types::PaymentsAuthorizeRouterData) -> Option<Addi
     }
 }
 
+fn get_risk_data(risk_data: Option<&serde_json::Value>) -> Option<RiskData> {
+    let custom_fields = risk_data?
+        .as_object()?
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    Some(RiskData { custom_fields })
+}
+
+fn get_three_ds2_request_data(
+    sdk_information: Option<&payments::SdkInfo>,
+) -> Option<AdyenThreeDS2RequestData> {
+    sdk_information.map(|sdk_information| AdyenThreeDS2RequestData {
+        device_channel: AdyenDeviceChannel::App,
+        sdk_app_id: sdk_information.sdk_app_id.clone(),
+        sdk_enc_data: sdk_information.sdk_enc_data.clone(),
+        sdk_ephem_pub_key: sdk_information.sdk_ephem_pub_key.clone(),
+        sdk_max_timeout: sdk_information.sdk_max_timeout,
+        sdk_reference_number: sdk_information.sdk_reference_number.clone(),
+        sdk_trans_id: sdk_information.sdk_trans_id.clone(),
+    })
+}
+
+fn get_authentication_data(
+    minimum_three_ds_version: Option<&String>,
+) -> Option<AdyenAuthenticationData> {
+    minimum_three_ds_version.map(|three_ds_version| AdyenAuthenticationData {
+        three_ds_request_data: AdyenThreeDSRequestData {
+            three_ds_version: three_ds_version.clone(),
+        },
+    })
+}
+
 fn get_channel_type(pm_type: &Option<storage_enums::PaymentMethodType>) -> Option<Channel> {
     pm_type.as_ref().and_then(|pmt| match pmt {
         storage_enums::PaymentMethodType::GoPay => Some(Channel::Web),
@@ -2301,6 +2394,9 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         })
     }
 }
@@ -2349,6 +2445,13 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: get_three_ds2_request_data(
+                item.router_data.request.sdk_information.as_ref(),
+            ),
+            authentication_data: get_authentication_data(
+                item.router_data.request.minimum_three_ds_version.as_ref(),
+            ),
         })
     }
 }
@@ -2399,6 +2502,9 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         };
         Ok(request)
     }
@@ -2450,6 +2556,9 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         };
         Ok(request)
     }
@@ -2497,6 +2606,9 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         };
         Ok(request)
     }
@@ -2544,6 +2656,9 @@ impl<'a>
             store_payment_method: None,
             channel: None,
             social_security_number: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         };
         Ok(request)
     }
@@ -2597,6 +2712,9 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         })
     }
 }
@@ -2691,6 +2809,9 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         })
     }
 }
@@ -2748,6 +2869,9 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         })
     }
 }
@@ -2803,6 +2927,9 @@ impl<'a>
             store_payment_method: None,
             channel: None,
             social_security_number: None,
+            risk_data: get_risk_data(item.router_data.request.risk_data.as_ref()),
+            three_ds2_request_data: None,
+            authentication_data: None,
         })
     }
 }
@@ -2874,6 +3001,38 @@ impl TryFrom<types::PaymentsBalanceResponseRouterData<AdyenBalanceResponse>>
     }
 }
 
+/// Raw AVS and CVC result codes of the issuer, stored on the payment attempt for fraud review
+fn get_avs_and_cvv_results(additional_data: &AdditionalData) -> (Option<String>, Option<String>) {
+    (
+        additional_data.avs_result_raw.clone(),
+        additional_data.cvc_result_raw.clone(),
+    )
+}
+
+fn get_three_ds_authentication_result(
+    additional_data: &AdditionalData,
+) -> Option<types::ThreeDsAuthenticationResult> {
+    let three_ds_version = additional_data.three_ds_version.clone();
+    let liability_shift = additional_data
+        .liability_shift
+        .as_ref()
+        .and_then(|liability_shift| liability_shift.parse::<bool>().ok());
+    let challenge_required = additional_data
+        .three_d_authenticated_response
+        .as_deref()
+        .and_then(|authentication_response| match authentication_response {
+            "C" => Some(true),
+            "Y" | "A" => Some(false),
+            _ => None,
+        });
+    (three_ds_version.is_some() || liability_shift.is_some() || challenge_required.is_some())
+        .then_some(types::ThreeDsAuthenticationResult {
+            three_ds_version,
+            liability_shift,
+            challenge_required,
+        })
+}
+
 pub fn get_adyen_response(
     response: Response,
     is_capture_manual: bool,
@@ -2886,24 +3045,14 @@ pub fn get_adyen_response(
     ),
     errors::ConnectorError,
 > {
+    let outcome = types::ConnectorPaymentOutcome::from_connector_response((
+        &response,
+        is_capture_manual,
+        status_code,
+    ));
     let status =
         storage_enums::AttemptStatus::foreign_from((is_capture_manual, response.result_code));
     let status = update_attempt_status_based_on_event_type_if_needed(status, &response.event_code);
-    let error = if response.refusal_reason.is_some() || response.refusal_reason_code.is_some() {
-        Some(types::ErrorResponse {
-            code: response
-                .refusal_reason_code
-                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
-            message: response
-                .refusal_reason
-                .clone()
-                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
-            reason: response.refusal_reason,
-            status_code,
-        })
-    } else {
-        None
-    };
     let mandate_reference = response
         .additional_data
         .as_ref()
@@ -2917,14 +3066,56 @@ pub fn get_adyen_response(
         .and_then(|additional_data| additional_data.network_tx_reference);
 
     let payments_response_data = types::PaymentsResponseData::TransactionResponse {
-        resource_id: types::ResponseId::ConnectorTransactionId(response.psp_reference),
+        resource_id: outcome.connector_transaction_id.map_or(
+            types::ResponseId::NoResponseId,
+            |connector_transaction_id| {
+                types::ResponseId::ConnectorTransactionId(connector_transaction_id)
+            },
+        ),
         redirection_data: None,
         mandate_reference,
         connector_metadata: None,
         network_txn_id,
         connector_response_reference_id: Some(response.merchant_reference),
     };
-    Ok((status, error, payments_response_data))
+    Ok((status, outcome.error, payments_response_data))
+}
+
+fn get_adyen_error_response(response: &Response, status_code: u16) -> Option<types::ErrorResponse> {
+    if response.refusal_reason.is_some() || response.refusal_reason_code.is_some() {
+        Some(types::ErrorResponse {
+            code: response
+                .refusal_reason_code
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .refusal_reason
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: response.refusal_reason.clone(),
+            status_code,
+        })
+    } else {
+        None
+    }
+}
+
+impl types::FromConnectorResponse<(&Response, bool, u16)> for types::ConnectorPaymentOutcome {
+    fn from_connector_response(
+        (response, is_capture_manual, status_code): (&Response, bool, u16),
+    ) -> Self {
+        let status = storage_enums::AttemptStatus::foreign_from((
+            is_capture_manual,
+            response.result_code.clone(),
+        ));
+        let status =
+            update_attempt_status_based_on_event_type_if_needed(status, &response.event_code);
+        Self {
+            status: storage_enums::IntentStatus::foreign_from(status),
+            connector_transaction_id: Some(response.psp_reference.clone()),
+            error: get_adyen_error_response(response, status_code),
+        }
+    }
 }
 
 pub fn get_adyen_response_for_multiple_partial_capture(
@@ -3085,10 +3276,17 @@ pub fn get_qr_code_response(
     ),
     errors::ConnectorError,
 > {
-    let status = storage_enums::AttemptStatus::foreign_from((
+    // The payment stays pending until the customer scans the Qr code and pays, which requires an
+    // action from the customer
+    let status = match storage_enums::AttemptStatus::foreign_from((
         is_manual_capture,
         response.result_code.clone(),
-    ));
+    )) {
+        storage_enums::AttemptStatus::Pending => {
+            storage_enums::AttemptStatus::AuthenticationPending
+        }
+        status => status,
+    };
     let error = if response.refusal_reason.is_some() || response.refusal_reason_code.is_some() {
         Some(types::ErrorResponse {
             code: response
@@ -3165,6 +3363,7 @@ pub fn get_qr_metadata(
     let qr_code_instructions = payments::QrCodeNextStepsInstruction {
         image_data_url,
         display_to_timestamp: None,
+        qr_code_data: Some(response.action.qr_code_data.to_owned()),
     };
 
     Some(common_utils::ext_traits::Encode::<
@@ -3385,6 +3584,41 @@ impl<F, Req>
         ),
     ) -> Result<Self, Self::Error> {
         let is_manual_capture = utils::is_manual_capture(capture_method);
+        let connector_response_metadata = match &item.response {
+            AdyenPaymentResponse::Response(response) => response
+                .additional_data
+                .as_ref()
+                .and_then(|additional_data| additional_data.auth_code.clone())
+                .map(|authorization_code| types::ConnectorResponseMetadata {
+                    authorization_code: Some(authorization_code),
+                    settled_at: None,
+                }),
+            AdyenPaymentResponse::PresentToShopper(_)
+            | AdyenPaymentResponse::QrCodeResponse(_)
+            | AdyenPaymentResponse::RedirectionResponse(_)
+            | AdyenPaymentResponse::RedirectionErrorResponse(_) => None,
+        };
+        let (avs_result, cvv_result) = match &item.response {
+            AdyenPaymentResponse::Response(response) => response
+                .additional_data
+                .as_ref()
+                .map(get_avs_and_cvv_results)
+                .unwrap_or_default(),
+            AdyenPaymentResponse::PresentToShopper(_)
+            | AdyenPaymentResponse::QrCodeResponse(_)
+            | AdyenPaymentResponse::RedirectionResponse(_)
+            | AdyenPaymentResponse::RedirectionErrorResponse(_) => (None, None),
+        };
+        let three_ds_authentication_result = match &item.response {
+            AdyenPaymentResponse::Response(response) => response
+                .additional_data
+                .as_ref()
+                .and_then(get_three_ds_authentication_result),
+            AdyenPaymentResponse::PresentToShopper(_)
+            | AdyenPaymentResponse::QrCodeResponse(_)
+            | AdyenPaymentResponse::RedirectionResponse(_)
+            | AdyenPaymentResponse::RedirectionErrorResponse(_) => None,
+        };
         let (status, error, payment_response_data) = match item.response {
             AdyenPaymentResponse::Response(response) => {
                 if is_multiple_capture_psync_flow {
@@ -3410,6 +3644,10 @@ impl<F, Req>
         Ok(Self {
             status,
             response: error.map_or_else(|| Ok(payment_response_data), Err),
+            connector_response_metadata,
+            avs_result,
+            cvv_result,
+            three_ds_authentication_result,
             ..item.data
         })
     }
@@ -4136,3 +4374,185 @@ impl ForeignFrom<AdyenStatus> for storage_enums::PayoutStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_authorised_response_is_mapped_to_outcome() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "pspReference": "V4HZ4RBFJGXXGN82",
+            "resultCode": "Authorised",
+            "merchantReference": "pay_adyen_1_1"
+        }))
+        .unwrap();
+
+        let automatic_capture =
+            types::ConnectorPaymentOutcome::from_connector_response((&response, false, 200));
+        let manual_capture =
+            types::ConnectorPaymentOutcome::from_connector_response((&response, true, 200));
+
+        assert_eq!(
+            automatic_capture.status,
+            storage_enums::IntentStatus::Succeeded
+        );
+        assert_eq!(
+            manual_capture.status,
+            storage_enums::IntentStatus::RequiresCapture
+        );
+        assert_eq!(
+            automatic_capture.connector_transaction_id.as_deref(),
+            Some("V4HZ4RBFJGXXGN82")
+        );
+        assert!(automatic_capture.error.is_none());
+    }
+
+    #[test]
+    fn test_refused_response_is_mapped_to_outcome() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "pspReference": "V4HZ4RBFJGXXGN82",
+            "resultCode": "Refused",
+            "merchantReference": "pay_adyen_1_1",
+            "refusalReason": "CVC Declined",
+            "refusalReasonCode": "24"
+        }))
+        .unwrap();
+
+        let outcome =
+            types::ConnectorPaymentOutcome::from_connector_response((&response, false, 200));
+        assert_eq!(outcome.status, storage_enums::IntentStatus::Failed);
+        assert!(matches!(
+            outcome.error,
+            Some(types::ErrorResponse { ref code, ref message, status_code: 200, .. })
+                if code == "24" && message == "CVC Declined"
+        ));
+
+        // The error of the outcome is returned to the payment trackers as the connector error
+        let (status, error, _) = get_adyen_response(response, false, 200).unwrap();
+        assert_eq!(status, storage_enums::AttemptStatus::Failure);
+        assert!(matches!(
+            error,
+            Some(types::ErrorResponse { code, .. }) if code == "24"
+        ));
+    }
+
+    #[test]
+    fn test_qr_code_response_requires_customer_action() {
+        let response: QrCodeResponseResponse = serde_json::from_value(serde_json::json!({
+            "resultCode": "Pending",
+            "action": {
+                "paymentMethodType": "swish",
+                "type": "qrCode",
+                "qrCodeData": "D/+gwGxCVKRHk5pb2qA3c2dG+mSeYLUm6JkBdMUGeSAk"
+            }
+        }))
+        .unwrap();
+
+        let (status, error, payments_response_data) =
+            get_qr_code_response(response, false, 200).unwrap();
+
+        assert_eq!(status, storage_enums::AttemptStatus::AuthenticationPending);
+        assert!(error.is_none());
+        let connector_metadata = match payments_response_data {
+            types::PaymentsResponseData::TransactionResponse {
+                connector_metadata, ..
+            } => connector_metadata,
+            _ => None,
+        };
+        let qr_code_instructions: payments::QrCodeNextStepsInstruction =
+            serde_json::from_value(connector_metadata.unwrap()).unwrap();
+        assert_eq!(
+            qr_code_instructions.qr_code_data,
+            Some("D/+gwGxCVKRHk5pb2qA3c2dG+mSeYLUm6JkBdMUGeSAk".to_string())
+        );
+        assert_eq!(qr_code_instructions.image_data_url.scheme(), "data");
+    }
+
+    #[test]
+    fn test_risk_data_is_sent_as_custom_risk_fields() {
+        let risk_data = serde_json::json!({ "risk_score": 12, "decision": "approve" });
+
+        assert_eq!(
+            serde_json::to_value(get_risk_data(Some(&risk_data))).unwrap(),
+            serde_json::json!({
+                "customFields": { "risk_score": "12", "decision": "approve" }
+            })
+        );
+        assert_eq!(get_risk_data(None), None);
+    }
+
+    #[test]
+    fn test_sdk_information_is_sent_as_three_ds2_request_data() {
+        let sdk_information = payments::SdkInfo {
+            sdk_app_id: Some("9063b12c-fcde-43c7-b28e-8d0af5520e8a".to_string()),
+            sdk_trans_id: Some("b60c9879-ac77-4918-a317-7b01c4317053".to_string()),
+            sdk_max_timeout: Some(60),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(get_three_ds2_request_data(Some(&sdk_information))).unwrap(),
+            serde_json::json!({
+                "deviceChannel": "app",
+                "sdkAppID": "9063b12c-fcde-43c7-b28e-8d0af5520e8a",
+                "sdkTransID": "b60c9879-ac77-4918-a317-7b01c4317053",
+                "sdkMaxTimeout": 60
+            })
+        );
+        assert_eq!(get_three_ds2_request_data(None), None);
+    }
+
+    #[test]
+    fn test_minimum_three_ds_version_is_sent_as_authentication_data() {
+        assert_eq!(
+            serde_json::to_value(get_authentication_data(Some(&"2.2.0".to_string()))).unwrap(),
+            serde_json::json!({ "threeDSRequestData": { "threeDSVersion": "2.2.0" } })
+        );
+        assert_eq!(get_authentication_data(None), None);
+    }
+
+    #[test]
+    fn test_challenge_requirement_is_read_from_three_ds_result() {
+        let frictionless: AdditionalData = serde_json::from_value(serde_json::json!({
+            "threeDSVersion": "2.2.0",
+            "threeDAuthenticatedResponse": "Y"
+        }))
+        .unwrap();
+        let challenge_required: AdditionalData = serde_json::from_value(serde_json::json!({
+            "threeDSVersion": "2.2.0",
+            "threeDAuthenticatedResponse": "C"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            get_three_ds_authentication_result(&frictionless),
+            Some(types::ThreeDsAuthenticationResult {
+                three_ds_version: Some("2.2.0".to_string()),
+                liability_shift: None,
+                challenge_required: Some(false),
+            })
+        );
+        assert_eq!(
+            get_three_ds_authentication_result(&challenge_required)
+                .and_then(|result| result.challenge_required),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_avs_and_cvv_results_are_read_from_additional_data() {
+        let additional_data: AdditionalData = serde_json::from_value(serde_json::json!({
+            "authCode": "A1B2C3",
+            "avsResultRaw": "Y",
+            "cvcResultRaw": "M"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            get_avs_and_cvv_results(&additional_data),
+            (Some("Y".to_string()), Some("M".to_string()))
+        );
+    }
+}
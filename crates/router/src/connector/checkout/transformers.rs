@@ -238,6 +238,24 @@ pub struct PaymentsRequest {
     pub return_url: ReturnUrl,
     pub capture: bool,
     pub reference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_descriptor: Option<CheckoutBillingDescriptor>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CheckoutBillingDescriptor {
+    name: String,
+    city: String,
+}
+
+impl CheckoutBillingDescriptor {
+    /// Checkout requires both the name and the city in the billing descriptor
+    fn new(name: Option<&String>, city: Option<&String>) -> Option<Self> {
+        name.zip(city).map(|(name, city)| Self {
+            name: name.to_owned(),
+            city: city.to_owned(),
+        })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -384,6 +402,10 @@ impl TryFrom<&CheckoutRouterData<&types::PaymentsAuthorizeRouterData>> for Payme
             return_url,
             capture,
             reference: item.router_data.connector_request_reference_id.clone(),
+            billing_descriptor: CheckoutBillingDescriptor::new(
+                item.router_data.request.statement_descriptor.as_ref(),
+                item.router_data.request.statement_descriptor_city.as_ref(),
+            ),
         })
     }
 }
@@ -1260,3 +1282,23 @@ impl From<String> for utils::ErrorCodeAndMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_descriptor_city_is_forwarded_in_billing_descriptor() {
+        let name = "Hyperswitch".to_string();
+        let city = "San Francisco".to_string();
+
+        assert_eq!(
+            CheckoutBillingDescriptor::new(Some(&name), Some(&city)),
+            Some(CheckoutBillingDescriptor {
+                name: "Hyperswitch".to_string(),
+                city: "San Francisco".to_string(),
+            })
+        );
+        assert_eq!(CheckoutBillingDescriptor::new(None, Some(&city)), None);
+    }
+}
@@ -104,7 +104,11 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for DlocalPaymentsRequest {
                             .as_ref()
                             .map(|ids| ids.mandate_id.clone()),
                         // [#595[FEATURE] Pass Mandate history information in payment flows/request]
-                        installments: item.request.mandate_id.clone().map(|_| "1".to_string()),
+                        installments: item
+                            .request
+                            .installments
+                            .map(|installments| installments.number_of_installments.to_string())
+                            .or(item.request.mandate_id.clone().map(|_| "1".to_string())),
                     }),
                     order_id: item.payment_id.clone(),
                     three_dsecure: match item.auth_type {
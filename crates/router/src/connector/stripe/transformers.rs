@@ -17,12 +17,14 @@ use uuid::Uuid;
 use crate::{
     collect_missing_value_keys,
     connector::utils::{self as connector_util, ApplePay, PaymentsPreProcessingData, RouterData},
+    consts,
     core::errors,
     services,
     types::{
         self, api,
         storage::enums,
         transformers::{ForeignFrom, ForeignTryFrom},
+        FromConnectorResponse,
     },
     utils::{self, OptionExt},
 };
@@ -2047,6 +2049,38 @@ pub struct PaymentIntentResponse {
     pub latest_attempt: Option<LatestAttempt>, //need a merchant to test this
 }
 
+impl types::FromConnectorResponse<(&PaymentIntentResponse, u16)>
+    for types::ConnectorPaymentOutcome
+{
+    fn from_connector_response((response, http_code): (&PaymentIntentResponse, u16)) -> Self {
+        let status =
+            enums::IntentStatus::foreign_from(enums::AttemptStatus::from(response.status.clone()));
+        // The last payment error is kept on payment intents which are retried with another
+        // payment method, it is only the error of the payment once the payment has failed
+        let error = response
+            .last_payment_error
+            .as_ref()
+            .filter(|_| status == enums::IntentStatus::Failed)
+            .map(|error| types::ErrorResponse {
+                code: error
+                    .code
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+                message: error
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+                reason: error.message.clone(),
+                status_code: http_code,
+            });
+        Self {
+            status,
+            connector_transaction_id: Some(response.id.clone()),
+            error,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct StripeSourceResponse {
     pub id: String,
@@ -2290,6 +2324,26 @@ impl ForeignFrom<(Option<StripePaymentMethodOptions>, String)> for types::Mandat
     }
 }
 
+impl PaymentIntentResponse {
+    /// `amount` echoes the amount sent to Stripe, the amount actually authorized is reported as
+    /// capturable until the payment is captured, and as received once it is captured
+    pub fn get_authorized_amount(&self) -> Option<i64> {
+        match self.status {
+            StripePaymentStatus::RequiresCapture => self.amount_capturable,
+            StripePaymentStatus::Succeeded => self.amount_received,
+            StripePaymentStatus::Failed
+            | StripePaymentStatus::Processing
+            | StripePaymentStatus::RequiresCustomerAction
+            | StripePaymentStatus::RequiresPaymentMethod
+            | StripePaymentStatus::RequiresConfirmation
+            | StripePaymentStatus::Canceled
+            | StripePaymentStatus::Chargeable
+            | StripePaymentStatus::Consumed
+            | StripePaymentStatus::Pending => None,
+        }
+    }
+}
+
 impl<F, T>
     TryFrom<types::ResponseRouterData<F, PaymentIntentResponse, T, types::PaymentsResponseData>>
     for types::RouterData<F, T, types::PaymentsResponseData>
@@ -2316,21 +2370,33 @@ impl<F, T>
         let connector_metadata =
             get_connector_metadata(item.response.next_action.as_ref(), item.response.amount)?;
 
+        let outcome = types::ConnectorPaymentOutcome::from_connector_response((
+            &item.response,
+            item.http_code,
+        ));
+        let payments_response_data = types::PaymentsResponseData::TransactionResponse {
+            resource_id: outcome.connector_transaction_id.map_or(
+                types::ResponseId::NoResponseId,
+                types::ResponseId::ConnectorTransactionId,
+            ),
+            redirection_data,
+            mandate_reference,
+            connector_metadata,
+            network_txn_id,
+            connector_response_reference_id: Some(item.response.id),
+        };
+
         Ok(Self {
             status: enums::AttemptStatus::from(item.response.status),
             // client_secret: Some(item.response.client_secret.clone().as_str()),
             // description: item.response.description.map(|x| x.as_str()),
             // statement_descriptor_suffix: item.response.statement_descriptor_suffix.map(|x| x.as_str()),
             // three_ds_form,
-            response: Ok(types::PaymentsResponseData::TransactionResponse {
-                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id.clone()),
-                redirection_data,
-                mandate_reference,
-                connector_metadata,
-                network_txn_id,
-                connector_response_reference_id: Some(item.response.id),
-            }),
+            response: outcome
+                .error
+                .map_or_else(|| Ok(payments_response_data), Err),
             amount_captured: item.response.amount_received,
+            connector_authorized_amount: item.response.get_authorized_amount(),
             ..item.data
         })
     }
@@ -3773,3 +3839,99 @@ mod test_validate_shipping_address_against_payment_method {
         }
     }
 }
+
+#[cfg(test)]
+mod test_authorized_amount {
+    use super::{PaymentIntentResponse, StripePaymentStatus};
+
+    #[test]
+    fn test_authorized_amount_is_read_from_capturable_and_received_amounts() {
+        let authorized = PaymentIntentResponse {
+            amount: 1050,
+            amount_capturable: Some(1049),
+            amount_received: Some(0),
+            status: StripePaymentStatus::RequiresCapture,
+            ..Default::default()
+        };
+        let captured = PaymentIntentResponse {
+            amount: 1050,
+            amount_capturable: Some(0),
+            amount_received: Some(1049),
+            status: StripePaymentStatus::Succeeded,
+            ..Default::default()
+        };
+        let processing = PaymentIntentResponse {
+            amount: 1050,
+            status: StripePaymentStatus::Processing,
+            ..Default::default()
+        };
+
+        assert_eq!(authorized.get_authorized_amount(), Some(1049));
+        assert_eq!(captured.get_authorized_amount(), Some(1049));
+        assert_eq!(processing.get_authorized_amount(), None);
+    }
+}
+
+#[cfg(test)]
+mod test_connector_payment_outcome {
+    use crate::{
+        connector::stripe::transformers::{
+            ErrorDetails, PaymentIntentResponse, StripePaymentStatus,
+        },
+        types::{self, storage::enums, FromConnectorResponse},
+    };
+
+    #[test]
+    fn test_succeeded_payment_intent_is_mapped_to_outcome() {
+        let response = PaymentIntentResponse {
+            id: "pi_3O5oHyD5R7gDAGff1HwcXyYN".to_string(),
+            status: StripePaymentStatus::Succeeded,
+            ..Default::default()
+        };
+
+        let outcome = types::ConnectorPaymentOutcome::from_connector_response((&response, 200));
+
+        assert_eq!(outcome.status, enums::IntentStatus::Succeeded);
+        assert_eq!(
+            outcome.connector_transaction_id.as_deref(),
+            Some("pi_3O5oHyD5R7gDAGff1HwcXyYN")
+        );
+        assert!(outcome.error.is_none());
+    }
+
+    #[test]
+    fn test_declined_payment_intent_is_mapped_to_outcome() {
+        let get_last_payment_error = || ErrorDetails {
+            code: Some("card_declined".to_string()),
+            error_type: Some("card_error".to_string()),
+            message: Some("Your card was declined.".to_string()),
+            param: None,
+        };
+        let response = PaymentIntentResponse {
+            id: "pi_3O5oHyD5R7gDAGff1HwcXyYN".to_string(),
+            status: StripePaymentStatus::Failed,
+            last_payment_error: Some(get_last_payment_error()),
+            ..Default::default()
+        };
+
+        let outcome = types::ConnectorPaymentOutcome::from_connector_response((&response, 200));
+
+        assert_eq!(outcome.status, enums::IntentStatus::Failed);
+        assert!(matches!(
+            outcome.error,
+            Some(types::ErrorResponse { code, message, status_code: 200, .. })
+                if code == "card_declined" && message == "Your card was declined."
+        ));
+
+        // The error of a payment which is awaiting customer action is not the outcome of the
+        // payment
+        let response = PaymentIntentResponse {
+            id: "pi_3O5oHyD5R7gDAGff1HwcXyYN".to_string(),
+            status: StripePaymentStatus::RequiresCustomerAction,
+            last_payment_error: Some(get_last_payment_error()),
+            ..Default::default()
+        };
+        let outcome = types::ConnectorPaymentOutcome::from_connector_response((&response, 200));
+        assert!(outcome.error.is_none());
+    }
+}
@@ -15,6 +15,14 @@ pub const REQUEST_TIMEOUT_ERROR_CODE: &str = "TIMEOUT";
 pub const REQUEST_TIMEOUT_ERROR_MESSAGE: &str = "Connector did not respond in specified time";
 pub const REQUEST_TIMEOUT_ERROR_MESSAGE_FROM_PSYNC: &str =
     "This Payment has been moved to failed as there is no response from the connector";
+/// Delay after which a payment, whose connector call timed out, is synced with the connector (in seconds)
+pub const CONNECTOR_TIMEOUT_SYNC_DELAY: i64 = 60;
+
+/// Key under which the fields returned by the connector are added to the payment metadata
+pub const CONNECTOR_RESPONSE_METADATA_KEY: &str = "connector_response";
+
+/// Prefix of the payment metadata keys which are reserved for the fields added by hyperswitch
+pub const RESERVED_METADATA_KEY_PREFIX: &str = "hyperswitch_";
 
 ///Payment intent fulfillment default timeout (in seconds)
 pub const DEFAULT_FULFILLMENT_TIME: i64 = 15 * 60;
@@ -49,3 +57,21 @@ pub(crate) const METRICS_HOST_TAG_NAME: &str = "host";
 
 // TTL for KV setup
 pub(crate) const KV_TTL: u32 = 300;
+
+// Placeholder for the values redacted from the logged connector requests and responses
+pub(crate) const REDACTED_CONNECTOR_IO_VALUE: &str = "*** redacted ***";
+
+// Maximum length of the merchant city shown on the statements of the customers
+pub(crate) const STATEMENT_DESCRIPTOR_CITY_MAX_LENGTH: usize = 13;
+
+// Maximum length of the statement descriptor name or suffix shown on card statements
+pub(crate) const STATEMENT_DESCRIPTOR_MAX_LENGTH: usize = 22;
+
+// Maximum length of the remittance reference shown on the bank statements for direct debits
+pub(crate) const BANK_DEBIT_STATEMENT_DESCRIPTOR_MAX_LENGTH: usize = 35;
+
+// Maximum number of years ahead of the current year a card can expire in
+pub(crate) const CARD_EXPIRY_MAX_YEARS_AHEAD: i32 = 20;
+
+// TTL of the responses stored for the idempotency keys of payments create requests
+pub(crate) const IDEMPOTENCY_KEY_TTL: i64 = 86400;
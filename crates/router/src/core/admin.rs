@@ -156,6 +156,7 @@ pub async fn create_merchant_account(
             redirect_to_merchant_with_http_post: req
                 .redirect_to_merchant_with_http_post
                 .unwrap_or_default(),
+            debug_connector_io: req.debug_connector_io.unwrap_or_default(),
             publishable_key,
             locker_id: req.locker_id,
             metadata,
@@ -450,6 +451,7 @@ pub async fn merchant_account_update(
         enable_payment_response_hash: req.enable_payment_response_hash,
         payment_response_hash_key: req.payment_response_hash_key,
         redirect_to_merchant_with_http_post: req.redirect_to_merchant_with_http_post,
+        debug_connector_io: req.debug_connector_io,
         locker_id: req.locker_id,
         metadata: req.metadata,
         publishable_key: None,
@@ -170,6 +170,63 @@ pub async fn list_customers(
     Ok(services::ApplicationResponse::Json(customers))
 }
 
+#[instrument(skip(state))]
+pub async fn list_customer_payments(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: customers::CustomerId,
+    constraints: customers::CustomerPaymentsListConstraints,
+) -> errors::CustomerResponse<customers::CustomerPaymentsListResponse> {
+    use common_utils::consts::PAYMENTS_LIST_MAX_LIMIT_V1;
+
+    let db = state.store.as_ref();
+
+    // Payments are only listed for customers which belong to the merchant
+    db.find_customer_by_customer_id_merchant_id(
+        &req.customer_id,
+        &merchant_account.merchant_id,
+        &key_store,
+    )
+    .await
+    .switch()?;
+
+    let payment_intents = db
+        .find_payment_intents_by_customer_id_merchant_id(
+            &req.customer_id,
+            &merchant_account.merchant_id,
+            Some(i64::from(std::cmp::min(
+                constraints.limit,
+                PAYMENTS_LIST_MAX_LIMIT_V1,
+            ))),
+            constraints.offset.map(i64::from),
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::CustomersErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the payments of the customer")?;
+
+    let data: Vec<customers::CustomerPaymentSummary> = payment_intents
+        .into_iter()
+        .map(|payment_intent| customers::CustomerPaymentSummary {
+            payment_id: payment_intent.payment_id,
+            status: payment_intent.status,
+            amount: payment_intent.amount,
+            currency: payment_intent.currency,
+            description: payment_intent.description,
+            created_at: payment_intent.created_at,
+        })
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        customers::CustomerPaymentsListResponse {
+            customer_id: req.customer_id,
+            size: data.len(),
+            data,
+        },
+    ))
+}
+
 #[instrument(skip_all)]
 pub async fn delete_customer(
     state: AppState,
@@ -96,6 +96,14 @@ pub enum ApiErrorResponse {
     FileProviderNotSupported { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_23", message = "{message}")]
     UnprocessableEntity { message: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_24", message = "Too many requests have been made by the merchant, please try again later")]
+    RateLimited,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_25", message = "The mandate has been revoked and cannot be used for payments")]
+    MandateRevoked,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_26", message = "The mandate has expired and cannot be used for payments")]
+    MandateExpired,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_27", message = "The idempotency key has already been used with a different request body")]
+    IdempotencyKeyReused,
     #[error(error_type = ErrorType::ConnectorError, code = "CE_00", message = "{code}: {message}", ignore = "status_code")]
     ExternalConnectorError {
         code: String,
@@ -96,6 +96,10 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
                 AER::BadRequest(ApiError::new("IR", 23, message.to_string(), None))
             },
             Self::UnprocessableEntity {message} => AER::Unprocessable(ApiError::new("IR", 23, message.to_string(), None)),
+            Self::RateLimited => AER::TooManyRequests(ApiError::new("IR", 24, "Too many requests have been made by the merchant, please try again later", None)),
+            Self::MandateRevoked => AER::BadRequest(ApiError::new("IR", 25, "The mandate has been revoked and cannot be used for payments", None)),
+            Self::MandateExpired => AER::BadRequest(ApiError::new("IR", 26, "The mandate has expired and cannot be used for payments", None)),
+            Self::IdempotencyKeyReused => AER::Conflict(ApiError::new("IR", 27, "The idempotency key has already been used with a different request body", None)),
             Self::ExternalConnectorError {
                 code,
                 message,
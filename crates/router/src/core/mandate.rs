@@ -5,7 +5,7 @@ use error_stack::{report, ResultExt};
 use futures::future;
 use router_env::{instrument, logger, tracing};
 
-use super::payments::helpers;
+use super::payments::{helpers, tokenization};
 use crate::{
     core::errors::{self, RouterResponse, StorageErrorExt},
     db::StorageInterface,
@@ -148,7 +148,9 @@ where
     FData: MandateBehaviour,
 {
     match resp.response {
+        // Mandates are neither created nor charged for declined payments
         Err(_) => {}
+        Ok(_) if tokenization::is_payment_declined(resp.status) => {}
         Ok(_) => match resp.request.get_mandate_id() {
             Some(mandate_id) => {
                 let mandate_id = &mandate_id.mandate_id;
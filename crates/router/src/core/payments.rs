@@ -1,8 +1,19 @@
 pub mod access_token;
 pub mod customers;
+pub mod debug_snapshot;
 pub mod flows;
 pub mod helpers;
+pub mod hosted_payment_page;
+pub mod idempotency;
 pub mod operations;
+pub mod psync_cache;
+pub mod rate_limit;
+pub mod retry;
+pub mod statement_descriptor;
+pub mod status_polling;
+pub mod test_cards;
+#[cfg(test)]
+mod test_utils;
 pub mod tokenization;
 pub mod transformers;
 pub mod types;
@@ -38,7 +49,8 @@ use self::{
 };
 use super::errors::StorageErrorExt;
 use crate::{
-    configs::settings::PaymentMethodTypeTokenFilter,
+    configs::settings::{InstallmentSupportedConnectors, PaymentMethodTypeTokenFilter},
+    consts,
     core::{
         errors::{self, CustomResult, RouterResponse, RouterResult},
         payment_methods::PaymentMethodRetrieve,
@@ -139,6 +151,56 @@ where
     )
     .await?;
 
+    if let Some(connector_call_type) = &connector {
+        validate_installments_for_connector_call(
+            payment_data.installments.as_ref(),
+            connector_call_type,
+            &state.conf.installment_supported_connectors,
+        )?;
+    }
+
+    if let Some(api::ConnectorCallType::Single(connector_data)) = &connector {
+        // The amount limits apply to the authorization, they are not enforced when an authorized
+        // payment is synced, captured or voided
+        if is_operation_confirm(&operation) {
+            helpers::validate_amount_for_connector(
+                payment_data.payment_attempt.amount,
+                payment_data.currency,
+                &connector_data.connector_name,
+                &state.conf.connector_amount_limits,
+            )?;
+        }
+        helpers::validate_card_holder_name_for_connector(
+            payment_data.payment_method_data.as_ref(),
+            &connector_data.connector_name,
+            &state.conf.card_holder_name_required_connectors,
+        )?;
+        payment_data.payment_attempt.capture_on =
+            payment_data.payment_attempt.capture_on.map(|capture_on| {
+                helpers::normalize_capture_on(
+                    capture_on,
+                    Some(std::slice::from_ref(&connector_data.connector_name)),
+                    &state.conf.date_only_capture_on_connectors,
+                )
+            });
+        helpers::validate_capture_on_for_connector(
+            payment_data.payment_attempt.capture_on,
+            &connector_data.connector_name,
+            &state.conf.connector_auth_hold_window,
+        )?;
+        payment_data.payment_attempt.authentication_type =
+            helpers::get_authentication_type_for_connector(
+                payment_data.payment_attempt.authentication_type,
+                &connector_data.connector_name,
+                &state.conf.connector_default_authentication_type,
+            );
+        payment_data.risk_data = helpers::get_risk_data_for_connector(
+            payment_data.risk_data.take(),
+            &connector_data.connector_name,
+            &state.conf.risk_data_supported_connectors,
+        );
+    }
+
     let schedule_time = match &connector {
         Some(api::ConnectorCallType::Single(connector_data)) => {
             if should_add_task_to_process_tracker(&payment_data) {
@@ -248,6 +310,8 @@ where
             .await?;
     }
 
+    payment_data.next_sync_at = get_next_sync_at(&*state.store, &payment_data).await;
+
     Ok((
         payment_data,
         req,
@@ -257,6 +321,22 @@ where
     ))
 }
 
+/// Processing payments are synced with the connector as per the sync schedule configured for the
+/// connector, the first sync is due `start_after` seconds after the attempt was last updated
+async fn get_next_sync_at<F: Clone>(
+    db: &dyn StorageInterface,
+    payment_data: &PaymentData<F>,
+) -> Option<time::PrimitiveDateTime> {
+    if payment_data.payment_intent.status != storage_enums::IntentStatus::Processing {
+        return None;
+    }
+    let connector = payment_data.payment_attempt.connector.as_ref()?;
+    let mapping = payment_sync::get_connector_pt_mapping(db, connector).await;
+    let start_after =
+        pt_utils::get_schedule_time(mapping, &payment_data.payment_attempt.merchant_id, 0)?;
+    Some(payment_data.payment_attempt.modified_at + time::Duration::seconds(i64::from(start_after)))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn payments_core<F, Res, Req, Op, FData, Ctx>(
     state: AppState,
@@ -286,6 +366,15 @@ where
     // To perform router related operation for PaymentResponse
     PaymentResponse: Operation<F, FData, Ctx>,
 {
+    if is_payment_create(&operation) {
+        rate_limit::check_payment_create_rate_limit(
+            &*state.store,
+            &state.conf.payment_create_rate_limit,
+            &merchant_account.merchant_id,
+        )
+        .await?;
+    }
+
     let (payment_data, req, customer, connector_http_status_code, external_latency) =
         payments_operation_core::<_, _, _, _, Ctx>(
             &state,
@@ -307,6 +396,7 @@ where
         &state.conf.server,
         operation,
         &state.conf.connector_request_reference_id_config,
+        &state.conf.response_masking_policy,
         connector_http_status_code,
         external_latency,
         header_payload.x_hs_latency,
@@ -317,6 +407,10 @@ fn is_start_pay<Op: Debug>(operation: &Op) -> bool {
     format!("{operation:?}").eq("PaymentStart")
 }
 
+fn is_payment_create<Op: Debug>(operation: &Op) -> bool {
+    format!("{operation:?}").eq("PaymentCreate")
+}
+
 #[derive(Clone, Debug)]
 pub struct PaymentsRedirectResponseData {
     pub connector: Option<String>,
@@ -529,6 +623,7 @@ impl<Ctx: PaymentMethodRetrieve> PaymentRedirectFlow<Ctx> for PaymentRedirectSyn
             merchant_id: req.merchant_id,
             param: req.param,
             force_sync: req.force_sync,
+            force_refresh: true,
             connector: req.connector,
             merchant_connector_details: req.creds_identifier.map(|creds_id| {
                 api::MerchantConnectorDetailsWrap {
@@ -622,6 +717,13 @@ where
     )
     .await?;
 
+    helpers::validate_payment_experience_for_connector(
+        payment_data.payment_attempt.payment_experience,
+        payment_data.payment_attempt.payment_method_type,
+        merchant_connector_account.get_payment_methods_enabled(),
+        connector_name,
+    )?;
+
     let (pd, tokenization_action) = get_connector_tokenization_action_when_confirm_true(
         state,
         operation,
@@ -763,31 +865,123 @@ where
         )
         .await?;
 
-    let router_data_res = if should_continue_further {
+    // Declining test cards fail the authorization in test mode without calling the connector
+    let test_card_outcome = if should_continue_further
+        && router_data.test_mode.unwrap_or(false)
+        && test_cards::is_test_card_flow::<F>()
+    {
+        payment_data
+            .payment_method_data
+            .as_ref()
+            .and_then(test_cards::get_test_card_outcome)
+            .filter(test_cards::TestCardOutcome::is_simulated)
+    } else {
+        None
+    };
+
+    let router_data_res = if let Some(test_card_outcome) = test_card_outcome {
+        logger::info!("Simulating the connector response for the test card in test mode");
+        Ok(test_cards::apply_test_card_outcome(
+            router_data,
+            test_card_outcome,
+        ))
+    } else if should_continue_further {
         // The status of payment_attempt and intent will be updated in the previous step
         // update this in router_data.
         // This is added because few connector integrations do not update the status,
         // and rely on previous status set in router_data
         router_data.status = payment_data.payment_attempt.status;
-        router_data
-            .decide_flows(
-                state,
-                &connector,
-                customer,
-                call_connector_action,
-                merchant_account,
-                connector_request,
-                key_store,
+        if retry::is_idempotent_flow::<F>() {
+            let connector = &connector;
+            let router_data = &router_data;
+            let mut connector_request = connector_request;
+            // The connector request is consumed by the first attempt,
+            // it is built again by the connector integration for the subsequent attempts
+            retry::retry_with_backoff(
+                &state.conf.connector_retry,
+                retry::is_transient_connector_error,
+                move || {
+                    router_data.clone().decide_flows(
+                        state,
+                        connector,
+                        customer,
+                        call_connector_action.clone(),
+                        merchant_account,
+                        connector_request.take(),
+                        key_store,
+                    )
+                },
             )
             .await
+        } else {
+            router_data
+                .decide_flows(
+                    state,
+                    &connector,
+                    customer,
+                    call_connector_action,
+                    merchant_account,
+                    connector_request,
+                    key_store,
+                )
+                .await
+        }
     } else {
         Ok(router_data)
     };
 
+    // The connector may have processed the payment even though the call timed out, a sync is
+    // scheduled to find out the status of the payment if it was not scheduled before the call
+    let is_connector_timeout = router_data_res
+        .as_ref()
+        .ok()
+        .and_then(|router_data| router_data.response.as_ref().err())
+        .map(helpers::is_connector_timeout_error)
+        .unwrap_or(false);
+    if is_connector_timeout
+        && schedule_time.is_none()
+        && should_add_task_to_process_tracker(payment_data)
+    {
+        operation
+            .to_domain()?
+            .add_task_to_process_tracker(
+                state,
+                &payment_data.payment_attempt,
+                validate_result.requeue,
+                Some(
+                    common_utils::date_time::now()
+                        + time::Duration::seconds(consts::CONNECTOR_TIMEOUT_SYNC_DELAY),
+                ),
+            )
+            .await
+            .map_err(|error| logger::error!(process_tracker_error=?error))
+            .ok();
+    }
+
+    helpers::record_connector_request_time(
+        &metrics::CONNECTOR_REQUEST_TIME,
+        connector_name,
+        utils::get_flow_name::<F>().unwrap_or_default(),
+        router_data_res
+            .as_ref()
+            .ok()
+            .and_then(|router_data| router_data.external_latency),
+    );
+
     let etime_connector = Instant::now();
     let duration_connector = etime_connector.saturating_duration_since(stime_connector);
     tracing::info!(duration = format!("Duration taken: {}", duration_connector.as_millis()));
 
+    if let Some(message) = router_data_res.as_ref().ok().and_then(|router_data| {
+        helpers::get_three_ds_version_below_minimum_message(
+            &state.conf.minimum_three_ds_version,
+            payment_data.payment_intent.business_country,
+            router_data,
+        )
+    }) {
+        logger::warn!(payment_id = %payment_data.payment_intent.payment_id, "{message}");
+    }
+
     router_data_res
 }
 
@@ -1471,6 +1665,7 @@ where
     pub token: Option<String>,
     pub confirm: Option<bool>,
     pub force_sync: Option<bool>,
+    pub force_refresh: bool,
     pub payment_method_data: Option<api::PaymentMethodData>,
     pub refunds: Vec<storage::Refund>,
     pub disputes: Vec<storage::Dispute>,
@@ -1487,6 +1682,61 @@ where
     pub surcharge_details: Option<SurchargeDetailsResponse>,
     pub frm_message: Option<FraudCheck>,
     pub payment_link_data: Option<api_models::payments::PaymentLinkResponse>,
+    pub used_saved_method: bool,
+    pub installments: Option<api_models::payments::InstallmentData>,
+    pub risk_data: Option<serde_json::Value>,
+    /// When a processing payment is expected to be synced with the connector next
+    pub next_sync_at: Option<time::PrimitiveDateTime>,
+}
+
+/// Source from which the payment method used for a payment is resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolvedPaymentMethod {
+    /// Payment method data passed in the request
+    PaymentMethodData,
+    /// Payment method saved against the payment token
+    Token,
+    /// Payment method of the mandate being used for a recurring payment
+    Mandate,
+    /// Payment method is yet to be provided
+    NotProvided,
+}
+
+impl ResolvedPaymentMethod {
+    pub fn is_saved_payment_method(&self) -> bool {
+        matches!(self, Self::Token | Self::Mandate)
+    }
+}
+
+impl<F: Clone> PaymentData<F> {
+    pub fn get_resolved_payment_method(&self) -> ResolvedPaymentMethod {
+        helpers::resolve_payment_method(
+            self.payment_method_data.as_ref(),
+            self.token.as_ref(),
+            self.mandate_id
+                .as_ref()
+                .map(|mandate_ids| &mandate_ids.mandate_id),
+        )
+    }
+
+    /// Amount of the authorization which is not captured yet. For multiple partial captures, the
+    /// captures which are pending with the connector are considered to be captured.
+    pub fn remaining_capturable(&self) -> i64 {
+        match &self.multiple_capture_data {
+            // Pending partial captures do not stop further partial captures of the amount which
+            // is left, so only terminal statuses leave nothing to capture
+            Some(multiple_capture_data) if !self.payment_attempt.status.is_terminal_status() => {
+                multiple_capture_data
+                    .get_remaining_capturable_amount(self.payment_attempt.get_total_amount())
+            }
+            Some(_) => 0,
+            None => helpers::get_remaining_capturable_amount(
+                self.payment_attempt.status,
+                self.payment_attempt.amount,
+                self.payment_intent.amount_captured.unwrap_or(0),
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1544,6 +1794,141 @@ where
     }
 }
 
+/// Reverses the capture of a payment which is not settled yet through the void flow of the
+/// connector, by cancelling the payment. The captured amount is refunded instead if the capture is
+/// settled or the connector doesn't support reversals.
+#[instrument(skip_all)]
+pub async fn payments_reverse_core<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentsCancelRequest,
+) -> RouterResponse<api::PaymentsResponse> {
+    let db = &*state.store;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &req.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt.get_id(),
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let is_capture_settled =
+        helpers::is_capture_settled(payment_intent.status, payment_attempt.status)?;
+    if helpers::is_capture_reversible(
+        payment_intent.status,
+        payment_attempt.status,
+        payment_attempt.connector.as_deref(),
+        &state.conf.capture_reversal_supported_connectors,
+    ) {
+        return payments_core::<api::Void, api::PaymentsResponse, _, _, _, Ctx>(
+            state,
+            merchant_account,
+            key_store,
+            PaymentCancel,
+            req,
+            services::AuthFlow::Merchant,
+            CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+        .await;
+    }
+
+    logger::info!(
+        "Capture of the payment {} cannot be reversed by {:?} (settled: {is_capture_settled}), refunding it instead",
+        payment_intent.payment_id,
+        payment_attempt.connector
+    );
+    crate::core::refunds::refund_create_core(
+        state.clone(),
+        merchant_account.clone(),
+        key_store.clone(),
+        api_models::refunds::RefundRequest {
+            refund_id: None,
+            payment_id: payment_intent.payment_id.clone(),
+            merchant_id: Some(merchant_account.merchant_id.clone()),
+            amount: payment_intent.amount_captured,
+            reason: req.cancellation_reason,
+            refund_type: Some(api_models::refunds::RefundType::Instant),
+            metadata: None,
+            merchant_connector_details: req.merchant_connector_details,
+        },
+    )
+    .await?;
+
+    payments_core::<api::PSync, api::PaymentsResponse, _, _, _, Ctx>(
+        state,
+        merchant_account,
+        key_store,
+        PaymentStatus,
+        api::PaymentsRetrieveRequest {
+            resource_id: api::PaymentIdType::PaymentIntentId(payment_intent.payment_id),
+            ..Default::default()
+        },
+        services::AuthFlow::Merchant,
+        CallConnectorAction::Avoid,
+        HeaderPayload::default(),
+    )
+    .await
+}
+
+/// Retries a failed payment on the connector of its failed attempt. The payment is confirmed
+/// again as a manual retry, which makes a new attempt under the same payment with the payment
+/// method of the failed attempt.
+#[instrument(skip_all)]
+pub async fn payments_retry_core<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentsRetryRequest,
+) -> RouterResponse<api::PaymentsResponse> {
+    let db = &*state.store;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &req.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt.get_id(),
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let retry_request = helpers::get_retry_request(&payment_intent, &payment_attempt)?;
+    helpers::validate_payment_token_for_retry(db, &payment_attempt).await?;
+
+    payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Ctx>(
+        state,
+        merchant_account,
+        key_store,
+        PaymentConfirm,
+        retry_request,
+        services::AuthFlow::Merchant,
+        CallConnectorAction::Trigger,
+        HeaderPayload::default(),
+    )
+    .await
+}
+
 pub fn should_call_connector<Op: Debug, F: Clone>(
     operation: &Op,
     payment_data: &PaymentData<F>,
@@ -1573,6 +1958,7 @@ pub fn should_call_connector<Op: Debug, F: Clone>(
             payment_data.payment_intent.status,
             storage_enums::IntentStatus::RequiresCapture
                 | storage_enums::IntentStatus::PartiallyCaptured
+                | storage_enums::IntentStatus::Processing
         ),
         "PaymentCapture" => {
             matches!(
@@ -1851,6 +2237,7 @@ where
         )
         .await?;
 
+    let connector_routing_trail = payment_data.payment_intent.connector_routing_trail.clone();
     let connector = if should_call_connector(operation, payment_data) {
         Some(match connector_choice {
             api::ConnectorChoice::SessionMultiple(session_connectors) => {
@@ -1877,9 +2264,53 @@ where
         None
     };
 
+    if payment_data.payment_intent.connector_routing_trail != connector_routing_trail {
+        payment_data.payment_intent = state
+            .store
+            .update_payment_intent(
+                payment_data.payment_intent.clone(),
+                storage::PaymentIntentUpdate::RoutingTrailUpdate {
+                    connector_routing_trail: payment_data
+                        .payment_intent
+                        .connector_routing_trail
+                        .clone()
+                        .unwrap_or_default(),
+                },
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+            .attach_printable("Failed to record the routed connector in the routing trail")?;
+    }
+
     Ok(connector)
 }
 
+/// Installments are validated against every connector the payment can be routed to, whether it
+/// was picked by routing, passed in the request or stored on the attempt of a retried payment.
+fn validate_installments_for_connector_call(
+    installments: Option<&api_models::payments::InstallmentData>,
+    connector_call_type: &api::ConnectorCallType,
+    installment_supported_connectors: &InstallmentSupportedConnectors,
+) -> RouterResult<()> {
+    match connector_call_type {
+        api::ConnectorCallType::Single(connector_data) => helpers::validate_installments_support(
+            installments,
+            &connector_data.connector_name,
+            installment_supported_connectors,
+        ),
+        api::ConnectorCallType::Multiple(session_connectors) => {
+            session_connectors.iter().try_for_each(|session_connector| {
+                helpers::validate_installments_support(
+                    installments,
+                    &session_connector.connector.connector_name,
+                    installment_supported_connectors,
+                )
+            })
+        }
+    }
+}
+
 pub fn connector_selection<F>(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
@@ -1933,6 +2364,11 @@ where
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to serialize routing algorithm to serde value")?;
 
+    payment_data.payment_intent.connector_routing_trail =
+        helpers::append_connector_to_routing_trail(
+            payment_data.payment_intent.connector_routing_trail.take(),
+            routing_data.routed_through.as_ref(),
+        );
     payment_data.payment_attempt.connector = routing_data.routed_through;
     payment_data.payment_attempt.straight_through_algorithm = encoded_algorithm;
 
@@ -2031,3 +2467,832 @@ pub fn should_add_task_to_process_tracker<F: Clone>(payment_data: &PaymentData<F
         )
     )
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{
+        configs::settings::{
+            CardHolderNameRequiredConnectors, ConnectorAmountLimits, ConnectorAuthHoldWindow,
+            ConnectorDefaultAuthenticationType, MinimumThreeDsVersion, RiskDataSupportedConnectors,
+            Settings,
+        },
+        core::payment_methods::Oss,
+        db::{MasterKeyInterface, StorageImpl},
+    };
+
+    async fn insert_payment_routed_through(
+        state: &AppState,
+        payment_id: &str,
+        connector_routing_trail: Vec<String>,
+    ) {
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let attempt_id = format!("{payment_id}_2");
+        let current_time = common_utils::date_time::now();
+        state
+            .store
+            .insert_payment_intent(
+                storage::PaymentIntentNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    status: storage_enums::IntentStatus::RequiresCapture,
+                    amount: 1000,
+                    currency: Some(storage_enums::Currency::USD),
+                    amount_captured: None,
+                    customer_id: None,
+                    description: None,
+                    return_url: None,
+                    metadata: None,
+                    connector_id: None,
+                    shipping_address_id: None,
+                    billing_address_id: None,
+                    statement_descriptor_name: None,
+                    statement_descriptor_suffix: None,
+                    created_at: None,
+                    modified_at: None,
+                    last_synced: None,
+                    setup_future_usage: None,
+                    off_session: None,
+                    client_secret: None,
+                    active_attempt: data_models::RemoteStorageObject::ForeignID(attempt_id.clone()),
+                    business_country: None,
+                    business_label: None,
+                    order_details: None,
+                    allowed_payment_method_types: None,
+                    connector_metadata: None,
+                    feature_metadata: None,
+                    attempt_count: 2,
+                    profile_id: None,
+                    merchant_decision: None,
+                    payment_link_id: None,
+                    payment_confirm_source: None,
+                    connector_routing_trail: Some(connector_routing_trail),
+                    statement_descriptor_city: None,
+                    shipping_amount: None,
+                    handling_amount: None,
+                    client_secret_expiry: None,
+                    success_url: None,
+                    failure_url: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        // The attempt created for the failover has not been routed to a connector yet
+        state
+            .store
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: attempt_id.clone(),
+                    status: storage_enums::AttemptStatus::Authorized,
+                    amount: 1000,
+                    currency: Some(storage_enums::Currency::USD),
+                    connector: None,
+                    straight_through_algorithm: Some(
+                        serde_json::json!({ "type": "single", "data": "adyen" }),
+                    ),
+                    payment_method: Some(storage_enums::PaymentMethod::Card),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_connector_response(
+                storage::ConnectorResponseNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id,
+                    created_at: current_time,
+                    modified_at: current_time,
+                    connector_name: None,
+                    connector_transaction_id: None,
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn test_routing_trail_records_every_connector_the_payment_is_routed_to() {
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let state = AppState::with_storage(
+            Settings::new().expect("invalid settings"),
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let key_store =
+            test_utils::get_merchant_key_store(state.store.get_master_key(), "merchant_1").await;
+        let merchant_account = test_utils::get_merchant_account();
+        // The first attempt of the payment was routed to stripe and failed
+        insert_payment_routed_through(&state, "pay_failover", vec!["stripe".to_string()]).await;
+
+        let request = api::PaymentsCancelRequest {
+            payment_id: "pay_failover".to_string(),
+            ..Default::default()
+        };
+        let (operation, mut payment_data, _) = operations::GetTracker::<
+            api::Void,
+            PaymentData<api::Void>,
+            api::PaymentsCancelRequest,
+            Oss,
+        >::get_trackers(
+            &PaymentCancel,
+            &state,
+            &api::PaymentIdType::PaymentIntentId("pay_failover".to_string()),
+            &request,
+            None,
+            &merchant_account,
+            &key_store,
+            services::AuthFlow::Merchant,
+        )
+        .await
+        .unwrap();
+
+        let connector = get_connector_choice(
+            &operation,
+            &state,
+            &request,
+            &merchant_account,
+            &key_store,
+            &mut payment_data,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            connector,
+            Some(api::ConnectorCallType::Single(connector_data))
+                if connector_data.connector_name == enums::Connector::Adyen
+        ));
+
+        let expected_trail = Some(vec!["stripe".to_string(), "adyen".to_string()]);
+        assert_eq!(
+            payment_data.payment_intent.connector_routing_trail,
+            expected_trail
+        );
+        let payment_intent = state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_failover",
+                "merchant_1",
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_intent.connector_routing_trail, expected_trail);
+
+        // The connector of an attempt which has already been routed is not recorded again
+        get_connector_choice(
+            &operation,
+            &state,
+            &request,
+            &merchant_account,
+            &key_store,
+            &mut payment_data,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            payment_data.payment_intent.connector_routing_trail,
+            expected_trail
+        );
+    }
+
+    #[test]
+    fn test_routing_trail_records_connectors_on_failover() {
+        let trail = helpers::append_connector_to_routing_trail(None, Some(&"stripe".to_string()));
+        let trail = helpers::append_connector_to_routing_trail(trail, Some(&"adyen".to_string()));
+
+        assert_eq!(trail, Some(vec!["stripe".to_string(), "adyen".to_string()]));
+        assert_eq!(
+            helpers::append_connector_to_routing_trail(trail.clone(), None),
+            trail
+        );
+    }
+
+    #[test]
+    fn test_redirect_payment_experience_for_wallet() {
+        use api_models::enums::{PaymentExperience, PaymentMethodType};
+
+        let payment_methods_enabled = vec![serde_json::json!({
+            "payment_method": "wallet",
+            "payment_method_types": [
+                {
+                    "payment_method_type": "paypal",
+                    "payment_experience": "redirect_to_url",
+                    "recurring_enabled": true,
+                    "installment_payment_enabled": false
+                }
+            ]
+        })];
+
+        assert!(helpers::validate_payment_experience_for_connector(
+            Some(PaymentExperience::RedirectToUrl),
+            Some(PaymentMethodType::Paypal),
+            Some(&payment_methods_enabled),
+            "paypal",
+        )
+        .is_ok());
+
+        let result = helpers::validate_payment_experience_for_connector(
+            Some(PaymentExperience::InvokeSdkClient),
+            Some(PaymentMethodType::Paypal),
+            Some(&payment_methods_enabled),
+            "paypal",
+        );
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::NotSupported { .. })
+        ));
+
+        // Payment experiences are not restricted when none are configured for the payment method type
+        assert!(helpers::validate_payment_experience_for_connector(
+            Some(PaymentExperience::InvokeSdkClient),
+            Some(PaymentMethodType::ApplePay),
+            Some(&payment_methods_enabled),
+            "paypal",
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_installments_on_non_supporting_connector() {
+        let installment_supported_connectors = InstallmentSupportedConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Adyen]),
+        };
+        let installments = api_models::payments::InstallmentData {
+            number_of_installments: 3,
+        };
+
+        let result = helpers::validate_installments_support(
+            Some(&installments),
+            &api_models::enums::Connector::Stripe,
+            &installment_supported_connectors,
+        );
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::NotImplemented {
+                message: errors::api_error_response::NotImplementedMessage::Reason(message)
+            }) if message == "Installments for stripe"
+        ));
+        assert!(helpers::validate_installments_support(
+            Some(&installments),
+            &api_models::enums::Connector::Adyen,
+            &installment_supported_connectors,
+        )
+        .is_ok());
+        assert!(helpers::validate_installments_support(
+            None,
+            &api_models::enums::Connector::Stripe,
+            &installment_supported_connectors,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_card_holder_name_for_name_requiring_connector() {
+        let card_holder_name_required_connectors = CardHolderNameRequiredConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Adyen]),
+        };
+        let card_with_name = api::PaymentMethodData::Card(api_models::payments::Card {
+            card_holder_name: masking::Secret::new("John Test".to_string()),
+            ..Default::default()
+        });
+        let card_without_name = api::PaymentMethodData::Card(api_models::payments::Card {
+            card_holder_name: masking::Secret::new(" ".to_string()),
+            ..Default::default()
+        });
+
+        assert!(helpers::validate_card_holder_name_for_connector(
+            Some(&card_with_name),
+            &api_models::enums::Connector::Adyen,
+            &card_holder_name_required_connectors,
+        )
+        .is_ok());
+        assert!(matches!(
+            helpers::validate_card_holder_name_for_connector(
+                Some(&card_without_name),
+                &api_models::enums::Connector::Adyen,
+                &card_holder_name_required_connectors,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "payment_method_data.card.card_holder_name"
+            })
+        ));
+        assert!(helpers::validate_card_holder_name_for_connector(
+            Some(&card_without_name),
+            &api_models::enums::Connector::Stripe,
+            &card_holder_name_required_connectors,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_risk_data_is_forwarded_to_supporting_connector() {
+        let risk_data_supported_connectors = RiskDataSupportedConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Adyen]),
+        };
+        let risk_data = serde_json::json!({ "risk_score": "12", "decision": "approve" });
+
+        assert!(helpers::validate_risk_data(Some(&risk_data)).is_ok());
+        assert_eq!(
+            helpers::get_risk_data_for_connector(
+                Some(risk_data.clone()),
+                &api_models::enums::Connector::Adyen,
+                &risk_data_supported_connectors,
+            ),
+            Some(risk_data.clone())
+        );
+        assert_eq!(
+            helpers::get_risk_data_for_connector(
+                Some(risk_data),
+                &api_models::enums::Connector::Stripe,
+                &risk_data_supported_connectors,
+            ),
+            None
+        );
+        assert!(matches!(
+            helpers::validate_risk_data(Some(&serde_json::json!("approve")))
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "risk_data"
+        ));
+    }
+
+    #[test]
+    fn test_amount_outside_connector_amount_limits() {
+        let amount_limits = ConnectorAmountLimits(std::collections::HashMap::from([(
+            "stripe".to_string(),
+            std::collections::HashMap::from([(
+                api_models::enums::Currency::USD,
+                crate::configs::settings::AmountLimit {
+                    min_amount: Some(50),
+                    max_amount: Some(99999999),
+                },
+            )]),
+        )]));
+        let stripe = api_models::enums::Connector::Stripe;
+
+        assert!(matches!(
+            helpers::validate_amount_for_connector(
+                49,
+                api_models::enums::Currency::USD,
+                &stripe,
+                &amount_limits,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                if message.contains("minimum amount of 50")
+        ));
+        assert!(matches!(
+            helpers::validate_amount_for_connector(
+                100000000,
+                api_models::enums::Currency::USD,
+                &stripe,
+                &amount_limits,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                if message.contains("maximum amount of 99999999")
+        ));
+        assert!(helpers::validate_amount_for_connector(
+            6540,
+            api_models::enums::Currency::USD,
+            &stripe,
+            &amount_limits,
+        )
+        .is_ok());
+        assert!(helpers::validate_amount_for_connector(
+            49,
+            api_models::enums::Currency::EUR,
+            &stripe,
+            &amount_limits,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_capture_on_beyond_connector_auth_hold_window() {
+        let auth_hold_window =
+            ConnectorAuthHoldWindow(std::collections::HashMap::from([("stripe".to_string(), 7)]));
+        let now = common_utils::date_time::now();
+
+        assert!(matches!(
+            helpers::validate_capture_on_for_connector(
+                Some(now + time::Duration::days(10)),
+                &api_models::enums::Connector::Stripe,
+                &auth_hold_window,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "capture_on"
+        ));
+        assert!(helpers::validate_capture_on_for_connector(
+            Some(now + time::Duration::days(5)),
+            &api_models::enums::Connector::Stripe,
+            &auth_hold_window,
+        )
+        .is_ok());
+        assert!(helpers::validate_capture_on_for_connector(
+            Some(now + time::Duration::days(10)),
+            &api_models::enums::Connector::Adyen,
+            &auth_hold_window,
+        )
+        .is_ok());
+    }
+
+    fn get_failed_attempt(payment_id: &str, payment_token: String) -> storage::PaymentAttemptNew {
+        storage::PaymentAttemptNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            attempt_id: format!("{payment_id}_1"),
+            status: storage_enums::AttemptStatus::Failure,
+            amount: 1000,
+            connector: Some("stripe".to_string()),
+            payment_method: Some(storage_enums::PaymentMethod::Card),
+            payment_token: Some(payment_token),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_payment_is_retried_on_the_same_connector() {
+        use data_models::payments::payment_attempt::PaymentAttemptInterface;
+        use storage_impl::MockDb;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let payment_intent =
+            test_utils::get_payment_intent("pay_retry_1", storage_enums::IntentStatus::Failed);
+        mock_db
+            .payment_intents
+            .lock()
+            .await
+            .push(payment_intent.clone());
+        let failed_attempt = mock_db
+            .insert_payment_attempt(
+                get_failed_attempt("pay_retry_1", "token_retry_1".to_string()),
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        let retry_request = helpers::get_retry_request(&payment_intent, &failed_attempt).unwrap();
+        assert_eq!(retry_request.confirm, Some(true));
+        assert_eq!(
+            retry_request.payment_token,
+            Some("token_retry_1".to_string())
+        );
+        assert_eq!(
+            retry_request.routing,
+            Some(serde_json::json!({ "algorithm": { "type": "single", "data": "stripe" } }))
+        );
+
+        let attempt_type =
+            helpers::get_attempt_type(&payment_intent, &failed_attempt, &retry_request, "confirm")
+                .unwrap();
+        assert_eq!(attempt_type, helpers::AttemptType::New);
+        let (payment_intent, payment_attempt) = attempt_type
+            .modify_payment_intent_and_payment_attempt(
+                &retry_request,
+                payment_intent,
+                failed_attempt,
+                &mock_db,
+                storage_scheme,
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_intent.payment_id, "pay_retry_1");
+        assert_eq!(payment_intent.attempt_count, 2);
+        assert_eq!(payment_intent.active_attempt.get_id(), "pay_retry_1_2");
+        assert_eq!(payment_attempt.payment_id, "pay_retry_1");
+        assert_eq!(payment_attempt.attempt_id, "pay_retry_1_2");
+
+        // Only failed payments can be retried
+        assert!(matches!(
+            helpers::get_retry_request(&payment_intent, &payment_attempt)
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_expired_payment_token_is_rejected() {
+        use data_models::payments::payment_attempt::PaymentAttemptInterface;
+        use storage_impl::{redis::kv_store::RedisConnInterface, MockDb};
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let payment_token = format!("token_{}", uuid::Uuid::new_v4());
+        let failed_attempt = mock_db
+            .insert_payment_attempt(
+                get_failed_attempt("pay_retry_2", payment_token.clone()),
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            helpers::validate_payment_token_for_retry(&mock_db, &failed_attempt)
+                .await
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { message })
+                if message.contains("expired")
+        ));
+
+        mock_db
+            .get_redis_conn()
+            .unwrap()
+            .set_key_with_expiry(
+                &format!("pm_token_{payment_token}_card_hyperswitch"),
+                "hyperswitch_token",
+                60,
+            )
+            .await
+            .unwrap();
+        assert!(
+            helpers::validate_payment_token_for_retry(&mock_db, &failed_attempt)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_authentication_type_defaults_to_connector_default() {
+        let default_authentication_types =
+            ConnectorDefaultAuthenticationType(std::collections::HashMap::from([(
+                "checkout".to_string(),
+                api_models::enums::AuthenticationType::ThreeDs,
+            )]));
+
+        assert_eq!(
+            helpers::get_authentication_type_for_connector(
+                None,
+                &api_models::enums::Connector::Checkout,
+                &default_authentication_types,
+            ),
+            Some(api_models::enums::AuthenticationType::ThreeDs)
+        );
+        assert_eq!(
+            helpers::get_authentication_type_for_connector(
+                Some(api_models::enums::AuthenticationType::NoThreeDs),
+                &api_models::enums::Connector::Checkout,
+                &default_authentication_types,
+            ),
+            Some(api_models::enums::AuthenticationType::NoThreeDs)
+        );
+        assert_eq!(
+            helpers::get_authentication_type_for_connector(
+                None,
+                &api_models::enums::Connector::Stripe,
+                &default_authentication_types,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_three_ds_version_below_connector_minimum() {
+        let minimum_three_ds_version = MinimumThreeDsVersion(std::collections::HashMap::from([(
+            "adyen".to_string(),
+            crate::configs::settings::ConnectorMinimumThreeDsVersion {
+                default: Some("2.1.0".to_string()),
+                countries: std::collections::HashMap::from([(
+                    api_models::enums::CountryAlpha2::DE,
+                    "2.2.0".to_string(),
+                )]),
+            },
+        )]));
+
+        // 3DS1 authentications are below the default minimum version of the connector
+        assert_eq!(
+            helpers::get_unmet_minimum_three_ds_version(
+                &minimum_three_ds_version,
+                "adyen",
+                Some(api_models::enums::CountryAlpha2::US),
+                "1.0.2",
+            ),
+            Some(&"2.1.0".to_string())
+        );
+        assert_eq!(
+            helpers::get_unmet_minimum_three_ds_version(
+                &minimum_three_ds_version,
+                "adyen",
+                None,
+                "2.1",
+            ),
+            None
+        );
+        // The version configured for the business country takes precedence
+        assert_eq!(
+            helpers::get_unmet_minimum_three_ds_version(
+                &minimum_three_ds_version,
+                "adyen",
+                Some(api_models::enums::CountryAlpha2::DE),
+                "2.1.0",
+            ),
+            Some(&"2.2.0".to_string())
+        );
+        assert_eq!(
+            helpers::get_unmet_minimum_three_ds_version(
+                &minimum_three_ds_version,
+                "adyen",
+                Some(api_models::enums::CountryAlpha2::DE),
+                "2.2.0",
+            ),
+            None
+        );
+        // Connectors without a configured minimum version are not enforced
+        assert_eq!(
+            helpers::get_unmet_minimum_three_ds_version(
+                &minimum_three_ds_version,
+                "stripe",
+                None,
+                "1.0.2",
+            ),
+            None
+        );
+    }
+
+    fn get_three_ds_authenticated_router_data<F>(
+        three_ds_version: &str,
+    ) -> router_types::RouterData<F, (), router_types::PaymentsResponseData> {
+        router_types::RouterData {
+            flow: std::marker::PhantomData,
+            merchant_id: "merchant_1".to_string(),
+            customer_id: None,
+            connector_customer: None,
+            connector: "adyen".to_string(),
+            payment_id: "pay_1".to_string(),
+            attempt_id: "pay_1_1".to_string(),
+            status: storage_enums::AttemptStatus::Charged,
+            payment_method: storage_enums::PaymentMethod::Card,
+            connector_auth_type: crate::types::ConnectorAuthType::default(),
+            description: None,
+            return_url: None,
+            address: PaymentAddress::default(),
+            auth_type: storage_enums::AuthenticationType::ThreeDs,
+            connector_meta_data: None,
+            amount_captured: None,
+            access_token: None,
+            session_token: None,
+            reference_id: None,
+            payment_method_token: None,
+            recurring_mandate_payment_data: None,
+            preprocessing_id: None,
+            payment_method_balance: None,
+            connector_api_version: None,
+            request: (),
+            response: Ok(router_types::PaymentsResponseData::TransactionResponse {
+                resource_id: crate::types::ResponseId::ConnectorTransactionId("psp_1".to_string()),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+            }),
+            payment_method_id: None,
+            connector_request_reference_id: "pay_1_1".to_string(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            test_mode: None,
+            connector_http_status_code: Some(200),
+            external_latency: None,
+            debug_connector_io: false,
+            connector_io_log: None,
+            connector_authorized_amount: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_metadata: None,
+            three_ds_authentication_result: Some(crate::types::ThreeDsAuthenticationResult {
+                three_ds_version: Some(three_ds_version.to_string()),
+                liability_shift: None,
+                challenge_required: None,
+            }),
+            apple_pay_flow: None,
+        }
+    }
+
+    #[test]
+    fn test_three_ds1_authentication_is_reported_without_failing_the_attempt() {
+        let minimum_three_ds_version = MinimumThreeDsVersion(std::collections::HashMap::from([(
+            "adyen".to_string(),
+            crate::configs::settings::ConnectorMinimumThreeDsVersion {
+                default: Some("2.1.0".to_string()),
+                countries: std::collections::HashMap::new(),
+            },
+        )]));
+
+        let router_data =
+            get_three_ds_authenticated_router_data::<crate::types::api::Authorize>("1.0.2");
+        assert!(helpers::get_three_ds_version_below_minimum_message(
+            &minimum_three_ds_version,
+            None,
+            &router_data,
+        )
+        .is_some());
+        // The connector has already authorized the payment, its outcome is kept
+        assert_eq!(router_data.status, storage_enums::AttemptStatus::Charged);
+        assert!(router_data.response.is_ok());
+
+        assert_eq!(
+            helpers::get_three_ds_version_below_minimum_message(
+                &minimum_three_ds_version,
+                None,
+                &get_three_ds_authenticated_router_data::<crate::types::api::Authorize>("2.2.0"),
+            ),
+            None
+        );
+
+        // The authentication is not checked again when the payment is synced
+        assert_eq!(
+            helpers::get_three_ds_version_below_minimum_message(
+                &minimum_three_ds_version,
+                None,
+                &get_three_ds_authenticated_router_data::<crate::types::api::PSync>("1.0.2"),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_connector_request_time_is_recorded_for_the_connector_call() {
+        use router_env::opentelemetry::{
+            metrics::MeterProvider,
+            sdk::{
+                export::metrics::{
+                    aggregation::{cumulative_temporality_selector, Count},
+                    InstrumentationLibraryReader,
+                },
+                metrics::{aggregators::HistogramAggregator, controllers, processors, selectors},
+            },
+        };
+
+        let controller = controllers::basic(processors::factory(
+            selectors::simple::histogram([0.1, 1.0]),
+            cumulative_temporality_selector(),
+        ))
+        .build();
+        let connector_request_time = controller
+            .meter("ROUTER_API")
+            .f64_histogram("CONNECTOR_REQUEST_TIME")
+            .init();
+
+        helpers::record_connector_request_time(
+            &connector_request_time,
+            "stripe",
+            "Authorize".to_string(),
+            Some(120),
+        );
+        // Nothing is recorded when the latency of the call is not known
+        helpers::record_connector_request_time(
+            &connector_request_time,
+            "stripe",
+            "Authorize".to_string(),
+            None,
+        );
+
+        controller
+            .collect(&crate::routes::metrics::CONTEXT)
+            .unwrap();
+        let mut recorded_calls = 0;
+        controller
+            .try_for_each(&mut |_, reader| {
+                reader.try_for_each(&cumulative_temporality_selector(), &mut |record| {
+                    if let Some(histogram) = record.aggregator().and_then(|aggregator| {
+                        aggregator.as_any().downcast_ref::<HistogramAggregator>()
+                    }) {
+                        recorded_calls += histogram.count()?;
+                    }
+                    Ok(())
+                })
+            })
+            .unwrap();
+        assert_eq!(recorded_calls, 1);
+    }
+}
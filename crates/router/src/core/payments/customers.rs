@@ -140,3 +140,76 @@ pub async fn update_connector_customer_in_customers(
             },
         )
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_connector_customer_is_reused_on_the_second_payment() {
+        let mut customer = domain::Customer {
+            id: None,
+            customer_id: "cus_123".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            name: None,
+            email: None,
+            phone: None,
+            phone_country_code: None,
+            description: None,
+            created_at: common_utils::date_time::now(),
+            metadata: None,
+            modified_at: common_utils::date_time::now(),
+            connector_customer: None,
+            address_id: None,
+        };
+
+        // First payment, the customer is created at the connector and the mapping is stored
+        assert_eq!(
+            get_connector_customer_details_if_present(&customer, "stripe_default"),
+            None
+        );
+        let customer_update = update_connector_customer_in_customers(
+            "stripe_default",
+            Some(&customer),
+            &Some("cus_stripe_1".to_string()),
+        )
+        .await;
+        if let Some(storage::CustomerUpdate::ConnectorCustomer { connector_customer }) =
+            customer_update
+        {
+            customer.connector_customer = connector_customer;
+        }
+
+        // Second payment through the same connector reuses the stored connector customer
+        assert_eq!(
+            get_connector_customer_details_if_present(&customer, "stripe_default"),
+            Some("cus_stripe_1")
+        );
+        assert_eq!(
+            get_connector_customer_details_if_present(&customer, "adyen_default"),
+            None
+        );
+
+        // Mapping the customer for another connector keeps the existing mapping
+        let customer_update = update_connector_customer_in_customers(
+            "adyen_default",
+            Some(&customer),
+            &Some("cus_adyen_1".to_string()),
+        )
+        .await;
+        if let Some(storage::CustomerUpdate::ConnectorCustomer { connector_customer }) =
+            customer_update
+        {
+            customer.connector_customer = connector_customer;
+        }
+        assert_eq!(
+            get_connector_customer_details_if_present(&customer, "stripe_default"),
+            Some("cus_stripe_1")
+        );
+        assert_eq!(
+            get_connector_customer_details_if_present(&customer, "adyen_default"),
+            Some("cus_adyen_1")
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use masking::PeekInterface;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+use super::PaymentData;
+use crate::types::{
+    api,
+    storage::{self, enums},
+};
+
+const REDACTED: &str = "*** redacted ***";
+
+/// Redacted snapshot of the state of a payment, meant to be shared while debugging payments which
+/// are stuck. Card details are masked and fields which are not needed to reason about the state
+/// of the payment are left out.
+#[derive(Debug, Clone, Serialize)]
+pub struct PaymentDataSnapshot {
+    pub payment_intent: PaymentIntentSnapshot,
+    pub payment_attempt: PaymentAttemptSnapshot,
+    pub connector_response: ConnectorResponseSnapshot,
+    pub payment_method_data: Option<PaymentMethodDataSnapshot>,
+    /// The CVC is never part of the snapshot, only whether it was provided
+    pub card_cvc: Option<&'static str>,
+    pub confirm: Option<bool>,
+    pub force_sync: Option<bool>,
+    pub refunds: Vec<RefundSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaymentIntentSnapshot {
+    pub payment_id: String,
+    pub merchant_id: String,
+    pub status: enums::IntentStatus,
+    pub amount: i64,
+    pub currency: Option<enums::Currency>,
+    pub amount_captured: Option<i64>,
+    pub active_attempt_id: String,
+    pub attempt_count: i16,
+    pub setup_future_usage: Option<enums::FutureUsage>,
+    pub off_session: Option<bool>,
+    pub profile_id: Option<String>,
+    pub merchant_decision: Option<String>,
+    pub connector_routing_trail: Option<Vec<String>>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_synced: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaymentAttemptSnapshot {
+    pub attempt_id: String,
+    pub status: enums::AttemptStatus,
+    pub connector: Option<String>,
+    pub connector_transaction_id: Option<String>,
+    pub amount: i64,
+    pub amount_to_capture: Option<i64>,
+    pub amount_capturable: i64,
+    pub capture_method: Option<enums::CaptureMethod>,
+    pub authentication_type: Option<enums::AuthenticationType>,
+    pub payment_method: Option<enums::PaymentMethod>,
+    pub payment_method_type: Option<enums::PaymentMethodType>,
+    pub multiple_capture_count: Option<i16>,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub error_reason: Option<String>,
+    pub avs_result: Option<String>,
+    pub cvv_result: Option<String>,
+    pub payment_channel: Option<enums::PaymentChannel>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_synced: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectorResponseSnapshot {
+    pub connector_name: Option<String>,
+    pub connector_transaction_id: Option<String>,
+    /// Whether the connector returned data to authenticate the customer, the data itself is
+    /// not part of the snapshot
+    pub has_authentication_data: bool,
+    pub three_ds_version: Option<String>,
+    pub liability_shift: Option<bool>,
+    pub challenge_required: Option<bool>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub settled_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentMethodDataSnapshot {
+    Card {
+        /// Card number with every digit except the card isin and the last four masked
+        card_number: String,
+        card_network: Option<enums::CardNetwork>,
+    },
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefundSnapshot {
+    pub refund_id: String,
+    pub refund_status: enums::RefundStatus,
+    pub refund_amount: i64,
+}
+
+impl From<&api::PaymentMethodData> for PaymentMethodDataSnapshot {
+    fn from(payment_method_data: &api::PaymentMethodData) -> Self {
+        match payment_method_data {
+            api::PaymentMethodData::Card(card) => {
+                let card_isin = card.card_number.clone().get_card_isin();
+                let card_last4 = card.card_number.clone().get_last4();
+                let masked_length = card
+                    .card_number
+                    .peek()
+                    .len()
+                    .saturating_sub(card_isin.len() + card_last4.len());
+                Self::Card {
+                    card_number: format!("{card_isin}{}{card_last4}", "*".repeat(masked_length)),
+                    card_network: card.card_network.clone(),
+                }
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+impl From<&storage::PaymentIntent> for PaymentIntentSnapshot {
+    fn from(payment_intent: &storage::PaymentIntent) -> Self {
+        Self {
+            payment_id: payment_intent.payment_id.clone(),
+            merchant_id: payment_intent.merchant_id.clone(),
+            status: payment_intent.status,
+            amount: payment_intent.amount,
+            currency: payment_intent.currency,
+            amount_captured: payment_intent.amount_captured,
+            active_attempt_id: payment_intent.active_attempt.get_id(),
+            attempt_count: payment_intent.attempt_count,
+            setup_future_usage: payment_intent.setup_future_usage,
+            off_session: payment_intent.off_session,
+            profile_id: payment_intent.profile_id.clone(),
+            merchant_decision: payment_intent.merchant_decision.clone(),
+            connector_routing_trail: payment_intent.connector_routing_trail.clone(),
+            created_at: payment_intent.created_at,
+            modified_at: payment_intent.modified_at,
+            last_synced: payment_intent.last_synced,
+        }
+    }
+}
+
+impl From<&storage::PaymentAttempt> for PaymentAttemptSnapshot {
+    fn from(payment_attempt: &storage::PaymentAttempt) -> Self {
+        Self {
+            attempt_id: payment_attempt.attempt_id.clone(),
+            status: payment_attempt.status,
+            connector: payment_attempt.connector.clone(),
+            connector_transaction_id: payment_attempt.connector_transaction_id.clone(),
+            amount: payment_attempt.amount,
+            amount_to_capture: payment_attempt.amount_to_capture,
+            amount_capturable: payment_attempt.amount_capturable,
+            capture_method: payment_attempt.capture_method,
+            authentication_type: payment_attempt.authentication_type,
+            payment_method: payment_attempt.payment_method,
+            payment_method_type: payment_attempt.payment_method_type,
+            multiple_capture_count: payment_attempt.multiple_capture_count,
+            error_code: payment_attempt.error_code.clone(),
+            error_message: payment_attempt.error_message.clone(),
+            error_reason: payment_attempt.error_reason.clone(),
+            avs_result: payment_attempt.avs_result.clone(),
+            cvv_result: payment_attempt.cvv_result.clone(),
+            payment_channel: payment_attempt.payment_channel,
+            created_at: payment_attempt.created_at,
+            modified_at: payment_attempt.modified_at,
+            last_synced: payment_attempt.last_synced,
+        }
+    }
+}
+
+impl From<&storage::ConnectorResponse> for ConnectorResponseSnapshot {
+    fn from(connector_response: &storage::ConnectorResponse) -> Self {
+        Self {
+            connector_name: connector_response.connector_name.clone(),
+            connector_transaction_id: connector_response.connector_transaction_id.clone(),
+            has_authentication_data: connector_response.authentication_data.is_some(),
+            three_ds_version: connector_response.three_ds_version.clone(),
+            liability_shift: connector_response.liability_shift,
+            challenge_required: connector_response.challenge_required,
+            settled_at: connector_response.settled_at,
+            modified_at: connector_response.modified_at,
+        }
+    }
+}
+
+impl From<&storage::Refund> for RefundSnapshot {
+    fn from(refund: &storage::Refund) -> Self {
+        Self {
+            refund_id: refund.refund_id.clone(),
+            refund_status: refund.refund_status,
+            refund_amount: refund.refund_amount,
+        }
+    }
+}
+
+impl<F: Clone> PaymentData<F> {
+    /// Returns a redacted snapshot of the payment data which can be serialized for debugging
+    pub fn to_debug_snapshot(&self) -> PaymentDataSnapshot {
+        PaymentDataSnapshot {
+            payment_intent: PaymentIntentSnapshot::from(&self.payment_intent),
+            payment_attempt: PaymentAttemptSnapshot::from(&self.payment_attempt),
+            connector_response: ConnectorResponseSnapshot::from(&self.connector_response),
+            payment_method_data: self
+                .payment_method_data
+                .as_ref()
+                .map(PaymentMethodDataSnapshot::from),
+            card_cvc: redact_card_cvc(self.card_cvc.as_ref(), self.payment_method_data.as_ref()),
+            confirm: self.confirm,
+            force_sync: self.force_sync,
+            refunds: self.refunds.iter().map(RefundSnapshot::from).collect(),
+        }
+    }
+}
+
+fn redact_card_cvc(
+    card_cvc: Option<&masking::Secret<String>>,
+    payment_method_data: Option<&api::PaymentMethodData>,
+) -> Option<&'static str> {
+    let is_cvc_in_payment_method_data = matches!(
+        payment_method_data,
+        Some(api::PaymentMethodData::Card(card)) if !card.card_cvc.peek().is_empty()
+    );
+    (card_cvc.is_some() || is_cvc_in_payment_method_data).then_some(REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use masking::Secret;
+
+    use super::*;
+
+    #[test]
+    fn test_debug_snapshot_redacts_card_details() {
+        let payment_method_data = api::PaymentMethodData::Card(api::Card {
+            card_number: cards::CardNumber::from_str("4242424242424242").unwrap(),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_cvc: Secret::new("737".to_string()),
+            ..Default::default()
+        });
+
+        let snapshot = serde_json::json!({
+            "payment_method_data": PaymentMethodDataSnapshot::from(&payment_method_data),
+            "card_cvc": redact_card_cvc(
+                Some(&Secret::new("737".to_string())),
+                Some(&payment_method_data),
+            ),
+        })
+        .to_string();
+
+        assert!(!snapshot.contains("4242424242424242"));
+        assert!(!snapshot.contains("737"));
+        assert!(snapshot.contains("424242******4242"));
+        assert!(snapshot.contains(REDACTED));
+        assert_eq!(redact_card_cvc(None, None), None);
+    }
+}
@@ -6,7 +6,7 @@ use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ApiErrorResponse, ConnectorErrorExt, RouterResult},
-        payments::{self, access_token, helpers, transformers, PaymentData},
+        payments::{self, access_token, helpers, psync_cache, transformers, PaymentData},
     },
     routes::AppState,
     services::{self, logger},
@@ -84,15 +84,32 @@ impl Feature<api::PSync, types::PaymentsSyncData>
             (types::SyncRequestType::MultipleCaptureSync(_), Err(err)) => Err(err),
             _ => {
                 // for bulk sync of captures, above logic needs to be handled at connector end
-                let resp = services::execute_connector_processing_step(
-                    state,
-                    connector_integration,
-                    &self,
-                    call_connector_action,
-                    connector_request,
+                let connector_transaction_id = self.request.connector_transaction_id.clone();
+                let force_refresh = self.request.force_refresh
+                    || call_connector_action != payments::CallConnectorAction::Trigger;
+                let merchant_id = self.merchant_id.clone();
+                let connector_name = self.connector.clone();
+                let resp = psync_cache::sync_with_cache(
+                    &*state.store,
+                    &state.conf.psync_cache,
+                    &merchant_id,
+                    &connector_name,
+                    &connector_transaction_id,
+                    force_refresh,
+                    self,
+                    |router_data| async move {
+                        services::execute_connector_processing_step(
+                            state,
+                            connector_integration,
+                            &router_data,
+                            call_connector_action,
+                            connector_request,
+                        )
+                        .await
+                        .to_payment_failed_response()
+                    },
                 )
-                .await
-                .to_payment_failed_response()?;
+                .await?;
                 Ok(resp)
             }
         }
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, str::FromStr};
 
 use base64::Engine;
 use common_utils::{
@@ -28,16 +28,23 @@ use x509_parser::parse_x509_certificate;
 
 use super::{
     operations::{BoxedOperation, Operation, PaymentResponse},
-    CustomerDetails, PaymentData,
+    CustomerDetails, PaymentData, ResolvedPaymentMethod,
 };
 use crate::{
-    configs::settings::{ConnectorRequestReferenceIdConfig, Server, TempLockerEnableConfig},
+    configs::settings::{
+        CaptureReversalSupportedConnectors, CardFingerprint, CardHolderNameRequiredConnectors,
+        ConnectorAmountLimits, ConnectorAuthHoldWindow, ConnectorDefaultAuthenticationType,
+        ConnectorRequestReferenceIdConfig, DateOnlyCaptureOnConnectors, DccSupportedConnectors,
+        InstallmentSupportedConnectors, MinimumThreeDsVersion, OpenPaymentIntentsLimit,
+        ResponseMaskingPolicy, RiskDataSupportedConnectors, RoundingMode, Server,
+        TempLockerEnableConfig,
+    },
     connector,
     consts::{self, BASE64_ENGINE},
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::{cards, vault, PaymentMethodRetrieve},
-        payments,
+        payments, utils as core_utils,
     },
     db::StorageInterface,
     routes::{metrics, payment_methods, AppState},
@@ -48,9 +55,13 @@ use crate::{
             self,
             types::{self, AsyncLift},
         },
-        storage::{self, enums as storage_enums, ephemeral_key, CustomerUpdate::Update},
+        storage::{
+            self, enums as storage_enums, ephemeral_key, payment_attempt::AttemptStatusExt,
+            CustomerUpdate::Update,
+        },
         transformers::{ForeignFrom, ForeignTryFrom},
-        ErrorResponse, MandateReference, RouterData,
+        ConnectorResponseMetadata, ErrorResponse, MandateReference, PaymentsResponseData,
+        RouterData,
     },
     utils::{
         self,
@@ -265,6 +276,7 @@ pub async fn create_or_find_address_for_payment_by_request(
     let key = merchant_key_store.key.get_inner().peek();
 
     Ok(match address_id {
+        // The lookup is scoped to the merchant, addresses of other merchants are not found
         Some(id) => Some(
             db.find_address_by_merchant_id_payment_id_address_id(
                 merchant_id,
@@ -392,60 +404,1369 @@ pub async fn get_address_by_id(
     }
 }
 
-pub async fn get_token_pm_type_mandate_details(
-    state: &AppState,
-    request: &api::PaymentsRequest,
-    mandate_type: Option<api::MandateTransactionType>,
-    merchant_account: &domain::MerchantAccount,
-) -> RouterResult<(
-    Option<String>,
-    Option<storage_enums::PaymentMethod>,
-    Option<storage_enums::PaymentMethodType>,
-    Option<MandateData>,
-    Option<payments::RecurringMandatePaymentData>,
-    Option<String>,
-)> {
-    let mandate_data = request.mandate_data.clone().map(MandateData::foreign_from);
-    match mandate_type {
-        Some(api::MandateTransactionType::NewMandateTransaction) => {
-            let setup_mandate = mandate_data.clone().get_required_value("mandate_data")?;
-            Ok((
-                request.payment_token.to_owned(),
-                request.payment_method,
-                request.payment_method_type,
-                Some(setup_mandate),
-                None,
-                None,
-            ))
-        }
-        Some(api::MandateTransactionType::RecurringMandateTransaction) => {
-            let (
-                token_,
-                payment_method_,
-                recurring_mandate_payment_data,
-                payment_method_type_,
-                mandate_connector,
-            ) = get_token_for_recurring_mandate(state, request, merchant_account).await?;
-            Ok((
-                token_,
-                payment_method_,
-                payment_method_type_.or(request.payment_method_type),
-                None,
-                recurring_mandate_payment_data,
-                mandate_connector,
-            ))
-        }
-        None => Ok((
-            request.payment_token.to_owned(),
-            request.payment_method,
-            request.payment_method_type,
-            mandate_data,
-            None,
-            None,
-        )),
+pub async fn get_token_pm_type_mandate_details(
+    state: &AppState,
+    request: &api::PaymentsRequest,
+    mandate_type: Option<api::MandateTransactionType>,
+    merchant_account: &domain::MerchantAccount,
+) -> RouterResult<(
+    Option<String>,
+    Option<storage_enums::PaymentMethod>,
+    Option<storage_enums::PaymentMethodType>,
+    Option<MandateData>,
+    Option<payments::RecurringMandatePaymentData>,
+    Option<String>,
+)> {
+    if let Some(api::PaymentMethodData::Card(card)) = &request.payment_method_data {
+        validate_card_fields_are_not_empty(card)?;
+    }
+
+    let mandate_data = request.mandate_data.clone().map(MandateData::foreign_from);
+    let payment_method = get_payment_method_with_bank_transfer_fallback(request);
+    match mandate_type {
+        Some(api::MandateTransactionType::NewMandateTransaction) => {
+            let setup_mandate = mandate_data.clone().get_required_value("mandate_data")?;
+            Ok((
+                request.payment_token.to_owned(),
+                payment_method,
+                request.payment_method_type,
+                Some(setup_mandate),
+                None,
+                None,
+            ))
+        }
+        Some(api::MandateTransactionType::RecurringMandateTransaction) => {
+            let (
+                token_,
+                payment_method_,
+                recurring_mandate_payment_data,
+                payment_method_type_,
+                mandate_connector,
+            ) = get_token_for_recurring_mandate(state, request, merchant_account).await?;
+            Ok((
+                token_,
+                payment_method_,
+                payment_method_type_.or(request.payment_method_type),
+                None,
+                recurring_mandate_payment_data,
+                mandate_connector,
+            ))
+        }
+        None => Ok((
+            request.payment_token.to_owned(),
+            payment_method,
+            request.payment_method_type,
+            mandate_data,
+            None,
+            None,
+        )),
+    }
+}
+
+/// Derives `off_session` from `setup_future_usage` when it is not passed explicitly.
+/// An explicitly passed `off_session` always takes precedence.
+pub fn get_off_session(
+    off_session: Option<bool>,
+    setup_future_usage: Option<api_enums::FutureUsage>,
+) -> Option<bool> {
+    off_session.or(setup_future_usage.map(|future_usage| match future_usage {
+        api_enums::FutureUsage::OffSession => true,
+        api_enums::FutureUsage::OnSession => false,
+    }))
+}
+
+/// A payment method set up for future on session payments cannot be charged while the customer is
+/// off session
+pub fn validate_setup_future_usage_and_off_session(
+    setup_future_usage: Option<api_enums::FutureUsage>,
+    off_session: Option<bool>,
+) -> RouterResult<()> {
+    utils::when(
+        setup_future_usage == Some(api_enums::FutureUsage::OnSession) && off_session == Some(true),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`off_session` cannot be `true` when `setup_future_usage` is `on_session`"
+                    .to_string(),
+            }))
+        },
+    )
+}
+
+/// Orders the refunds of a payment by the time at which they were created. Refunds created at the
+/// same time are ordered by their id, so that the order is deterministic.
+pub fn sort_refunds(refunds: &mut [storage::Refund]) {
+    refunds.sort_by_key(|refund| (refund.created_at, refund.id));
+}
+
+/// Returns the total amount of the successful refunds of the payment, if there are any
+pub fn get_amount_refunded(refunds: &[storage::Refund]) -> Option<i64> {
+    refunds
+        .iter()
+        .filter(|refund| refund.refund_status == storage_enums::RefundStatus::Success)
+        .map(|refund| refund.refund_amount)
+        .reduce(|total, refund_amount| total + refund_amount)
+}
+
+/// Returns whether the captured amount of the payment is partially or fully refunded. Each refund
+/// is synced with the connector individually, so only the refunds which succeeded are considered.
+pub fn get_payment_refund_status(
+    amount_captured: i64,
+    refunds: &[storage::Refund],
+) -> Option<api_enums::PaymentRefundStatus> {
+    get_amount_refunded(refunds).map(|amount_refunded| {
+        if amount_refunded >= amount_captured {
+            api_enums::PaymentRefundStatus::Refunded
+        } else {
+            api_enums::PaymentRefundStatus::PartiallyRefunded
+        }
+    })
+}
+
+/// Appends the connector chosen for the current attempt to the ordered list of connectors
+/// which have been tried for the payment, so that failovers can be traced on the intent.
+pub fn append_connector_to_routing_trail(
+    connector_routing_trail: Option<Vec<String>>,
+    connector: Option<&String>,
+) -> Option<Vec<String>> {
+    match connector {
+        Some(connector) => {
+            let mut trail = connector_routing_trail.unwrap_or_default();
+            trail.push(connector.to_owned());
+            Some(trail)
+        }
+        None => connector_routing_trail,
+    }
+}
+
+/// Merchant initiated transactions are charged while the customer is off session, so the
+/// customer cannot complete an interactive 3DS challenge
+pub fn validate_authentication_type_for_mit(
+    authentication_type: Option<api_enums::AuthenticationType>,
+    off_session: Option<bool>,
+    mandate_type: Option<&api::MandateTransactionType>,
+) -> RouterResult<()> {
+    let is_off_session_mit = off_session == Some(true)
+        && matches!(
+            mandate_type,
+            Some(api::MandateTransactionType::RecurringMandateTransaction)
+        );
+    utils::when(
+        is_off_session_mit && authentication_type == Some(api_enums::AuthenticationType::ThreeDs),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`authentication_type` cannot be `three_ds` for off session merchant initiated transactions".to_string(),
+            }))
+        },
+    )
+}
+
+/// Parses the `browser_info` of the request into the typed browser information sent to the
+/// connectors
+pub fn get_browser_information(
+    browser_info: Option<&serde_json::Value>,
+) -> RouterResult<Option<crate::types::BrowserInformation>> {
+    browser_info
+        .cloned()
+        .map(|browser_info| browser_info.parse_value("BrowserInformation"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "browser_info",
+        })
+}
+
+/// 3DS 2.0 requires the details of the customer's browser, so these have to be provided when a
+/// card payment is confirmed with `three_ds` authentication. The details which are read through
+/// javascript are only required when javascript is enabled in the browser.
+pub fn validate_browser_info_for_three_ds(
+    authentication_type: Option<api_enums::AuthenticationType>,
+    payment_method: Option<api_enums::PaymentMethod>,
+    payment_method_data: Option<&api::PaymentMethodData>,
+    browser_info: Option<&crate::types::BrowserInformation>,
+) -> RouterResult<()> {
+    let is_card_payment = payment_method == Some(api_enums::PaymentMethod::Card)
+        || matches!(payment_method_data, Some(api::PaymentMethodData::Card(_)));
+    if authentication_type != Some(api_enums::AuthenticationType::ThreeDs) || !is_card_payment {
+        return Ok(());
+    }
+
+    let default_browser_info = crate::types::BrowserInformation::default();
+    let browser_info = browser_info.unwrap_or(&default_browser_info);
+    let java_script_enabled = browser_info.java_script_enabled.unwrap_or(false);
+
+    let mut missing_fields = vec![
+        ("browser_info.user_agent", browser_info.user_agent.is_none()),
+        (
+            "browser_info.accept_header",
+            browser_info.accept_header.is_none(),
+        ),
+        ("browser_info.language", browser_info.language.is_none()),
+        (
+            "browser_info.java_script_enabled",
+            browser_info.java_script_enabled.is_none(),
+        ),
+    ];
+    if java_script_enabled {
+        missing_fields.extend([
+            (
+                "browser_info.color_depth",
+                browser_info.color_depth.is_none(),
+            ),
+            (
+                "browser_info.screen_height",
+                browser_info.screen_height.is_none(),
+            ),
+            (
+                "browser_info.screen_width",
+                browser_info.screen_width.is_none(),
+            ),
+            ("browser_info.time_zone", browser_info.time_zone.is_none()),
+            (
+                "browser_info.java_enabled",
+                browser_info.java_enabled.is_none(),
+            ),
+        ]);
+    }
+    let missing_field_names: Vec<&'static str> = missing_fields
+        .into_iter()
+        .filter_map(|(field_name, is_missing)| is_missing.then_some(field_name))
+        .collect();
+
+    utils::when(!missing_field_names.is_empty(), || {
+        Err(report!(errors::ApiErrorResponse::MissingRequiredFields {
+            field_names: missing_field_names
+        }))
+    })
+}
+
+/// Whether a payment intent can move from the status `from` to the status `to`
+pub fn is_valid_transition(
+    from: storage_enums::IntentStatus,
+    to: storage_enums::IntentStatus,
+) -> bool {
+    use storage_enums::IntentStatus;
+
+    if from == to {
+        return true;
+    }
+    match from {
+        // A successful payment can only be reversed before it is settled
+        IntentStatus::Succeeded => matches!(to, IntentStatus::Processing | IntentStatus::Cancelled),
+        IntentStatus::Cancelled => false,
+        // Failed payments can be retried with a new attempt
+        IntentStatus::Failed
+        | IntentStatus::RequiresPaymentMethod
+        | IntentStatus::RequiresConfirmation
+        | IntentStatus::RequiresCustomerAction => true,
+        IntentStatus::Processing
+        | IntentStatus::RequiresMerchantAction
+        | IntentStatus::RequiresCapture => to != IntentStatus::RequiresConfirmation,
+        IntentStatus::PartiallyCaptured => !matches!(
+            to,
+            IntentStatus::RequiresPaymentMethod
+                | IntentStatus::RequiresConfirmation
+                | IntentStatus::RequiresCustomerAction
+        ),
+    }
+}
+
+/// Fails if the update moves the payment intent to a status which cannot be reached from its
+/// current status
+pub fn validate_intent_status_transition(
+    current_status: storage_enums::IntentStatus,
+    payment_intent_update: &storage::PaymentIntentUpdate,
+) -> RouterResult<()> {
+    match payment_intent_update.get_status() {
+        Some(status) if !is_valid_transition(current_status, status) => {
+            Err(report!(errors::ApiErrorResponse::InternalServerError)).attach_printable(format!(
+                "Invalid payment intent status transition from {current_status} to {status}"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Customers are redirected away for 3DS authentication and for redirection based payment
+/// methods, so a return url has to be available to bring them back once the payment is completed.
+/// `return_url` is the url of the payment or else the default return url of the merchant.
+pub fn validate_return_url_for_redirection(
+    authentication_type: Option<api_enums::AuthenticationType>,
+    payment_method: Option<api_enums::PaymentMethod>,
+    payment_experience: Option<api_enums::PaymentExperience>,
+    return_url: Option<&String>,
+) -> RouterResult<()> {
+    let is_redirection_required = authentication_type
+        == Some(api_enums::AuthenticationType::ThreeDs)
+        || payment_method == Some(api_enums::PaymentMethod::BankRedirect)
+        || payment_experience == Some(api_enums::PaymentExperience::RedirectToUrl);
+
+    utils::when(is_redirection_required && return_url.is_none(), || {
+        Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "return_url"
+        }))
+    })
+}
+
+/// Fails if installments are requested but the connector the payment is routed to doesn't
+/// support them, instead of silently dropping the installments.
+pub fn validate_installments_support(
+    installments: Option<&api_models::payments::InstallmentData>,
+    connector: &api_enums::Connector,
+    installment_supported_connectors: &InstallmentSupportedConnectors,
+) -> RouterResult<()> {
+    utils::when(
+        installments.is_some()
+            && !installment_supported_connectors
+                .connector_list
+                .contains(connector),
+        || {
+            Err(report!(errors::ApiErrorResponse::NotImplemented {
+                message: errors::api_error_response::NotImplementedMessage::Reason(format!(
+                    "Installments for {connector}"
+                )),
+            }))
+        },
+    )
+}
+
+/// Connectors which perform address verification (AVS) reject card payments without the
+/// card holder name, the name is optional for all other connectors.
+pub fn validate_card_holder_name_for_connector(
+    payment_method_data: Option<&api::PaymentMethodData>,
+    connector: &api_enums::Connector,
+    card_holder_name_required_connectors: &CardHolderNameRequiredConnectors,
+) -> RouterResult<()> {
+    let is_card_holder_name_missing = match payment_method_data {
+        Some(api::PaymentMethodData::Card(card)) => card.card_holder_name.peek().trim().is_empty(),
+        _ => false,
+    };
+
+    utils::when(
+        is_card_holder_name_missing
+            && card_holder_name_required_connectors
+                .connector_list
+                .contains(connector),
+        || {
+            Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "payment_method_data.card.card_holder_name"
+            }))
+            .attach_printable(format!("Card holder name is required by {connector}"))
+        },
+    )
+}
+
+/// Risk data is passed through to the connector as key value pairs, so it has to be a JSON object
+pub fn validate_risk_data(risk_data: Option<&serde_json::Value>) -> RouterResult<()> {
+    utils::when(
+        risk_data.map_or(false, |risk_data| !risk_data.is_object()),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "risk_data".to_string(),
+                expected_format: "a JSON object".to_string(),
+            }))
+        },
+    )
+}
+
+/// The fields added by hyperswitch are stored in the metadata of the payment along with the
+/// metadata passed by the merchant, so the merchant cannot pass the keys reserved for them
+pub fn validate_metadata_keys(metadata: Option<&pii::SecretSerdeValue>) -> RouterResult<()> {
+    let reserved_key = metadata
+        .and_then(|metadata| metadata.peek().as_object())
+        .and_then(|metadata_object| {
+            metadata_object.keys().find(|key| {
+                key.starts_with(consts::RESERVED_METADATA_KEY_PREFIX)
+                    || key.as_str() == consts::CONNECTOR_RESPONSE_METADATA_KEY
+            })
+        });
+
+    reserved_key.map_or(Ok(()), |reserved_key| {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("metadata cannot contain the reserved key {reserved_key}"),
+        }))
+    })
+}
+
+/// App based 3DS authentication needs the details generated by the 3DS SDK on the device of the
+/// customer for the handshake with the issuer, so all of them have to be provided together
+pub fn validate_sdk_information(
+    sdk_information: Option<&api_models::payments::SdkInfo>,
+) -> RouterResult<()> {
+    let is_present = |value: Option<&String>| value.map_or(false, |value| !value.is_empty());
+    sdk_information.map_or(Ok(()), |sdk_information| {
+        [
+            (
+                "sdk_information.sdk_app_id",
+                is_present(sdk_information.sdk_app_id.as_ref()),
+            ),
+            (
+                "sdk_information.sdk_enc_data",
+                is_present(
+                    sdk_information
+                        .sdk_enc_data
+                        .as_ref()
+                        .map(|data| data.peek()),
+                ),
+            ),
+            (
+                "sdk_information.sdk_ephem_pub_key",
+                sdk_information
+                    .sdk_ephem_pub_key
+                    .as_ref()
+                    .map_or(false, |public_key| !public_key.is_empty()),
+            ),
+            (
+                "sdk_information.sdk_trans_id",
+                is_present(sdk_information.sdk_trans_id.as_ref()),
+            ),
+            (
+                "sdk_information.sdk_reference_number",
+                is_present(sdk_information.sdk_reference_number.as_ref()),
+            ),
+        ]
+        .into_iter()
+        .find(|(_, is_present)| !is_present)
+        .map_or(Ok(()), |(field_name, _)| {
+            Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                field_name
+            }))
+        })?;
+
+        // The 3DS specification allows the customer between 5 and 99 minutes to authenticate
+        let is_invalid_max_timeout = sdk_information
+            .sdk_max_timeout
+            .map_or(false, |max_timeout| !(5..=99).contains(&max_timeout));
+        utils::when(is_invalid_max_timeout, || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "sdk_information.sdk_max_timeout".to_string(),
+                expected_format: "sdk_max_timeout to be between 5 and 99 minutes".to_string(),
+            }))
+        })
+    })
+}
+
+/// Returns the risk data to be forwarded to the connector the payment is routed to, risk data is
+/// dropped for connectors which don't support it.
+pub fn get_risk_data_for_connector(
+    risk_data: Option<serde_json::Value>,
+    connector: &api_enums::Connector,
+    risk_data_supported_connectors: &RiskDataSupportedConnectors,
+) -> Option<serde_json::Value> {
+    if risk_data.is_some()
+        && !risk_data_supported_connectors
+            .connector_list
+            .contains(connector)
+    {
+        logger::info!("Risk data is not forwarded as it is not supported by {connector}");
+        return None;
+    }
+    risk_data
+}
+
+/// Wallets and other payment methods can be integrated with a connector through a redirection or
+/// by invoking an SDK or app on the customer's device. The payment experiences configured for the
+/// payment method type in the merchant connector account are the ones supported by the
+/// connector, when none are configured any payment experience is allowed.
+pub fn validate_payment_experience_for_connector(
+    payment_experience: Option<api_enums::PaymentExperience>,
+    payment_method_type: Option<api_enums::PaymentMethodType>,
+    payment_methods_enabled: Option<&Vec<serde_json::Value>>,
+    connector_name: &str,
+) -> RouterResult<()> {
+    let (payment_experience, payment_method_type) =
+        match payment_experience.zip(payment_method_type) {
+            Some(experience_and_method_type) => experience_and_method_type,
+            None => return Ok(()),
+        };
+
+    let supported_payment_experiences = payment_methods_enabled
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|payment_methods_enabled| {
+            payment_methods_enabled
+                .parse_value::<api_models::admin::PaymentMethodsEnabled>("PaymentMethodsEnabled")
+                .map_err(|err| logger::error!(payment_methods_enabled_parsing_error=?err))
+                .ok()
+        })
+        .flat_map(|payment_methods_enabled| {
+            payment_methods_enabled
+                .payment_method_types
+                .unwrap_or_default()
+        })
+        .filter(|enabled_payment_method_type| {
+            enabled_payment_method_type.payment_method_type == payment_method_type
+        })
+        .map(|enabled_payment_method_type| enabled_payment_method_type.payment_experience)
+        .collect::<Option<Vec<_>>>();
+
+    match supported_payment_experiences {
+        Some(supported_payment_experiences)
+            if !supported_payment_experiences.is_empty()
+                && !supported_payment_experiences.contains(&payment_experience) =>
+        {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: format!(
+                    "Payment experience {payment_experience} for {payment_method_type} through {connector_name}"
+                ),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Confirming a payment which is already being processed by the connector, or which has reached
+/// a terminal status, could charge the customer twice. A payment which is waiting on the customer
+/// to complete an action, like 3DS authentication, has to be completed through that flow instead
+/// of being confirmed again. A failed payment can only be confirmed again as a manual retry, in
+/// which case `get_attempt_type` decides whether the payment can be retried.
+pub fn validate_intent_status_for_confirm(
+    intent_status: &api_enums::IntentStatus,
+    retry_action: Option<&api_enums::RetryAction>,
+) -> RouterResult<()> {
+    match intent_status {
+        api_enums::IntentStatus::Failed
+            if retry_action == Some(&api_enums::RetryAction::ManualRetry) =>
+        {
+            Ok(())
+        }
+        api_enums::IntentStatus::Processing
+        | api_enums::IntentStatus::Succeeded
+        | api_enums::IntentStatus::Failed => {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "You cannot confirm this payment because it has status {intent_status}, \
+                    only payments which require confirmation can be confirmed"
+                ),
+            }))
+        }
+        api_enums::IntentStatus::RequiresCustomerAction => {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "You cannot confirm this payment because it has status {intent_status}, \
+                    the customer has to complete the pending action by following the \
+                    next_action of the payment"
+                ),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The capture method of a payment can be overridden at confirm, but not once the payment has
+/// been authorized by the connector
+pub fn validate_capture_method_override(
+    intent_status: &api_enums::IntentStatus,
+    capture_method: Option<api_enums::CaptureMethod>,
+) -> RouterResult<()> {
+    let is_authorized = matches!(
+        intent_status,
+        api_enums::IntentStatus::Processing
+            | api_enums::IntentStatus::RequiresCapture
+            | api_enums::IntentStatus::PartiallyCaptured
+            | api_enums::IntentStatus::Succeeded
+    );
+    utils::when(capture_method.is_some() && is_authorized, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot update the capture_method of this payment because it has status \
+                {intent_status}, the capture_method can only be updated before the payment \
+                is authorized"
+            ),
+        }))
+    })
+}
+
+/// Returns true if the capture of the payment is settled. A capture is settled once the payment
+/// succeeds or a part of its amount is captured, until then the capture is being processed by the
+/// connector. Payments which are yet to be captured should be cancelled instead.
+pub fn is_capture_settled(
+    intent_status: api_enums::IntentStatus,
+    attempt_status: api_enums::AttemptStatus,
+) -> RouterResult<bool> {
+    match (intent_status, attempt_status) {
+        (api_enums::IntentStatus::Succeeded, _)
+        | (api_enums::IntentStatus::PartiallyCaptured, _) => Ok(true),
+        (api_enums::IntentStatus::Processing, api_enums::AttemptStatus::CaptureInitiated) => {
+            Ok(false)
+        }
+        _ => Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot reverse this payment because it has status {intent_status}"
+            ),
+        })),
+    }
+}
+
+/// Returns true if the capture of the payment is still being processed by a connector which can
+/// reverse it. Such captures are reversed through the void flow of the connector.
+pub fn is_capture_reversible(
+    intent_status: api_enums::IntentStatus,
+    attempt_status: api_enums::AttemptStatus,
+    connector: Option<&str>,
+    capture_reversal_supported_connectors: &CaptureReversalSupportedConnectors,
+) -> bool {
+    matches!(is_capture_settled(intent_status, attempt_status), Ok(false))
+        && connector
+            .and_then(|connector| api_enums::Connector::from_str(connector).ok())
+            .map_or(false, |connector| {
+                is_capture_reversal_supported(&connector, capture_reversal_supported_connectors)
+            })
+}
+
+/// Returns true if the connector can reverse a capture which is not settled yet. The capture
+/// is refunded instead for the other connectors.
+pub fn is_capture_reversal_supported(
+    connector: &api_enums::Connector,
+    capture_reversal_supported_connectors: &CaptureReversalSupportedConnectors,
+) -> bool {
+    capture_reversal_supported_connectors
+        .connector_list
+        .contains(connector)
+}
+
+/// Converts the amount being captured to the currency chosen by the customer through dynamic
+/// currency conversion (DCC), which is only supported by some connectors. Both the original and
+/// the converted amounts are returned so that they can be recorded against the payment attempt.
+pub fn get_dcc_details_for_capture(
+    dcc_offer: &api_models::payments::DccOffer,
+    connector: &api_enums::Connector,
+    dcc_supported_connectors: &DccSupportedConnectors,
+    original_currency: api_enums::Currency,
+    amount_to_capture: i64,
+    rounding_mode: RoundingMode,
+) -> RouterResult<api_models::payments::DccDetails> {
+    utils::when(
+        !dcc_supported_connectors.connector_list.contains(connector),
+        || {
+            Err(report!(errors::ApiErrorResponse::NotImplemented {
+                message: errors::api_error_response::NotImplementedMessage::Reason(format!(
+                    "Dynamic currency conversion for {connector}"
+                )),
+            }))
+        },
+    )?;
+    utils::when(
+        !dcc_offer.exchange_rate.is_finite() || dcc_offer.exchange_rate <= 0.0,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "dcc_offer.exchange_rate".to_string(),
+                expected_format: "a positive number".to_string(),
+            }))
+        },
+    )?;
+
+    let converted_amount = convert_amount(
+        amount_to_capture,
+        original_currency,
+        dcc_offer.target_currency,
+        dcc_offer.exchange_rate,
+        rounding_mode,
+    )
+    .ok_or(errors::ApiErrorResponse::InvalidDataFormat {
+        field_name: "dcc_offer.exchange_rate".to_string(),
+        expected_format: "an exchange rate which converts the amount to a valid amount".to_string(),
+    })
+    .into_report()?;
+
+    Ok(api_models::payments::DccDetails {
+        exchange_rate: dcc_offer.exchange_rate,
+        original_currency,
+        original_amount: amount_to_capture,
+        target_currency: dcc_offer.target_currency,
+        converted_amount,
+    })
+}
+
+/// Converts an amount in the lowest denomination of `from_currency` to the lowest denomination of
+/// `to_currency`, where the exchange rate is the number of units of `to_currency` for a unit of
+/// `from_currency`. The exchange rate is taken as the decimal number it is written as, so that the
+/// conversion is done on integers and only the final amount is rounded.
+fn convert_amount(
+    amount: i64,
+    from_currency: api_enums::Currency,
+    to_currency: api_enums::Currency,
+    exchange_rate: f64,
+    rounding_mode: RoundingMode,
+) -> Option<i64> {
+    // The shortest decimal representation of the exchange rate, without an exponent
+    let exchange_rate = exchange_rate.to_string();
+    let (integer_digits, fraction_digits) = exchange_rate
+        .split_once('.')
+        .unwrap_or((exchange_rate.as_str(), ""));
+    let exchange_rate_digits = format!("{integer_digits}{fraction_digits}")
+        .parse::<i128>()
+        .ok()?;
+    let exchange_rate_scale = u32::try_from(fraction_digits.len()).ok()?;
+
+    let numerator = i128::from(amount)
+        .checked_mul(exchange_rate_digits)?
+        .checked_mul(10_i128.checked_pow(u32::from(
+            to_currency.number_of_digits_after_decimal_point(),
+        ))?)?;
+    let denominator = 10_i128.checked_pow(exchange_rate_scale.checked_add(u32::from(
+        from_currency.number_of_digits_after_decimal_point(),
+    ))?)?;
+    core_utils::round_amount(numerator, denominator, rounding_mode)
+}
+
+/// The city in the statement descriptor is limited in length by the card networks, longer values
+/// are rejected instead of being truncated by the connector.
+pub fn validate_statement_descriptor_city(
+    statement_descriptor_city: Option<&String>,
+) -> RouterResult<()> {
+    utils::when(
+        statement_descriptor_city.map_or(false, |city| {
+            city.chars().count() > consts::STATEMENT_DESCRIPTOR_CITY_MAX_LENGTH
+        }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "statement_descriptor_city".to_string(),
+                expected_format: format!(
+                    "at most {} characters",
+                    consts::STATEMENT_DESCRIPTOR_CITY_MAX_LENGTH
+                ),
+            }))
+        },
+    )
+}
+
+/// Statement descriptors of bank debits are sent as the remittance reference of the debit, which
+/// only allows the SEPA character set. Descriptors of every other payment method follow the
+/// rules of the card networks.
+pub fn validate_statement_descriptor(
+    field_name: &str,
+    statement_descriptor: Option<&String>,
+    payment_method: Option<api_enums::PaymentMethod>,
+) -> RouterResult<()> {
+    let descriptor = match statement_descriptor {
+        Some(descriptor) => descriptor,
+        None => return Ok(()),
+    };
+    let length = descriptor.chars().count();
+
+    let (is_valid, expected_format) = match payment_method {
+        Some(api_enums::PaymentMethod::BankDebit) => (
+            length <= consts::BANK_DEBIT_STATEMENT_DESCRIPTOR_MAX_LENGTH
+                && descriptor
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || " /-?:().,'+".contains(c)),
+            format!(
+                "at most {} characters containing only letters, digits, spaces and / - ? : ( ) . , ' + for bank debit payments",
+                consts::BANK_DEBIT_STATEMENT_DESCRIPTOR_MAX_LENGTH
+            ),
+        ),
+        _ => (
+            length <= consts::STATEMENT_DESCRIPTOR_MAX_LENGTH
+                && descriptor.chars().any(char::is_alphabetic)
+                && !descriptor.chars().any(|c| "<>\\'\"*".contains(c)),
+            format!(
+                "at most {} characters containing at least one letter and none of < > \\ ' \" * for {} payments",
+                consts::STATEMENT_DESCRIPTOR_MAX_LENGTH,
+                payment_method.unwrap_or(api_enums::PaymentMethod::Card)
+            ),
+        ),
+    };
+
+    utils::when(!is_valid, || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: field_name.to_string(),
+            expected_format,
+        }))
+    })
+}
+
+/// Validates the amounts which are added on top of the payment amount. None of them can be
+/// negative, the payment amount along with all of them has to fit in an `i64` and together they
+/// cannot exceed the payment amount.
+pub fn validate_additional_amounts(
+    amount: Option<api::Amount>,
+    surcharge_details: Option<&api_models::payments::RequestSurchargeDetails>,
+) -> RouterResult<()> {
+    let additional_amounts = surcharge_details
+        .map(|surcharge_details| {
+            vec![
+                (
+                    "surcharge_details.surcharge_amount",
+                    Some(surcharge_details.surcharge_amount),
+                ),
+                ("surcharge_details.tax_amount", surcharge_details.tax_amount),
+            ]
+        })
+        .unwrap_or_default();
+
+    let total_additional_amount = additional_amounts.into_iter().try_fold(
+        0i64,
+        |total, (field_name, additional_amount)| match additional_amount {
+            Some(additional_amount) if additional_amount < 0 => {
+                Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: field_name.to_string(),
+                    expected_format: "a non negative amount".to_string(),
+                }))
+            }
+            Some(additional_amount) => total.checked_add(additional_amount).ok_or_else(|| {
+                report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "{field_name} is too large, the total of the additional amounts overflows"
+                    ),
+                })
+            }),
+            None => Ok(total),
+        },
+    )?;
+
+    match amount.map(i64::from) {
+        Some(amount) => {
+            utils::when(
+                amount.checked_add(total_additional_amount).is_none(),
+                || {
+                    Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The additional amounts {total_additional_amount} are too large, the total of amount and additional amounts overflows"
+                    ),
+                }))
+                },
+            )?;
+            utils::when(total_additional_amount > amount, || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The additional amounts {total_additional_amount} cannot exceed the payment amount {amount}"
+                    ),
+                }))
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Total of the order, which is the amount of every item in the order details along with the
+/// shipping and handling costs. Returns `None` if the total overflows.
+pub fn get_order_total(
+    order_details: Option<&Vec<api_models::payments::OrderDetailsWithAmount>>,
+    shipping_amount: Option<i64>,
+    handling_amount: Option<i64>,
+) -> Option<i64> {
+    order_details
+        .into_iter()
+        .flatten()
+        .map(|order| order.amount.checked_mul(i64::from(order.quantity)))
+        .chain(
+            [shipping_amount, handling_amount]
+                .into_iter()
+                .flatten()
+                .map(Some),
+        )
+        .try_fold(0i64, |total, amount| total.checked_add(amount?))
+}
+
+/// Shipping and handling costs are part of the payment amount, so they cannot be negative and
+/// the order total including them cannot exceed the payment amount.
+pub fn validate_shipping_and_handling_amounts(
+    amount: Option<api::Amount>,
+    shipping_amount: Option<i64>,
+    handling_amount: Option<i64>,
+    order_details: Option<&Vec<api_models::payments::OrderDetailsWithAmount>>,
+) -> RouterResult<()> {
+    for (field_name, additional_amount) in [
+        ("shipping_amount", shipping_amount),
+        ("handling_amount", handling_amount),
+    ] {
+        utils::when(additional_amount.map_or(false, |value| value < 0), || {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: field_name.to_string(),
+                expected_format: "a non negative amount".to_string(),
+            }))
+        })?;
+    }
+
+    let order_total =
+        get_order_total(order_details, shipping_amount, handling_amount).ok_or_else(|| {
+            report!(errors::ApiErrorResponse::InvalidRequestData {
+                message:
+                    "The total of order_details, shipping_amount and handling_amount overflows"
+                        .to_string(),
+            })
+        })?;
+
+    match amount.map(i64::from) {
+        Some(amount) => utils::when(order_total > amount, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The total of order_details, shipping_amount and handling_amount {order_total} cannot exceed the payment amount {amount}"
+                ),
+            }))
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Rejects the creation of a payment when the customer already has the maximum number of
+/// payments in a non terminal status. Only the payments created within the merchant's intent
+/// fulfillment time are counted, older payments can no longer be confirmed by the customer.
+pub async fn validate_open_payment_intents_limit(
+    db: &dyn StorageInterface,
+    config: &OpenPaymentIntentsLimit,
+    customer_id: Option<&String>,
+    merchant_id: &str,
+    intent_fulfillment_time: Option<i64>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
+) -> RouterResult<()> {
+    let customer_id = match customer_id {
+        Some(customer_id) if config.enabled => customer_id,
+        _ => return Ok(()),
+    };
+
+    let created_after = common_utils::date_time::now().saturating_sub(Duration::seconds(
+        intent_fulfillment_time.unwrap_or(consts::DEFAULT_FULFILLMENT_TIME),
+    ));
+    let open_payment_intents_count = db
+        .get_open_payment_intents_count_by_customer_id_merchant_id(
+            customer_id,
+            merchant_id,
+            created_after,
+            storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to count the open payment intents of the customer")?;
+
+    check_open_payment_intents_limit(config, open_payment_intents_count)
+}
+
+pub fn check_open_payment_intents_limit(
+    config: &OpenPaymentIntentsLimit,
+    open_payment_intents_count: i64,
+) -> RouterResult<()> {
+    utils::when(
+        open_payment_intents_count >= i64::from(config.max_open_intents_per_customer),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "customer has reached the limit of {} open payments",
+                    config.max_open_intents_per_customer
+                ),
+            }))
+        },
+    )
+}
+
+/// Installments in the request replace the ones stored on the attempt when the payment was
+/// created or updated
+pub fn get_installments(
+    request_installments: Option<api_models::payments::InstallmentData>,
+    payment_attempt: &PaymentAttempt,
+) -> RouterResult<Option<api_models::payments::InstallmentData>> {
+    request_installments
+        .map(Ok)
+        .or_else(|| {
+            payment_attempt.installments.clone().map(|installments| {
+                installments
+                    .parse_value("InstallmentData")
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to parse the installments of the payment attempt")
+            })
+        })
+        .transpose()
+}
+
+/// Normalizes `capture_on` to the scheduling granularity supported by the connectors the payment
+/// can be routed to. Sub-second precision is always dropped, and if any of the connectors only
+/// schedules captures by date, the time is truncated to the start of the day.
+pub fn normalize_capture_on(
+    capture_on: time::PrimitiveDateTime,
+    connectors: Option<&[api_enums::Connector]>,
+    date_only_connectors: &DateOnlyCaptureOnConnectors,
+) -> time::PrimitiveDateTime {
+    if connectors
+        .unwrap_or_default()
+        .iter()
+        .any(|connector| date_only_connectors.connector_list.contains(connector))
+    {
+        capture_on.replace_time(time::Time::MIDNIGHT)
+    } else {
+        capture_on - Duration::nanoseconds(i64::from(capture_on.nanosecond()))
+    }
+}
+
+/// Authorizations are released by the connector once its hold window elapses, a capture scheduled
+/// after that would fail. Connectors without a configured window are not validated.
+pub fn validate_capture_on_for_connector(
+    capture_on: Option<time::PrimitiveDateTime>,
+    connector: &api_enums::Connector,
+    auth_hold_window: &ConnectorAuthHoldWindow,
+) -> RouterResult<()> {
+    let max_hold_days = auth_hold_window.0.get(&connector.to_string());
+    match (capture_on, max_hold_days) {
+        (Some(capture_on), Some(max_hold_days)) => utils::when(
+            capture_on > common_utils::date_time::now() + Duration::days(i64::from(*max_hold_days)),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "capture_on".to_string(),
+                    expected_format: format!(
+                        "a time within {max_hold_days} days from now, the authorization is not \
+                        held by {connector} beyond that"
+                    ),
+                }))
+            },
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Connectors decline payments outside of their per transaction amount limits, such payments are
+/// rejected before reaching the connector. Currencies without configured limits are not validated.
+pub fn validate_amount_for_connector(
+    amount: i64,
+    currency: api_enums::Currency,
+    connector: &api_enums::Connector,
+    amount_limits: &ConnectorAmountLimits,
+) -> RouterResult<()> {
+    let amount_limit = amount_limits
+        .0
+        .get(&connector.to_string())
+        .and_then(|currency_limits| currency_limits.get(&currency));
+    if let Some(min_amount) = amount_limit
+        .and_then(|amount_limit| amount_limit.min_amount)
+        .filter(|min_amount| amount < *min_amount)
+    {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount {amount} is below the minimum amount of {min_amount} allowed by {connector} for {currency}"
+            ),
+        }))?
+    }
+    if let Some(max_amount) = amount_limit
+        .and_then(|amount_limit| amount_limit.max_amount)
+        .filter(|max_amount| amount > *max_amount)
+    {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount {amount} is above the maximum amount of {max_amount} allowed by {connector} for {currency}"
+            ),
+        }))?
+    }
+    Ok(())
+}
+
+/// The authentication type of the payment if it was specified, otherwise the default configured
+/// for the connector the payment is routed to
+pub fn get_authentication_type_for_connector(
+    authentication_type: Option<api_enums::AuthenticationType>,
+    connector: &api_enums::Connector,
+    default_authentication_types: &ConnectorDefaultAuthenticationType,
+) -> Option<api_enums::AuthenticationType> {
+    authentication_type.or_else(|| {
+        default_authentication_types
+            .0
+            .get(&connector.to_string())
+            .copied()
+    })
+}
+
+/// Parses a dotted 3DS protocol version such as `2.1.0`, trailing zero components are dropped so
+/// that `2.1` and `2.1.0` compare as equal
+fn parse_three_ds_version(version: &str) -> Option<Vec<u32>> {
+    let mut components = version
+        .split('.')
+        .map(|component| component.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    while components.last() == Some(&0) {
+        components.pop();
+    }
+    Some(components)
+}
+
+/// Returns the minimum 3DS version configured for the connector. The version configured for the
+/// business country of the payment takes precedence over the default version of the connector.
+pub fn get_minimum_three_ds_version<'a>(
+    minimum_three_ds_version: &'a MinimumThreeDsVersion,
+    connector: &str,
+    business_country: Option<api_enums::CountryAlpha2>,
+) -> Option<&'a String> {
+    let connector_minimum_version = minimum_three_ds_version.0.get(connector)?;
+    business_country
+        .and_then(|country| connector_minimum_version.countries.get(&country))
+        .or(connector_minimum_version.default.as_ref())
+}
+
+/// Returns the minimum 3DS version configured for the connector if the version negotiated for the
+/// authentication is below it. Versions which cannot be parsed are not enforced.
+pub fn get_unmet_minimum_three_ds_version<'a>(
+    minimum_three_ds_version: &'a MinimumThreeDsVersion,
+    connector: &str,
+    business_country: Option<api_enums::CountryAlpha2>,
+    three_ds_version: &str,
+) -> Option<&'a String> {
+    let minimum_version =
+        get_minimum_three_ds_version(minimum_three_ds_version, connector, business_country)?;
+    match (
+        parse_three_ds_version(three_ds_version),
+        parse_three_ds_version(minimum_version),
+    ) {
+        (Some(version), Some(minimum)) if version < minimum => Some(minimum_version),
+        _ => None,
+    }
+}
+
+/// The 3DS version is only negotiated when the customer is authenticated, which happens in the
+/// authorize and complete authorize flows
+pub fn is_three_ds_authentication_flow<F>() -> bool {
+    core_utils::get_flow_name::<F>()
+        .map(|flow_name| flow_name == "Authorize" || flow_name == "CompleteAuthorize")
+        .unwrap_or(false)
+}
+
+/// The minimum 3DS version of the connector is sent along with the authorization, so connectors
+/// which honour it never authenticate the customer with a lower version. Returns a description of
+/// the authentication if it was still negotiated with a lower version. The authorization has
+/// already been processed by the connector at this point, so it is not failed locally.
+pub fn get_three_ds_version_below_minimum_message<F, T>(
+    minimum_three_ds_version: &MinimumThreeDsVersion,
+    business_country: Option<api_enums::CountryAlpha2>,
+    router_data: &RouterData<F, T, PaymentsResponseData>,
+) -> Option<String> {
+    if !is_three_ds_authentication_flow::<F>() {
+        return None;
+    }
+    router_data
+        .three_ds_authentication_result
+        .as_ref()
+        .and_then(|authentication_result| authentication_result.three_ds_version.as_ref())
+        .and_then(|three_ds_version| {
+            get_unmet_minimum_three_ds_version(
+                minimum_three_ds_version,
+                &router_data.connector,
+                business_country,
+                three_ds_version,
+            )
+            .map(|minimum_version| {
+                format!(
+                    "The payment was authenticated with 3DS version {three_ds_version}, below the \
+                    minimum version {minimum_version} required for {}",
+                    router_data.connector
+                )
+            })
+        })
+}
+
+/// Removes the card details which the response masking policy of the merchant does not allow to
+/// be returned in the payments response
+pub fn mask_payment_method_data_response(
+    payment_method_data: api::PaymentMethodDataResponse,
+    policy: ResponseMaskingPolicy,
+) -> api::PaymentMethodDataResponse {
+    match (payment_method_data, policy) {
+        (api::PaymentMethodDataResponse::Card(_), ResponseMaskingPolicy::Minimal) => {
+            api::PaymentMethodDataResponse::Card(api_models::payments::CardResponse {
+                last4: None,
+                card_type: None,
+                card_network: None,
+                card_issuer: None,
+                card_issuing_country: None,
+                card_isin: None,
+                card_exp_month: None,
+                card_exp_year: None,
+                card_holder_name: None,
+            })
+        }
+        (api::PaymentMethodDataResponse::Card(card), ResponseMaskingPolicy::Last4) => {
+            api::PaymentMethodDataResponse::Card(api_models::payments::CardResponse {
+                last4: card.last4,
+                card_type: None,
+                card_network: card.card_network,
+                card_issuer: None,
+                card_issuing_country: None,
+                card_isin: None,
+                card_exp_month: None,
+                card_exp_year: None,
+                card_holder_name: None,
+            })
+        }
+        (payment_method_data, _) => payment_method_data,
+    }
+}
+
+/// Converts the latency of the connector call, as captured in the router data, to the
+/// milliseconds stored against the payment attempt
+pub fn get_latency_in_milliseconds(external_latency: Option<u128>) -> Option<i64> {
+    external_latency.and_then(|latency| i64::try_from(latency).ok())
+}
+
+/// Connector calls which time out are reported with the timeout error code, the connector may
+/// have processed the payment regardless.
+pub fn is_connector_timeout_error(error_response: &ErrorResponse) -> bool {
+    error_response.code == consts::REQUEST_TIMEOUT_ERROR_CODE
+}
+
+/// A timed out connector call leaves the payment pending since it may have succeeded, while
+/// declines fail the payment. Errors while syncing the payment only fail the payment for genuine
+/// failures reported by the connector.
+pub fn get_attempt_status_for_connector_error(
+    flow_name: &str,
+    error_response: &ErrorResponse,
+    previous_status: storage_enums::AttemptStatus,
+) -> storage_enums::AttemptStatus {
+    if flow_name == "PSync" {
+        match error_response.status_code {
+            // marking failure for 2xx because this is genuine payment failure
+            200..=299 => storage_enums::AttemptStatus::Failure,
+            _ => previous_status,
+        }
+    } else if is_connector_timeout_error(error_response) {
+        storage_enums::AttemptStatus::Pending
+    } else {
+        match error_response.status_code {
+            500..=511 => storage_enums::AttemptStatus::Pending,
+            _ => storage_enums::AttemptStatus::Failure,
+        }
+    }
+}
+
+/// Returns the details of the error of the attempt, if the connector returned an error
+pub fn get_error_details(
+    attempt_status: storage_enums::AttemptStatus,
+    error_code: Option<String>,
+    error_message: Option<String>,
+    connector: Option<String>,
+) -> Option<api_models::payments::ErrorDetails> {
+    if error_code.is_none() && error_message.is_none() {
+        return None;
+    }
+
+    let decline_category = if error_code.as_deref() == Some(consts::REQUEST_TIMEOUT_ERROR_CODE) {
+        api_models::payments::DeclineCategory::ConnectorTimeout
+    } else {
+        match attempt_status {
+            storage_enums::AttemptStatus::AuthenticationFailed => {
+                api_models::payments::DeclineCategory::AuthenticationFailed
+            }
+            storage_enums::AttemptStatus::AuthorizationFailed
+            | storage_enums::AttemptStatus::Failure => {
+                api_models::payments::DeclineCategory::Declined
+            }
+            _ => api_models::payments::DeclineCategory::ProcessingError,
+        }
+    };
+
+    Some(api_models::payments::ErrorDetails {
+        code: error_code,
+        message: error_message,
+        decline_category,
+        connector,
+    })
+}
+
+/// Connectors may authorize an amount different from the one sent to them, due to rounding or
+/// currency conversion. Returns whether the amount reported by the connector differs from the
+/// amount of the payment, if the connector reports the authorized amount.
+pub fn get_connector_amount_mismatch(connector_amount: Option<i64>, amount: i64) -> Option<bool> {
+    connector_amount.map(|connector_amount| connector_amount != amount)
+}
+
+/// Adds the fields returned by the connector to the metadata of the payment intent under a
+/// separate key, so that the metadata passed by the merchant is preserved. Returns `None` if
+/// there is nothing to be added.
+pub fn get_intent_metadata_with_connector_response(
+    metadata: Option<&pii::SecretSerdeValue>,
+    connector_response_metadata: Option<&ConnectorResponseMetadata>,
+) -> RouterResult<Option<pii::SecretSerdeValue>> {
+    let connector_response_metadata = match connector_response_metadata {
+        Some(connector_response_metadata) => connector_response_metadata,
+        None => return Ok(None),
+    };
+
+    let mut metadata_object = match metadata.map(|metadata| metadata.peek().clone()) {
+        Some(serde_json::Value::Object(metadata_object)) => metadata_object,
+        Some(serde_json::Value::Null) | None => serde_json::Map::new(),
+        Some(_) => {
+            logger::warn!(
+                "Connector response is not added to the payment metadata as it is not an object"
+            );
+            return Ok(None);
+        }
+    };
+
+    let connector_response_value = serde_json::to_value(connector_response_metadata)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the connector response metadata")?;
+    metadata_object.insert(
+        consts::CONNECTOR_RESPONSE_METADATA_KEY.to_string(),
+        connector_response_value,
+    );
+
+    Ok(Some(pii::SecretSerdeValue::new(serde_json::Value::Object(
+        metadata_object,
+    ))))
+}
+
+/// The payment method data passed in the request takes precedence, the payment token may be the
+/// one generated for it. Otherwise the payment method of the mandate is used if the payment is a
+/// mandate payment, followed by the payment method saved against the payment token.
+pub fn resolve_payment_method(
+    payment_method_data: Option<&api::PaymentMethodData>,
+    payment_token: Option<&String>,
+    mandate_id: Option<&String>,
+) -> ResolvedPaymentMethod {
+    match (payment_method_data, mandate_id, payment_token) {
+        (Some(_), _, _) => ResolvedPaymentMethod::PaymentMethodData,
+        (None, Some(_), _) => ResolvedPaymentMethod::Mandate,
+        (None, None, Some(_)) => ResolvedPaymentMethod::Token,
+        (None, None, None) => ResolvedPaymentMethod::NotProvided,
+    }
+}
+
+/// Records the latency of the connector call in the connector request time histogram, tagged by
+/// the connector and the flow, so that the response times of the connectors can be compared
+pub fn record_connector_request_time(
+    connector_request_time: &router_env::opentelemetry::metrics::Histogram<f64>,
+    connector_name: &str,
+    flow: String,
+    external_latency: Option<u128>,
+) {
+    if let Some(latency) = external_latency.and_then(|latency| u64::try_from(latency).ok()) {
+        connector_request_time.record(
+            &metrics::CONTEXT,
+            std::time::Duration::from_millis(latency).as_secs_f64(),
+            &[
+                metrics::request::add_attributes("connector", connector_name.to_string()),
+                metrics::request::add_attributes("flow", flow),
+            ],
+        );
     }
 }
 
+/// A saved payment method is used when the payment method is resolved from a payment token
+/// or a mandate, instead of the payment method data passed in the request
+pub fn is_saved_payment_method_used(
+    payment_method_data: &Option<api::PaymentMethodData>,
+    payment_token: &Option<String>,
+    mandate_id: &Option<String>,
+) -> bool {
+    resolve_payment_method(
+        payment_method_data.as_ref(),
+        payment_token.as_ref(),
+        mandate_id.as_ref(),
+    )
+    .is_saved_payment_method()
+}
+
+/// Bank transfers do not charge the customer immediately, the payment moves to
+/// `requires_customer_action` and the bank details are shown to the customer as the next action.
+/// The next action is derived from the payment method on the attempt, so infer it from the
+/// payment method data when the merchant has not sent it explicitly.
+pub fn get_payment_method_with_bank_transfer_fallback(
+    request: &api::PaymentsRequest,
+) -> Option<storage_enums::PaymentMethod> {
+    request
+        .payment_method
+        .or(match request.payment_method_data {
+            Some(api::PaymentMethodData::BankTransfer(_)) => {
+                Some(storage_enums::PaymentMethod::BankTransfer)
+            }
+            _ => None,
+        })
+}
+
 pub async fn get_token_for_recurring_mandate(
     state: &AppState,
     req: &api::PaymentsRequest,
@@ -473,11 +1794,7 @@ pub async fn get_token_for_recurring_mandate(
                 message: "customer_id must match mandate customer_id".into()
             }))?
         }
-        if mandate.mandate_status != storage_enums::MandateStatus::Active {
-            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
-                message: "mandate is not active".into()
-            }))?
-        };
+        verify_mandate_is_chargeable(&mandate)?;
         mandate.payment_method_id.clone()
     };
     verify_mandate_details(
@@ -541,11 +1858,11 @@ pub fn validate_merchant_id(
 
     let request_merchant_id = request_merchant_id.unwrap_or(merchant_id);
 
+    // The merchant in the request is not the one which is authenticated, so this is treated as
+    // an attempt to access the resources of another merchant
     utils::when(merchant_id.ne(request_merchant_id), || {
-        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
-            message: format!(
-                "Invalid `merchant_id`: {request_merchant_id} not found in merchant account"
-            )
+        Err(report!(errors::ApiErrorResponse::AccessForbidden {
+            resource: request_merchant_id.to_string()
         }))
     })
 }
@@ -583,6 +1900,68 @@ pub fn validate_request_amount_and_amount_to_capture(
     }
 }
 
+/// Automatic capture, which is the default capture method, always captures the full amount, so
+/// an `amount_to_capture` other than the payment amount is only allowed for manual or scheduled
+/// captures. Since the capture_method can be overridden until the payment is confirmed, this is
+/// validated against the capture_method which is in effect when the payment is confirmed.
+pub fn validate_amount_to_capture_and_capture_method(
+    amount: Option<api::Amount>,
+    amount_to_capture: Option<i64>,
+    capture_method: Option<api_enums::CaptureMethod>,
+) -> RouterResult<()> {
+    let is_automatic_capture =
+        capture_method.unwrap_or_default() == api_enums::CaptureMethod::Automatic;
+    let is_partial_amount_to_capture = amount_to_capture
+        .map(|amount_to_capture| Some(amount_to_capture) != amount.map(i64::from))
+        .unwrap_or(false);
+
+    utils::when(is_automatic_capture && is_partial_amount_to_capture, || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "amount_to_capture".to_string(),
+            expected_format: "amount_to_capture to be the payment amount for automatic capture, \
+                or capture_method to be manual or scheduled"
+                .to_string(),
+        }))
+    })
+}
+
+/// Rejects card fields which are empty or contain only whitespace, these pass the presence checks
+/// of the request but are rejected by the connector
+pub fn validate_card_fields_are_not_empty(card: &api::Card) -> RouterResult<()> {
+    let card_fields = [
+        ("card_number", card.card_number.peek().as_str()),
+        ("card_exp_month", card.card_exp_month.peek().as_str()),
+        ("card_exp_year", card.card_exp_year.peek().as_str()),
+        ("card_cvc", card.card_cvc.peek().as_str()),
+    ];
+    card_fields
+        .into_iter()
+        .find(|(_, value)| value.trim().is_empty())
+        .map_or(Ok(()), |(field_name, _)| {
+            Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: field_name.to_string(),
+                expected_format: "a non empty value".to_string(),
+            }))
+        })
+}
+
+/// The capture_method passed at confirm overrides the one of the attempt, and the
+/// `amount_to_capture` of the attempt is then validated against the overridden capture_method
+pub fn get_capture_method_for_confirm(
+    amount: i64,
+    amount_to_capture: Option<i64>,
+    request_capture_method: Option<api_enums::CaptureMethod>,
+    attempt_capture_method: Option<api_enums::CaptureMethod>,
+) -> RouterResult<Option<api_enums::CaptureMethod>> {
+    let capture_method = request_capture_method.or(attempt_capture_method);
+    validate_amount_to_capture_and_capture_method(
+        Some(api::Amount::from(amount)),
+        amount_to_capture,
+        capture_method,
+    )?;
+    Ok(capture_method)
+}
+
 #[instrument(skip_all)]
 pub fn validate_card_data(
     payment_method_data: Option<api::PaymentMethodData>,
@@ -608,11 +1987,14 @@ pub fn validate_card_data(
         let exp_month = card
             .card_exp_month
             .peek()
-            .to_string()
             .parse::<u8>()
-            .into_report()
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "card_exp_month",
+            .ok()
+            .filter(|month| (1..=12).contains(month))
+            .ok_or_else(|| {
+                report!(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "card_exp_month".to_string(),
+                    expected_format: "a month between 01 and 12".to_string(),
+                })
             })?;
         let month = ::cards::CardExpirationMonth::try_from(exp_month).change_context(
             errors::ApiErrorResponse::PreconditionFailed {
@@ -623,11 +2005,20 @@ pub fn validate_card_data(
         if year_str.len() == 2 {
             year_str = format!("20{}", year_str);
         }
-        let exp_year = year_str.parse::<u16>().into_report().change_context(
-            errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "card_exp_year",
-            },
-        )?;
+        // Years too far in the future are rejected along with the past ones, since they are
+        // most likely typos
+        let current_year = common_utils::date_time::now().year();
+        let max_year = current_year + consts::CARD_EXPIRY_MAX_YEARS_AHEAD;
+        let exp_year = year_str
+            .parse::<u16>()
+            .ok()
+            .filter(|year| (current_year..=max_year).contains(&i32::from(*year)))
+            .ok_or_else(|| {
+                report!(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "card_exp_year".to_string(),
+                    expected_format: format!("a year between {current_year} and {max_year}"),
+                })
+            })?;
         let year = ::cards::CardExpirationYear::try_from(exp_year).change_context(
             errors::ApiErrorResponse::PreconditionFailed {
                 message: "Invalid Expiry Year".to_string(),
@@ -707,6 +2098,18 @@ fn validate_new_mandate_request(
         }))
     })?;
 
+    // The mandate is created only when the payment is confirmed, so an off session payment
+    // cannot be made against it while the setup payment is still awaiting confirmation
+    fp_utils::when(
+        !is_confirm_operation && req.confirm != Some(true) && req.off_session == Some(true),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`off_session` can be set only when `confirm` is `true` for mandates"
+                    .into()
+            }))
+        },
+    )?;
+
     let mandate_data = req
         .mandate_data
         .clone()
@@ -846,6 +2249,34 @@ fn validate_recurring_mandate(req: api::MandateValidationFields) -> RouterResult
     Ok(())
 }
 
+/// Only active mandates can be charged, charges against a revoked mandate are rejected with
+/// `MandateRevoked`
+pub fn verify_mandate_status(mandate_status: storage_enums::MandateStatus) -> RouterResult<()> {
+    match mandate_status {
+        storage_enums::MandateStatus::Active => Ok(()),
+        storage_enums::MandateStatus::Revoked => {
+            Err(report!(errors::ApiErrorResponse::MandateRevoked))
+        }
+        storage_enums::MandateStatus::Inactive | storage_enums::MandateStatus::Pending => {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "mandate is not active".into()
+            }))
+        }
+    }
+}
+
+/// Checks that the mandate can be charged before a payment attempt is created against it, the
+/// mandate has to be active and its end date, if any, should not have passed.
+pub fn verify_mandate_is_chargeable(mandate: &storage::Mandate) -> RouterResult<()> {
+    verify_mandate_status(mandate.mandate_status)?;
+    utils::when(
+        mandate
+            .end_date
+            .map_or(false, |end_date| end_date < common_utils::date_time::now()),
+        || Err(report!(errors::ApiErrorResponse::MandateExpired)),
+    )
+}
+
 pub fn verify_mandate_details(
     request_amount: i64,
     request_currency: api_enums::Currency,
@@ -883,8 +2314,11 @@ pub fn verify_mandate_details(
             .map(|mandate_currency| mandate_currency != request_currency)
             .unwrap_or(false),
         || {
-            Err(report!(errors::ApiErrorResponse::MandateValidationFailed {
-                reason: "cross currency mandates not supported".to_string()
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "currency {request_currency} does not match the mandate currency, cross \
+                     currency mandates are not supported"
+                ),
             }))
         },
     )
@@ -1166,6 +2600,27 @@ pub async fn get_connector_default(
     ))
 }
 
+/// Inserts the customer, or returns the existing one if a customer with the same
+/// `(merchant_id, customer_id)` was already created, for example by a retried request.
+#[instrument(skip_all)]
+pub async fn insert_customer_if_not_exists(
+    db: &dyn StorageInterface,
+    customer: domain::Customer,
+    key_store: &domain::MerchantKeyStore,
+) -> CustomResult<domain::Customer, errors::StorageError> {
+    let customer_id = customer.customer_id.clone();
+    let merchant_id = customer.merchant_id.clone();
+
+    match db.insert_customer(customer, key_store).await {
+        Err(err) if err.current_context().is_db_unique_violation() => {
+            logger::info!("Customer {customer_id} already exists, fetching the existing customer");
+            db.find_customer_by_customer_id_merchant_id(&customer_id, &merchant_id, key_store)
+                .await
+        }
+        result => result,
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn create_customer_if_not_exist<'a, F: Clone, R, Ctx>(
     operation: BoxedOperation<'a, F, R, Ctx>,
@@ -1283,7 +2738,7 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R, Ctx>(
                     .change_context(errors::StorageError::SerializationFailed)
                     .attach_printable("Failed while encrypting Customer while insert")?;
                     metrics::CUSTOMER_CREATED.add(&metrics::CONTEXT, 1, &[]);
-                    db.insert_customer(new_customer, key_store).await
+                    insert_customer_if_not_exists(db, new_customer, key_store).await
                 }
             })
         }
@@ -1456,6 +2911,15 @@ pub async fn make_pm_data<'a, F: Clone, R, Ctx: PaymentMethodRetrieve>(
         _ => Ok(None),
     }?;
 
+    // Saved cards are only known once they are fetched from the locker, so the fingerprint is
+    // generated for every card the payment is made with, whichever operation tokenizes it
+    if let Some(api::PaymentMethodData::Card(card)) = &payment_method {
+        payment_data.payment_attempt.payment_method_fingerprint = Some(generate_card_fingerprint(
+            &state.conf.card_fingerprint,
+            card,
+        )?);
+    }
+
     Ok((operation, payment_method))
 }
 
@@ -1578,6 +3042,23 @@ pub(crate) fn validate_status_with_capture_method(
     )
 }
 
+/// Nothing can be captured once the attempt reaches a terminal status or while it is being
+/// processed by the connector, otherwise the part of the authorized amount which is not captured
+/// yet can be captured.
+pub fn get_remaining_capturable_amount(
+    attempt_status: storage_enums::AttemptStatus,
+    authorized_amount: i64,
+    already_captured: i64,
+) -> i64 {
+    if attempt_status.is_terminal_status()
+        || attempt_status.maps_to_intent_status(storage_enums::IntentStatus::Processing)
+    {
+        0
+    } else {
+        authorized_amount.saturating_sub(already_captured).max(0)
+    }
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_amount_to_capture(
     amount: i64,
@@ -1885,7 +3366,7 @@ pub fn get_handle_response_url(
     response: api::PaymentsResponse,
     connector: String,
 ) -> RouterResult<api::RedirectionResponse> {
-    let payments_return_url = response.return_url.as_ref();
+    let payments_return_url = get_return_url_for_status(&response);
 
     let redirection_response = make_pg_redirect_response(payment_id, &response, connector);
 
@@ -1901,6 +3382,22 @@ pub fn get_handle_response_url(
     make_url_with_signature(&return_url, merchant_account)
 }
 
+/// Returns the url the customer is redirected to once the payment has reached the given status,
+/// the success and failure urls of the payment take precedence over its return url. Authorized
+/// payments waiting for a manual capture are successful from the customer's point of view.
+pub fn get_return_url_for_status(response: &api::PaymentsResponse) -> Option<&String> {
+    let status_url = match response.status {
+        api_enums::IntentStatus::Succeeded | api_enums::IntentStatus::RequiresCapture => {
+            response.success_url.as_ref()
+        }
+        api_enums::IntentStatus::Failed | api_enums::IntentStatus::Cancelled => {
+            response.failure_url.as_ref()
+        }
+        _ => None,
+    };
+    status_url.or(response.return_url.as_ref())
+}
+
 pub fn make_merchant_url_with_response(
     merchant_account: &domain::MerchantAccount,
     redirection_response: api::PgRedirectResponse,
@@ -2084,6 +3581,24 @@ pub fn hmac_sha512_sorted_query_params(
     Ok(hex::encode(signature))
 }
 
+/// Returns the fingerprint of the card number, which is the same for every payment made with the
+/// card irrespective of the customer. The fingerprint is keyed so that the card number cannot be
+/// recovered from it.
+pub fn generate_card_fingerprint(
+    card_fingerprint: &CardFingerprint,
+    card: &api::Card,
+) -> RouterResult<String> {
+    let fingerprint = crypto::HmacSha256::sign_message(
+        &crypto::HmacSha256,
+        card_fingerprint.hash_key.peek().as_bytes(),
+        card.card_number.peek().as_bytes(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to generate the card fingerprint")?;
+
+    Ok(hex::encode(fingerprint))
+}
+
 pub fn check_if_operation_confirm<Op: std::fmt::Debug>(operations: Op) -> bool {
     format!("{operations:?}") == "PaymentConfirm"
 }
@@ -2164,6 +3679,20 @@ pub fn generate_mandate(
     }
 }
 
+/// Returns the time after which the client secret of the payment intent can no longer be used.
+/// The expiry is stored on the payment intent when it is created, for intents created before that,
+/// the merchant_account's intent fulfillment time is used
+pub fn get_client_secret_expiry(
+    payment_intent: &PaymentIntent,
+    merchant_intent_fulfillment_time: Option<i64>,
+) -> time::PrimitiveDateTime {
+    payment_intent.client_secret_expiry.unwrap_or_else(|| {
+        payment_intent.created_at.saturating_add(Duration::seconds(
+            merchant_intent_fulfillment_time.unwrap_or(consts::DEFAULT_FULFILLMENT_TIME),
+        ))
+    })
+}
+
 // A function to manually authenticate the client secret with intent fulfillment time
 pub fn authenticate_client_secret(
     request_client_secret: Option<&String>,
@@ -2172,18 +3701,19 @@ pub fn authenticate_client_secret(
 ) -> Result<(), errors::ApiErrorResponse> {
     match (request_client_secret, &payment_intent.client_secret) {
         (Some(req_cs), Some(pi_cs)) => {
-            if req_cs != pi_cs {
+            // Compared in constant time, so that the secret cannot be guessed from response times
+            if ring::constant_time::verify_slices_are_equal(req_cs.as_bytes(), pi_cs.as_bytes())
+                .is_err()
+            {
                 Err(errors::ApiErrorResponse::ClientSecretInvalid)
             } else {
-                //This is done to check whether the merchant_account's intent fulfillment time has expired or not
-                let payment_intent_fulfillment_deadline =
-                    payment_intent.created_at.saturating_add(Duration::seconds(
-                        merchant_intent_fulfillment_time
-                            .unwrap_or(consts::DEFAULT_FULFILLMENT_TIME),
-                    ));
                 let current_timestamp = common_utils::date_time::now();
                 fp_utils::when(
-                    current_timestamp > payment_intent_fulfillment_deadline,
+                    current_timestamp
+                        > get_client_secret_expiry(
+                            payment_intent,
+                            merchant_intent_fulfillment_time,
+                        ),
                     || Err(errors::ApiErrorResponse::ClientSecretExpired),
                 )
             }
@@ -2404,6 +3934,13 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: None,
+            success_url: None,
+            failure_url: None,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(900);
@@ -2452,6 +3989,13 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: None,
+            success_url: None,
+            failure_url: None,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(10);
@@ -2500,15 +4044,170 @@ mod tests {
             profile_id: None,
             merchant_decision: None,
             payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: None,
+            success_url: None,
+            failure_url: None,
+        };
+        let req_cs = Some("1".to_string());
+        let merchant_fulfillment_time = Some(10);
+        assert!(authenticate_client_secret(
+            req_cs.as_ref(),
+            &payment_intent,
+            merchant_fulfillment_time,
+        )
+        .is_err())
+    }
+
+    #[test]
+    fn test_authenticate_client_secret_matching_and_mismatched_secret() {
+        let payment_intent = PaymentIntent {
+            id: 21,
+            payment_id: "pay_23".to_string(),
+            merchant_id: "22".to_string(),
+            status: storage_enums::IntentStatus::RequiresPaymentMethod,
+            amount: 200,
+            currency: None,
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: Some("pay_23_secret_el9ksDkiB8hi6j9N78yo".to_string()),
+            active_attempt: data_models::RemoteStorageObject::ForeignID("nopes".to_string()),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: None,
+            merchant_decision: None,
+            payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: None,
+            success_url: None,
+            failure_url: None,
+        };
+        let merchant_fulfillment_time = Some(900);
+
+        let matching_cs = "pay_23_secret_el9ksDkiB8hi6j9N78yo".to_string();
+        assert!(authenticate_client_secret(
+            Some(&matching_cs),
+            &payment_intent,
+            merchant_fulfillment_time,
+        )
+        .is_ok());
+
+        for mismatched_cs in ["pay_23_secret_el9ksDkiB8hi6j9N78yO", "pay_23_secret_el9ks"] {
+            assert!(matches!(
+                authenticate_client_secret(
+                    Some(&mismatched_cs.to_string()),
+                    &payment_intent,
+                    merchant_fulfillment_time,
+                ),
+                Err(errors::ApiErrorResponse::ClientSecretInvalid)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_client_secret_is_required_for_client_auth_confirm() {
+        let request = api::PaymentsRequest {
+            confirm: Some(true),
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_client_secret_for_confirm(services::AuthFlow::Client, &request),
+            Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "client_secret"
+            })
+        ));
+        assert!(validate_client_secret_for_confirm(services::AuthFlow::Merchant, &request).is_ok());
+
+        let request = api::PaymentsRequest {
+            client_secret: Some("pay_23_secret_el9ksDkiB8hi6j9N78yo".to_string()),
+            ..request
+        };
+        assert!(validate_client_secret_for_confirm(services::AuthFlow::Client, &request).is_ok());
+    }
+
+    #[test]
+    fn test_authenticate_client_secret_past_stored_expiry() {
+        let payment_intent = PaymentIntent {
+            id: 21,
+            payment_id: "pay_23".to_string(),
+            merchant_id: "22".to_string(),
+            status: storage_enums::IntentStatus::RequiresPaymentMethod,
+            amount: 200,
+            currency: None,
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now().saturating_sub(Duration::seconds(20)),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: Some("pay_23_secret_el9ksDkiB8hi6j9N78yo".to_string()),
+            active_attempt: data_models::RemoteStorageObject::ForeignID("nopes".to_string()),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: None,
+            merchant_decision: None,
+            payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: Some(
+                common_utils::date_time::now().saturating_sub(Duration::seconds(10)),
+            ),
+            success_url: None,
+            failure_url: None,
         };
-        let req_cs = Some("1".to_string());
-        let merchant_fulfillment_time = Some(10);
-        assert!(authenticate_client_secret(
-            req_cs.as_ref(),
-            &payment_intent,
-            merchant_fulfillment_time,
-        )
-        .is_err())
+        // The stored expiry takes precedence over the merchant's intent fulfillment time
+        let merchant_fulfillment_time = Some(900);
+
+        assert!(matches!(
+            authenticate_client_secret(
+                Some(&"pay_23_secret_el9ksDkiB8hi6j9N78yo".to_string()),
+                &payment_intent,
+                merchant_fulfillment_time,
+            ),
+            Err(errors::ApiErrorResponse::ClientSecretExpired)
+        ));
     }
 }
 
@@ -2582,6 +4281,13 @@ impl MerchantConnectorAccountType {
             Self::CacheVal(_) => None,
         }
     }
+
+    pub fn get_payment_methods_enabled(&self) -> Option<&Vec<serde_json::Value>> {
+        match self {
+            Self::DbVal(val) => val.payment_methods_enabled.as_ref(),
+            Self::CacheVal(_) => None,
+        }
+    }
 }
 
 /// Query for merchant connector account either by business label or profile id
@@ -2700,6 +4406,13 @@ pub fn router_data_type_conversion<F1, F2, Req1, Req2, Res1, Res2>(
         connector_api_version: router_data.connector_api_version,
         connector_http_status_code: router_data.connector_http_status_code,
         external_latency: router_data.external_latency,
+        debug_connector_io: router_data.debug_connector_io,
+        connector_io_log: router_data.connector_io_log,
+        connector_authorized_amount: router_data.connector_authorized_amount,
+        avs_result: router_data.avs_result,
+        cvv_result: router_data.cvv_result,
+        connector_response_metadata: router_data.connector_response_metadata,
+        three_ds_authentication_result: router_data.three_ds_authentication_result,
         apple_pay_flow: router_data.apple_pay_flow,
     }
 }
@@ -2816,6 +4529,99 @@ pub fn get_attempt_type(
     }
 }
 
+/// Builds the confirm request which retries a failed payment on the connector of its failed
+/// attempt. The payment method of the failed attempt is reused through its payment token.
+pub fn get_retry_request(
+    payment_intent: &PaymentIntent,
+    payment_attempt: &PaymentAttempt,
+) -> RouterResult<api::PaymentsRequest> {
+    utils::when(payment_intent.status != enums::IntentStatus::Failed, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot retry this payment because it has status {}, only failed payments can be retried",
+                payment_intent.status
+            ),
+        }))
+    })?;
+
+    let connector_name = payment_attempt
+        .connector
+        .as_ref()
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "You cannot retry this payment because it was not routed to any connector"
+                .to_string(),
+        })
+        .into_report()?;
+    let connector = api_enums::RoutableConnectors::from_str(connector_name)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("unable to parse connector name {connector_name:?}"))?;
+    let routing = utils::Encode::<api::StraightThroughAlgorithm>::encode_to_value(
+        &api::StraightThroughAlgorithm::Single(connector),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Unable to serialize the straight through algorithm of the retry")?;
+
+    let payment_token = payment_attempt
+        .payment_token
+        .clone()
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "You cannot retry this payment because the payment method of the failed \
+                attempt is not available, confirm the payment with `retry_action` as \
+                `manual_retry` and the payment method instead"
+                .to_string(),
+        })
+        .into_report()?;
+
+    Ok(api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(
+            payment_intent.payment_id.clone(),
+        )),
+        merchant_id: Some(payment_intent.merchant_id.clone()),
+        confirm: Some(true),
+        retry_action: Some(api_models::enums::RetryAction::ManualRetry),
+        routing: Some(routing),
+        payment_token: Some(payment_token),
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        ..Default::default()
+    })
+}
+
+/// The payment token of the failed attempt refers to the payment method stored in the temporary
+/// locker, which is only kept for a limited time after the payment is created
+pub async fn validate_payment_token_for_retry(
+    db: &dyn StorageInterface,
+    payment_attempt: &PaymentAttempt,
+) -> RouterResult<()> {
+    let payment_token = payment_attempt
+        .payment_token
+        .as_ref()
+        .zip(payment_attempt.payment_method);
+    if let Some((payment_token, payment_method)) = payment_token {
+        let redis_conn = db
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get redis connection")?;
+        let hyperswitch_token = redis_conn
+            .get_key::<Option<String>>(&format!(
+                "pm_token_{payment_token}_{payment_method}_hyperswitch"
+            ))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the token from redis")?;
+        utils::when(hyperswitch_token.is_none(), || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "You cannot retry this payment because the payment method of the failed \
+                    attempt has expired, confirm the payment with `retry_action` as \
+                    `manual_retry` and the payment method instead"
+                    .to_string(),
+            }))
+        })?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum AttemptType {
     New,
@@ -2831,14 +4637,25 @@ impl AttemptType {
         payment_method_data: &Option<api_models::payments::PaymentMethodData>,
         old_payment_attempt: PaymentAttempt,
         new_attempt_count: i16,
-    ) -> storage::PaymentAttemptNew {
+        connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+    ) -> RouterResult<storage::PaymentAttemptNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
+        let attempt_id = core_utils::add_merchant_attempt_id_prefix(
+            connector_request_reference_id_config,
+            &old_payment_attempt.merchant_id,
+            utils::get_payment_attempt_id(&old_payment_attempt.payment_id, new_attempt_count),
+        )?;
+        // The new attempt gets a reference of its own, unless the payment_id is sent as the
+        // reference for all the attempts of the payment
+        let connector_request_reference_id = core_utils::generate_connector_request_reference_id(
+            connector_request_reference_id_config,
+            &old_payment_attempt.merchant_id,
+            &old_payment_attempt.payment_id,
+            &attempt_id,
+        );
 
-        storage::PaymentAttemptNew {
-            attempt_id: utils::get_payment_attempt_id(
-                &old_payment_attempt.payment_id,
-                new_attempt_count,
-            ),
+        Ok(storage::PaymentAttemptNew {
+            attempt_id,
             payment_id: old_payment_attempt.payment_id,
             merchant_id: old_payment_attempt.merchant_id,
 
@@ -2892,7 +4709,23 @@ impl AttemptType {
             connector_response_reference_id: None,
             amount_capturable: old_payment_attempt.amount,
             surcharge_metadata: old_payment_attempt.surcharge_metadata,
-        }
+            dcc_details: None,
+            latency_ms: None,
+            connector_amount: None,
+            amount_mismatch: None,
+            avs_result: None,
+            cvv_result: None,
+            payment_method_fingerprint: None,
+            review_status: None,
+            sdk_information: None,
+            // The retry is made through the same channel as the previous attempt
+            payment_channel: old_payment_attempt.payment_channel,
+            connector_request_reference_id: Some(connector_request_reference_id),
+            // The retry is made for the same number of installments as the previous attempt
+            installments: old_payment_attempt.installments,
+            // Set when the retried payment is confirmed
+            used_saved_method: None,
+        })
     }
 
     #[instrument(skip_all)]
@@ -2903,20 +4736,31 @@ impl AttemptType {
         fetched_payment_attempt: PaymentAttempt,
         db: &dyn StorageInterface,
         storage_scheme: storage::enums::MerchantStorageScheme,
+        connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
     ) -> RouterResult<(PaymentIntent, PaymentAttempt)> {
         match self {
             Self::SameOld => Ok((fetched_payment_intent, fetched_payment_attempt)),
             Self::New => {
                 let new_attempt_count = fetched_payment_intent.attempt_count + 1;
+                let new_payment_attempt = Self::make_new_payment_attempt(
+                    &request.payment_method_data,
+                    fetched_payment_attempt,
+                    new_attempt_count,
+                    connector_request_reference_id_config,
+                )?;
+
+                let payment_intent_update = storage::PaymentIntentUpdate::StatusAndAttemptUpdate {
+                    status: payment_intent_status_fsm(&request.payment_method_data, Some(true)),
+                    active_attempt_id: new_payment_attempt.attempt_id.clone(),
+                    attempt_count: new_attempt_count,
+                };
+                validate_intent_status_transition(
+                    fetched_payment_intent.status,
+                    &payment_intent_update,
+                )?;
+
                 let new_payment_attempt = db
-                    .insert_payment_attempt(
-                        Self::make_new_payment_attempt(
-                            &request.payment_method_data,
-                            fetched_payment_attempt,
-                            new_attempt_count,
-                        ),
-                        storage_scheme,
-                    )
+                    .insert_payment_attempt(new_payment_attempt, storage_scheme)
                     .await
                     .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
                         payment_id: fetched_payment_intent.payment_id.to_owned(),
@@ -2925,14 +4769,7 @@ impl AttemptType {
                 let updated_payment_intent = db
                     .update_payment_intent(
                         fetched_payment_intent,
-                        storage::PaymentIntentUpdate::StatusAndAttemptUpdate {
-                            status: payment_intent_status_fsm(
-                                &request.payment_method_data,
-                                Some(true),
-                            ),
-                            active_attempt_id: new_payment_attempt.attempt_id.clone(),
-                            attempt_count: new_attempt_count,
-                        },
+                        payment_intent_update,
                         storage_scheme,
                     )
                     .await
@@ -3063,6 +4900,625 @@ pub fn is_manual_retry_allowed(
 #[cfg(test)]
 mod test {
     #![allow(clippy::unwrap_used)]
+    #[test]
+    fn test_setup_mandate_is_validated_at_confirm() {
+        let setup_request = |confirm: bool, off_session: Option<bool>| {
+            api_models::payments::MandateValidationFields {
+                mandate_id: None,
+                confirm: Some(confirm),
+                customer_id: Some("cus_123".to_string()),
+                mandate_data: Some(api_models::payments::MandateData::default()),
+                setup_future_usage: Some(api_models::enums::FutureUsage::OffSession),
+                off_session,
+            }
+        };
+
+        // Create the setup payment without confirming it
+        assert!(matches!(
+            super::validate_mandate(setup_request(false, None), false),
+            Ok(Some(
+                super::api::MandateTransactionType::NewMandateTransaction
+            ))
+        ));
+        // Off session usage is not allowed before the setup payment is confirmed
+        assert!(super::validate_mandate(setup_request(false, Some(true)), false).is_err());
+        // Confirm the setup payment
+        assert!(matches!(
+            super::validate_mandate(setup_request(true, None), true),
+            Ok(Some(
+                super::api::MandateTransactionType::NewMandateTransaction
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_address_of_another_merchant_is_not_found() {
+        use storage_impl::MockDb;
+
+        use crate::db::MasterKeyInterface;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let key_store = super::payments::test_utils::get_merchant_key_store(
+            mock_db.get_master_key(),
+            "merchant_1",
+        )
+        .await;
+        let address = super::api::Address {
+            address: Some(api_models::payments::AddressDetails {
+                city: Some("San Francisco".to_string()),
+                ..Default::default()
+            }),
+            phone: None,
+        };
+
+        let address_id = super::create_or_find_address_for_payment_by_request(
+            &mock_db,
+            Some(&address),
+            None,
+            "merchant_1",
+            Some(&"cus_123".to_string()),
+            &key_store,
+            "pay_1",
+            super::storage_enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .address_id;
+
+        let result = super::create_or_find_address_for_payment_by_request(
+            &mock_db,
+            None,
+            Some(&address_id),
+            "merchant_2",
+            None,
+            &key_store,
+            "pay_1",
+            super::storage_enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await;
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::AddressNotFound)
+        ));
+
+        let address = super::create_or_find_address_for_payment_by_request(
+            &mock_db,
+            None,
+            Some(&address_id),
+            "merchant_1",
+            None,
+            &key_store,
+            "pay_1",
+            super::storage_enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            address.map(|address| address.merchant_id),
+            Some("merchant_1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_creating_same_customer_twice_returns_existing_customer() {
+        use storage_impl::MockDb;
+
+        use crate::db::MasterKeyInterface;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let key_store = super::payments::test_utils::get_merchant_key_store(
+            mock_db.get_master_key(),
+            "merchant_1",
+        )
+        .await;
+        let customer = super::domain::Customer {
+            id: None,
+            customer_id: "cus_123".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            name: None,
+            email: None,
+            phone: None,
+            phone_country_code: None,
+            description: None,
+            created_at: common_utils::date_time::now(),
+            metadata: None,
+            modified_at: common_utils::date_time::now(),
+            connector_customer: None,
+            address_id: None,
+        };
+
+        let first = super::insert_customer_if_not_exists(&mock_db, customer.clone(), &key_store)
+            .await
+            .unwrap();
+        let second = super::insert_customer_if_not_exists(&mock_db, customer, &key_store)
+            .await
+            .unwrap();
+
+        assert_eq!(first.customer_id, second.customer_id);
+        assert_eq!(mock_db.customers.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shipping_recipient_is_stored_separately_from_customer() {
+        use masking::Secret;
+
+        let shipping = super::api::Address {
+            address: Some(api_models::payments::AddressDetails {
+                city: Some("San Francisco".to_string()),
+                country: Some(api_models::enums::CountryAlpha2::US),
+                line1: Some(Secret::new("1467 Harrison Street".to_string())),
+                zip: Some(Secret::new("94122".to_string())),
+                first_name: Some(Secret::new("Jane".to_string())),
+                last_name: Some(Secret::new("Roe".to_string())),
+                ..Default::default()
+            }),
+            phone: Some(api_models::payments::PhoneDetails {
+                number: Some(Secret::new("8056594427".to_string())),
+                country_code: Some("+1".to_string()),
+            }),
+        };
+
+        let address = super::get_domain_address_for_payments(
+            shipping.address.clone().unwrap_or_default(),
+            &shipping,
+            "merchant_1",
+            "cus_john_doe",
+            "pay_1",
+            &[0; 32],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(address.customer_id, "cus_john_doe");
+        assert_eq!(super::api::Address::from(&address), shipping);
+    }
+
+    #[test]
+    fn test_intent_status_transitions() {
+        use super::storage_enums::IntentStatus;
+
+        assert!(super::is_valid_transition(
+            IntentStatus::RequiresConfirmation,
+            IntentStatus::Processing
+        ));
+        assert!(super::is_valid_transition(
+            IntentStatus::Processing,
+            IntentStatus::Succeeded
+        ));
+        assert!(super::is_valid_transition(
+            IntentStatus::Failed,
+            IntentStatus::Processing
+        ));
+        assert!(super::is_valid_transition(
+            IntentStatus::RequiresCapture,
+            IntentStatus::PartiallyCaptured
+        ));
+        assert!(super::is_valid_transition(
+            IntentStatus::Succeeded,
+            IntentStatus::Succeeded
+        ));
+
+        assert!(!super::is_valid_transition(
+            IntentStatus::Succeeded,
+            IntentStatus::RequiresConfirmation
+        ));
+        assert!(!super::is_valid_transition(
+            IntentStatus::Succeeded,
+            IntentStatus::Failed
+        ));
+        assert!(!super::is_valid_transition(
+            IntentStatus::Cancelled,
+            IntentStatus::Processing
+        ));
+        assert!(!super::is_valid_transition(
+            IntentStatus::Processing,
+            IntentStatus::RequiresConfirmation
+        ));
+
+        let result = super::validate_intent_status_transition(
+            IntentStatus::Succeeded,
+            &super::storage::PaymentIntentUpdate::PGStatusUpdate {
+                status: IntentStatus::RequiresConfirmation,
+            },
+        );
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::InternalServerError)
+        ));
+    }
+
+    fn card_with_expiry(
+        card_exp_month: &str,
+        card_exp_year: &str,
+    ) -> super::api::PaymentMethodData {
+        super::api::PaymentMethodData::Card(super::api::Card {
+            card_number: "4242424242424242".parse().unwrap(),
+            card_exp_month: masking::Secret::new(card_exp_month.to_string()),
+            card_exp_year: masking::Secret::new(card_exp_year.to_string()),
+            card_cvc: masking::Secret::new("737".to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_card_expiry_month_out_of_range() {
+        let next_year = (common_utils::date_time::now().year() + 1).to_string();
+        let result = super::validate_card_data(Some(card_with_expiry("13", &next_year)));
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "card_exp_month"
+        ));
+        assert!(super::validate_card_data(Some(card_with_expiry("00", &next_year))).is_err());
+    }
+
+    #[test]
+    fn test_empty_card_cvc_is_rejected() {
+        let card = super::api::Card {
+            card_number: "4242424242424242".parse().unwrap(),
+            card_exp_month: masking::Secret::new("10".to_string()),
+            card_exp_year: masking::Secret::new("2030".to_string()),
+            card_cvc: masking::Secret::new("  ".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            super::validate_card_fields_are_not_empty(&card)
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "card_cvc"
+        ));
+
+        let card = super::api::Card {
+            card_cvc: masking::Secret::new("737".to_string()),
+            ..card
+        };
+        assert!(super::validate_card_fields_are_not_empty(&card).is_ok());
+    }
+
+    #[test]
+    fn test_card_expiry_year_in_the_past() {
+        let result = super::validate_card_data(Some(card_with_expiry("10", "2019")));
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "card_exp_year"
+        ));
+        assert!(super::validate_card_data(Some(card_with_expiry("10", "2099"))).is_err());
+
+        let next_year = (common_utils::date_time::now().year() + 1).to_string();
+        assert!(super::validate_card_data(Some(card_with_expiry("10", &next_year))).is_ok());
+    }
+
+    #[test]
+    fn test_payment_method_data_must_match_payment_method() {
+        let card_data =
+            api_models::payments::PaymentMethodData::Card(api_models::payments::Card::default());
+        let wallet_data = api_models::payments::PaymentMethodData::Wallet(
+            api_models::payments::WalletData::PaypalRedirect(
+                api_models::payments::PaypalRedirection {},
+            ),
+        );
+
+        let matching_request = super::api::PaymentsRequest {
+            payment_method: Some(api_models::enums::PaymentMethod::Card),
+            payment_method_data: Some(card_data.clone()),
+            ..Default::default()
+        };
+        assert!(super::validate_payment_method_fields_present(&matching_request).is_ok());
+
+        let mismatched_requests = [
+            super::api::PaymentsRequest {
+                payment_method: Some(api_models::enums::PaymentMethod::Card),
+                payment_method_data: Some(wallet_data),
+                ..Default::default()
+            },
+            super::api::PaymentsRequest {
+                payment_method: Some(api_models::enums::PaymentMethod::Wallet),
+                payment_method_type: Some(api_models::enums::PaymentMethodType::Paypal),
+                payment_method_data: Some(card_data),
+                ..Default::default()
+            },
+        ];
+        for request in mismatched_requests {
+            assert!(matches!(
+                super::validate_payment_method_fields_present(&request)
+                    .map_err(|err| err.current_context().clone()),
+                Err(super::errors::ApiErrorResponse::InvalidRequestData { message })
+                    if message == "payment_method_data doesn't correspond to the specified payment_method"
+            ));
+        }
+    }
+
+    fn get_mandate() -> super::storage::Mandate {
+        super::storage::Mandate {
+            id: 1,
+            mandate_id: "mandate_id".to_string(),
+            customer_id: "customer_id".to_string(),
+            merchant_id: "merchant_id".to_string(),
+            payment_method_id: "payment_method_id".to_string(),
+            mandate_status: super::storage_enums::MandateStatus::Active,
+            mandate_type: super::storage_enums::MandateType::MultiUse,
+            customer_accepted_at: None,
+            customer_ip_address: None,
+            customer_user_agent: None,
+            network_transaction_id: None,
+            previous_attempt_id: None,
+            created_at: common_utils::date_time::now(),
+            mandate_amount: None,
+            mandate_currency: None,
+            amount_captured: None,
+            connector: "stripe".to_string(),
+            connector_mandate_id: None,
+            start_date: None,
+            end_date: None,
+            metadata: None,
+            connector_mandate_ids: None,
+            original_payment_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_charge_against_revoked_mandate_is_rejected() {
+        use storage_impl::MockDb;
+
+        use crate::db::mandate::MandateInterface;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let mandate = mock_db
+            .insert_mandate(super::storage::MandateNew {
+                mandate_id: "mandate_revoke_test".to_string(),
+                customer_id: "customer_id".to_string(),
+                merchant_id: "merchant_id".to_string(),
+                payment_method_id: "payment_method_id".to_string(),
+                mandate_status: super::storage_enums::MandateStatus::Active,
+                mandate_type: super::storage_enums::MandateType::MultiUse,
+                connector: "stripe".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(super::verify_mandate_status(mandate.mandate_status).is_ok());
+
+        let revoked_mandate = mock_db
+            .update_mandate_by_merchant_id_mandate_id(
+                "merchant_id",
+                "mandate_revoke_test",
+                super::storage::MandateUpdate::StatusUpdate {
+                    mandate_status: super::storage_enums::MandateStatus::Revoked,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            super::verify_mandate_status(revoked_mandate.mandate_status)
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::MandateRevoked)
+        ));
+    }
+
+    #[test]
+    fn test_mandate_is_chargeable_only_when_active_and_not_expired() {
+        let now = common_utils::date_time::now();
+        let active_mandate = super::storage::Mandate {
+            end_date: Some(now + time::Duration::days(30)),
+            ..get_mandate()
+        };
+        let revoked_mandate = super::storage::Mandate {
+            mandate_status: super::storage_enums::MandateStatus::Revoked,
+            ..active_mandate.clone()
+        };
+        let expired_mandate = super::storage::Mandate {
+            end_date: Some(now - time::Duration::days(1)),
+            ..active_mandate.clone()
+        };
+
+        assert!(super::verify_mandate_is_chargeable(&active_mandate).is_ok());
+        assert!(matches!(
+            super::verify_mandate_is_chargeable(&revoked_mandate)
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::MandateRevoked)
+        ));
+        assert!(matches!(
+            super::verify_mandate_is_chargeable(&expired_mandate)
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::MandateExpired)
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_merchant_id_is_forbidden() {
+        assert!(super::validate_merchant_id("merchant_1", None).is_ok());
+        assert!(super::validate_merchant_id("merchant_1", Some("merchant_1")).is_ok());
+        assert!(matches!(
+            super::validate_merchant_id("merchant_1", Some("merchant_2"))
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::AccessForbidden { resource })
+                if resource == "merchant_2"
+        ));
+    }
+
+    #[test]
+    fn test_mandate_reuse_with_different_currency() {
+        let mandate = super::storage::Mandate {
+            mandate_amount: Some(1000),
+            mandate_currency: Some(super::storage_enums::Currency::USD),
+            ..get_mandate()
+        };
+
+        assert!(matches!(
+            super::verify_mandate_details(500, api_models::enums::Currency::EUR, mandate.clone())
+                .map_err(|err| err.current_context().clone()),
+            Err(super::errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+        assert!(
+            super::verify_mandate_details(500, api_models::enums::Currency::USD, mandate).is_ok()
+        );
+    }
+
+    fn get_redirect_payments_response(
+        status: api_models::enums::IntentStatus,
+    ) -> api_models::payments::PaymentsResponse {
+        api_models::payments::PaymentsResponse {
+            status,
+            return_url: Some("https://example.com/return".to_string()),
+            success_url: Some("https://example.com/success".to_string()),
+            failure_url: Some("https://example.com/failure".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_success_url_is_selected_for_succeeded_payment() {
+        let response = get_redirect_payments_response(api_models::enums::IntentStatus::Succeeded);
+        assert_eq!(
+            super::get_return_url_for_status(&response).map(String::as_str),
+            Some("https://example.com/success")
+        );
+
+        let response =
+            get_redirect_payments_response(api_models::enums::IntentStatus::RequiresCapture);
+        assert_eq!(
+            super::get_return_url_for_status(&response).map(String::as_str),
+            Some("https://example.com/success")
+        );
+
+        // Payments which are not final yet are redirected to the return url
+        let response = get_redirect_payments_response(api_models::enums::IntentStatus::Processing);
+        assert_eq!(
+            super::get_return_url_for_status(&response).map(String::as_str),
+            Some("https://example.com/return")
+        );
+    }
+
+    #[test]
+    fn test_failure_url_is_selected_for_failed_payment() {
+        let response = get_redirect_payments_response(api_models::enums::IntentStatus::Failed);
+        assert_eq!(
+            super::get_return_url_for_status(&response).map(String::as_str),
+            Some("https://example.com/failure")
+        );
+
+        let response = api_models::payments::PaymentsResponse {
+            failure_url: None,
+            ..get_redirect_payments_response(api_models::enums::IntentStatus::Failed)
+        };
+        assert_eq!(
+            super::get_return_url_for_status(&response).map(String::as_str),
+            Some("https://example.com/return")
+        );
+    }
+
+    #[test]
+    fn test_capture_on_is_truncated_for_date_only_connector() {
+        let date_only_connectors = super::DateOnlyCaptureOnConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Stripe]),
+        };
+        let capture_on = time::macros::datetime!(2023-10-16 11:12:13.456);
+
+        assert_eq!(
+            super::normalize_capture_on(
+                capture_on,
+                Some(&[api_models::enums::Connector::Stripe]),
+                &date_only_connectors
+            ),
+            time::macros::datetime!(2023-10-16 0:00)
+        );
+        // The payment can be routed to any of the connectors in the list
+        assert_eq!(
+            super::normalize_capture_on(
+                capture_on,
+                Some(&[
+                    api_models::enums::Connector::Adyen,
+                    api_models::enums::Connector::Stripe
+                ]),
+                &date_only_connectors
+            ),
+            time::macros::datetime!(2023-10-16 0:00)
+        );
+        assert_eq!(
+            super::normalize_capture_on(
+                capture_on,
+                Some(&[api_models::enums::Connector::Adyen]),
+                &date_only_connectors
+            ),
+            time::macros::datetime!(2023-10-16 11:12:13)
+        );
+    }
+
+    #[test]
+    fn test_saved_payment_method_used() {
+        let card = Some(api_models::payments::PaymentMethodData::Card(
+            api_models::payments::Card::default(),
+        ));
+        let token = Some("token_123".to_string());
+        let mandate_id = Some("man_123".to_string());
+
+        assert!(super::is_saved_payment_method_used(&None, &token, &None));
+        assert!(super::is_saved_payment_method_used(
+            &None,
+            &None,
+            &mandate_id
+        ));
+        // Fresh card, the token is the one generated for the card data in the request
+        assert!(!super::is_saved_payment_method_used(&card, &token, &None));
+        assert!(!super::is_saved_payment_method_used(&card, &None, &None));
+    }
+
+    #[test]
+    fn test_resolve_payment_method() {
+        let card =
+            api_models::payments::PaymentMethodData::Card(api_models::payments::Card::default());
+        let token = "token_123".to_string();
+        let mandate_id = "man_123".to_string();
+
+        assert_eq!(
+            super::resolve_payment_method(Some(&card), Some(&token), None),
+            super::ResolvedPaymentMethod::PaymentMethodData
+        );
+        assert_eq!(
+            super::resolve_payment_method(None, Some(&token), None),
+            super::ResolvedPaymentMethod::Token
+        );
+        assert_eq!(
+            super::resolve_payment_method(None, Some(&token), Some(&mandate_id)),
+            super::ResolvedPaymentMethod::Mandate
+        );
+        assert_eq!(
+            super::resolve_payment_method(None, None, None),
+            super::ResolvedPaymentMethod::NotProvided
+        );
+    }
+
+    #[test]
+    fn test_bank_transfer_create_infers_payment_method() {
+        let request = api_models::payments::PaymentsRequest {
+            payment_method_data: Some(api_models::payments::PaymentMethodData::BankTransfer(
+                Box::new(api_models::payments::BankTransferData::Pix {}),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(
+            super::get_payment_method_with_bank_transfer_fallback(&request),
+            Some(super::storage_enums::PaymentMethod::BankTransfer)
+        );
+
+        let request = api_models::payments::PaymentsRequest {
+            payment_method: Some(api_models::enums::PaymentMethod::Card),
+            ..Default::default()
+        };
+        assert_eq!(
+            super::get_payment_method_with_bank_transfer_fallback(&request),
+            Some(super::storage_enums::PaymentMethod::Card)
+        );
+    }
+
     #[test]
     fn test_client_secret_parse() {
         let client_secret1 = "pay_3TgelAms4RQec8xSStjF_secret_fc34taHLw1ekPgNh92qr";
@@ -3224,6 +5680,20 @@ pub fn validate_customer_access(
     Ok(())
 }
 
+/// Payments confirmed with the publishable key are only authenticated by the client secret of the
+/// payment, a confirm request without it cannot be matched against the secret of the intent
+pub fn validate_client_secret_for_confirm(
+    auth_flow: services::AuthFlow,
+    request: &api::PaymentsRequest,
+) -> Result<(), errors::ApiErrorResponse> {
+    if auth_flow == services::AuthFlow::Client && request.client_secret.is_none() {
+        Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "client_secret",
+        })?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct ApplePayData {
     version: masking::Secret<String>,
@@ -0,0 +1,177 @@
+use api_models::payments::{HostedPaymentPageResponse, NextActionData};
+use error_stack::{report, IntoReport, ResultExt};
+
+use crate::{
+    configs::settings::HostedPaymentPage,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments::helpers,
+    },
+    routes::AppState,
+    services,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+    utils::{self, OptionExt},
+};
+
+/// The customer can only complete payments on the hosted page which are waiting for the payment
+/// method or for the confirmation
+const HOSTED_PAGE_ALLOWED_STATUSES: [enums::IntentStatus; 2] = [
+    enums::IntentStatus::RequiresPaymentMethod,
+    enums::IntentStatus::RequiresConfirmation,
+];
+
+/// Returns the url of the hosted checkout page of the payment, the client secret of the payment is
+/// added to the url so that the page can complete the payment on behalf of the customer
+pub fn create_hosted_payment_page_url(
+    hosted_payment_page: &HostedPaymentPage,
+    payment_intent: &storage::PaymentIntent,
+    merchant_intent_fulfillment_time: Option<i64>,
+) -> RouterResult<String> {
+    if !HOSTED_PAGE_ALLOWED_STATUSES.contains(&payment_intent.status) {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot create a hosted payment page for this payment because it has status {}",
+                payment_intent.status
+            ),
+        }))?
+    }
+
+    let client_secret = payment_intent
+        .client_secret
+        .as_ref()
+        .get_required_value("client_secret")
+        .change_context(errors::ApiErrorResponse::ClientSecretExpired)?;
+    // The page could not complete the payment with an expired client secret
+    utils::when(
+        common_utils::date_time::now()
+            > helpers::get_client_secret_expiry(payment_intent, merchant_intent_fulfillment_time),
+        || Err(report!(errors::ApiErrorResponse::ClientSecretExpired)),
+    )?;
+
+    let mut hosted_page_url = url::Url::parse(&format!(
+        "{}/{}",
+        hosted_payment_page.base_url.trim_end_matches('/'),
+        payment_intent.payment_id
+    ))
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Invalid base url configured for the hosted payment page")?;
+    hosted_page_url
+        .query_pairs_mut()
+        .append_pair("client_secret", client_secret);
+
+    Ok(hosted_page_url.to_string())
+}
+
+pub async fn get_hosted_payment_page(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+) -> RouterResponse<HostedPaymentPageResponse> {
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let hosted_page_url = create_hosted_payment_page_url(
+        &state.conf.hosted_payment_page,
+        &payment_intent,
+        merchant_account.intent_fulfillment_time,
+    )?;
+
+    Ok(services::ApplicationResponse::Json(
+        HostedPaymentPageResponse {
+            payment_id: payment_intent.payment_id,
+            next_action: NextActionData::RedirectToUrl {
+                redirect_to_url: hosted_page_url.clone(),
+            },
+            hosted_page_url,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::core::payments::test_utils;
+
+    fn get_payment_intent(status: enums::IntentStatus) -> storage::PaymentIntent {
+        storage::PaymentIntent {
+            client_secret: Some("pay_hosted_1_secret_el9ksDkiB8hi6j9N78yo".to_string()),
+            ..test_utils::get_payment_intent("pay_hosted_1", status)
+        }
+    }
+
+    #[test]
+    fn test_hosted_payment_page_url_for_created_payment() {
+        let hosted_payment_page = HostedPaymentPage {
+            base_url: "https://checkout.example.com/pay/".to_string(),
+        };
+
+        let hosted_page_url = create_hosted_payment_page_url(
+            &hosted_payment_page,
+            &get_payment_intent(enums::IntentStatus::RequiresPaymentMethod),
+            None,
+        )
+        .unwrap();
+        let parsed_url = url::Url::parse(&hosted_page_url).unwrap();
+
+        assert_eq!(parsed_url.host_str(), Some("checkout.example.com"));
+        assert_eq!(parsed_url.path(), "/pay/pay_hosted_1");
+        assert_eq!(
+            parsed_url.query_pairs().collect::<Vec<_>>(),
+            vec![(
+                "client_secret".into(),
+                "pay_hosted_1_secret_el9ksDkiB8hi6j9N78yo".into()
+            )]
+        );
+
+        assert!(matches!(
+            create_hosted_payment_page_url(
+                &hosted_payment_page,
+                &get_payment_intent(enums::IntentStatus::Succeeded),
+                None,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_hosted_payment_page_url_is_not_created_with_expired_client_secret() {
+        let hosted_payment_page = HostedPaymentPage {
+            base_url: "https://checkout.example.com/pay/".to_string(),
+        };
+        let mut payment_intent = get_payment_intent(enums::IntentStatus::RequiresPaymentMethod);
+        payment_intent.client_secret_expiry =
+            Some(common_utils::date_time::now() - time::Duration::minutes(1));
+
+        assert!(matches!(
+            create_hosted_payment_page_url(&hosted_payment_page, &payment_intent, None)
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::ClientSecretExpired)
+        ));
+
+        // Intents created without an expiry use the intent fulfillment time of the merchant
+        payment_intent.client_secret_expiry = None;
+        payment_intent.created_at = common_utils::date_time::now() - time::Duration::minutes(10);
+        assert!(matches!(
+            create_hosted_payment_page_url(&hosted_payment_page, &payment_intent, Some(60))
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::ClientSecretExpired)
+        ));
+        assert!(
+            create_hosted_payment_page_url(&hosted_payment_page, &payment_intent, Some(3600))
+                .is_ok()
+        );
+    }
+}
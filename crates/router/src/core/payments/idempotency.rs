@@ -0,0 +1,357 @@
+use std::future::Future;
+
+use api_models::payments::{PaymentsRequest, PaymentsResponse};
+use common_utils::crypto::{self, GenerateDigest};
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::SetnxReply;
+use router_env::logger;
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    services::{self, RedirectForm},
+};
+
+pub const IDEMPOTENCY_KEY_PREFIX: &str = "IDEMPOTENCY";
+
+/// Stored against the idempotency key while the first request with the key is being processed
+const IDEMPOTENCY_KEY_IN_PROGRESS: &str = "IN_PROGRESS";
+
+fn get_redis_idempotency_key(merchant_id: &str, idempotency_key: &str) -> String {
+    format!("{IDEMPOTENCY_KEY_PREFIX}_{merchant_id}_{idempotency_key}")
+}
+
+/// The response stored against an idempotency key, along with the hash of the request that
+/// created it
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct StoredResponse {
+    request_hash: String,
+    response: StoredResponseBody,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StoredResponseBody {
+    /// The body is stored the same way as it is written to the response, so that the replayed
+    /// body is identical to the original one
+    Json { body: String },
+    /// The payment method data of the redirection form is not stored, since it contains card
+    /// details
+    Form {
+        redirect_form: RedirectForm,
+        amount: String,
+        currency: String,
+    },
+}
+
+fn get_request_hash(request: &PaymentsRequest) -> RouterResult<String> {
+    let request_body = serde_json::to_vec(request)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the payments request for idempotency")?;
+    crypto::Sha256
+        .generate_digest(&request_body)
+        .map(hex::encode)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to hash the payments request for idempotency")
+}
+
+/// Returns the response to be stored for a successful request, `None` if the response cannot be
+/// replayed
+fn store_response(
+    response: &services::ApplicationResponse<PaymentsResponse>,
+) -> RouterResult<Option<StoredResponseBody>> {
+    let serialize = |payments_response: &PaymentsResponse| {
+        serde_json::to_string(payments_response)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the payments response for idempotent replays")
+    };
+    Ok(match response {
+        // The headers of the response describe the connector call made by the original request,
+        // they are not replayed
+        services::ApplicationResponse::Json(payments_response)
+        | services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+            Some(StoredResponseBody::Json {
+                body: serialize(payments_response)?,
+            })
+        }
+        services::ApplicationResponse::Form(redirection_data) => Some(StoredResponseBody::Form {
+            redirect_form: redirection_data.redirect_form.clone(),
+            amount: redirection_data.amount.clone(),
+            currency: redirection_data.currency.clone(),
+        }),
+        services::ApplicationResponse::StatusOk
+        | services::ApplicationResponse::TextPlain(_)
+        | services::ApplicationResponse::JsonForRedirection(_)
+        | services::ApplicationResponse::PaymenkLinkForm(_)
+        | services::ApplicationResponse::FileData(_) => None,
+    })
+}
+
+fn replay_response(
+    stored_response: StoredResponseBody,
+) -> services::ApplicationResponse<PaymentsResponse> {
+    match stored_response {
+        StoredResponseBody::Json { body } => {
+            services::ApplicationResponse::FileData((body.into_bytes(), mime::APPLICATION_JSON))
+        }
+        StoredResponseBody::Form {
+            redirect_form,
+            amount,
+            currency,
+        } => services::ApplicationResponse::Form(Box::new(services::RedirectionFormData {
+            redirect_form,
+            payment_method_data: None,
+            amount,
+            currency,
+        })),
+    }
+}
+
+/// Runs `create` once per idempotency key of the merchant. The response of the first request is
+/// stored and returned to every replay of the request with the same key, without creating the
+/// payment again. A request with the same key but a different body fails with
+/// `IdempotencyKeyReused`. Requests without an idempotency key are not affected.
+pub async fn create_with_idempotency<Fut>(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    idempotency_key: Option<&str>,
+    request: &PaymentsRequest,
+    create: Fut,
+) -> RouterResponse<PaymentsResponse>
+where
+    Fut: Future<Output = RouterResponse<PaymentsResponse>>,
+{
+    let redis_key = match idempotency_key {
+        Some(idempotency_key) => get_redis_idempotency_key(merchant_id, idempotency_key),
+        None => return create.await,
+    };
+    let request_hash = get_request_hash(request)?;
+    let redis_conn = db
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let stored_response = redis_conn
+        .get_key::<Option<String>>(&redis_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    match stored_response {
+        Some(stored_response) if stored_response == IDEMPOTENCY_KEY_IN_PROGRESS => {
+            Err(errors::ApiErrorResponse::ResourceBusy).into_report()?
+        }
+        Some(stored_response) => {
+            let stored_response: StoredResponse = serde_json::from_str(&stored_response)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to deserialize the stored payments response")?;
+            crate::utils::when(stored_response.request_hash != request_hash, || {
+                Err(errors::ApiErrorResponse::IdempotencyKeyReused).into_report()
+            })?;
+            logger::info!("Replaying the stored response for idempotency key {redis_key}");
+            return Ok(replay_response(stored_response.response));
+        }
+        None => {}
+    }
+
+    match redis_conn
+        .set_key_if_not_exists_with_expiry(
+            &redis_key,
+            IDEMPOTENCY_KEY_IN_PROGRESS,
+            Some(consts::IDEMPOTENCY_KEY_TTL),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+    {
+        SetnxReply::KeySet => {}
+        SetnxReply::KeyNotSet => Err(errors::ApiErrorResponse::ResourceBusy).into_report()?,
+    }
+
+    let response = create.await;
+    // The payment has already been created at this point, so a response which cannot be stored
+    // releases the idempotency key instead of failing the request
+    let stored_response = match &response {
+        Ok(application_response) => serialize_stored_response(request_hash, application_response),
+        Err(_) => Ok(None),
+    };
+    let is_response_stored = match stored_response {
+        Ok(Some(stored_response)) => redis_conn
+            .set_key_with_expiry(&redis_key, stored_response, consts::IDEMPOTENCY_KEY_TTL)
+            .await
+            .map_err(|error| {
+                logger::error!(
+                    ?error,
+                    "Failed to store the payments response for idempotency key {redis_key}"
+                )
+            })
+            .is_ok(),
+        Ok(None) => false,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                "Failed to serialize the payments response for idempotency key {redis_key}"
+            );
+            false
+        }
+    };
+    // Failed requests, and requests whose response is not stored, can be retried with the same
+    // idempotency key
+    if !is_response_stored {
+        redis_conn
+            .delete_key(&redis_key)
+            .await
+            .map_err(|error| {
+                logger::error!(?error, "Failed to release idempotency key {redis_key}")
+            })
+            .ok();
+    }
+    response
+}
+
+/// Serializes the response to be stored against the idempotency key, `None` if the response
+/// cannot be replayed
+fn serialize_stored_response(
+    request_hash: String,
+    response: &services::ApplicationResponse<PaymentsResponse>,
+) -> RouterResult<Option<String>> {
+    store_response(response)?
+        .map(|stored_response| {
+            serde_json::to_string(&StoredResponse {
+                request_hash,
+                response: stored_response,
+            })
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the payments response for idempotent replays")
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use storage_impl::MockDb;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_same_request_with_idempotency_key_creates_one_payment() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+        let payments_response = PaymentsResponse {
+            payment_id: Some("pay_idempotent_1".to_string()),
+            merchant_id: Some("merchant_1".to_string()),
+            amount: 1000,
+            client_secret: Some(masking::Secret::new(
+                "pay_idempotent_1_secret_el9ksDkiB8hi6j9N78yo".to_string(),
+            )),
+            ..Default::default()
+        };
+        // The body written for the original response by `services::api::server_wrap`
+        let original_body = serde_json::to_string(&payments_response).unwrap();
+        let request = PaymentsRequest {
+            amount: Some(api_models::payments::Amount::from(1000)),
+            ..Default::default()
+        };
+
+        let created_payments = &AtomicUsize::new(0);
+        let created_payments_response = &payments_response;
+        let create = move || async move {
+            created_payments.fetch_add(1, Ordering::SeqCst);
+            let response: RouterResponse<PaymentsResponse> =
+                Ok(services::ApplicationResponse::JsonWithHeaders((
+                    created_payments_response.clone(),
+                    vec![("connector_http_status_code".to_string(), "200".to_string())],
+                )));
+            response
+        };
+
+        let first_response = create_with_idempotency(
+            &mock_db,
+            "merchant_1",
+            Some(&idempotency_key),
+            &request,
+            create(),
+        )
+        .await
+        .unwrap();
+        let second_response = create_with_idempotency(
+            &mock_db,
+            "merchant_1",
+            Some(&idempotency_key),
+            &request,
+            create(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created_payments.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            first_response,
+            services::ApplicationResponse::JsonWithHeaders((response, _))
+                if response == payments_response
+        ));
+        assert!(matches!(
+            second_response,
+            services::ApplicationResponse::FileData((body, content_type))
+                if body == original_body.as_bytes() && content_type == mime::APPLICATION_JSON
+        ));
+
+        let different_request = PaymentsRequest {
+            amount: Some(api_models::payments::Amount::from(2000)),
+            ..Default::default()
+        };
+        let reused_key_response = create_with_idempotency(
+            &mock_db,
+            "merchant_1",
+            Some(&idempotency_key),
+            &different_request,
+            create(),
+        )
+        .await;
+        assert!(matches!(
+            reused_key_response.map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::IdempotencyKeyReused)
+        ));
+        assert_eq!(created_payments.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_released_when_the_response_is_not_stored() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+        let request = PaymentsRequest::default();
+
+        let created_payments = &AtomicUsize::new(0);
+        // The response cannot be replayed, so it is not stored against the idempotency key
+        let create = move || async move {
+            created_payments.fetch_add(1, Ordering::SeqCst);
+            let response: RouterResponse<PaymentsResponse> =
+                Ok(services::ApplicationResponse::StatusOk);
+            response
+        };
+
+        for _ in 0..2 {
+            assert!(matches!(
+                create_with_idempotency(
+                    &mock_db,
+                    "merchant_1",
+                    Some(&idempotency_key),
+                    &request,
+                    create(),
+                )
+                .await,
+                Ok(services::ApplicationResponse::StatusOk)
+            ));
+        }
+        // The key is not left in progress, the retry with the same key is processed again
+        assert_eq!(created_payments.load(Ordering::SeqCst), 2);
+    }
+}
@@ -12,7 +12,9 @@ use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress, PaymentData,
+        },
     },
     db::StorageInterface,
     routes::AppState,
@@ -53,25 +55,18 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         let storage_scheme = merchant_account.storage_scheme;
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         let payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
-        helpers::validate_payment_status_against_not_allowed_statuses(
-            &payment_intent.status,
-            &[
-                enums::IntentStatus::Failed,
-                enums::IntentStatus::Succeeded,
-                enums::IntentStatus::Cancelled,
-                enums::IntentStatus::Processing,
-                enums::IntentStatus::RequiresMerchantAction,
-            ],
-            "cancel",
-        )?;
-
         let mut payment_attempt = db
             .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
                 payment_intent.payment_id.as_str(),
@@ -82,6 +77,27 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
+        // Captures which are still being processed are reversed through the void flow of the
+        // connector, if the connector supports it
+        if !helpers::is_capture_reversible(
+            payment_intent.status,
+            payment_attempt.status,
+            payment_attempt.connector.as_deref(),
+            &state.conf.capture_reversal_supported_connectors,
+        ) {
+            helpers::validate_payment_status_against_not_allowed_statuses(
+                &payment_intent.status,
+                &[
+                    enums::IntentStatus::Failed,
+                    enums::IntentStatus::Succeeded,
+                    enums::IntentStatus::Cancelled,
+                    enums::IntentStatus::Processing,
+                    enums::IntentStatus::RequiresMerchantAction,
+                ],
+                "cancel",
+            )?;
+        }
+
         let shipping_address = helpers::create_or_find_address_for_payment_by_request(
             db,
             None,
@@ -138,6 +154,8 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             .await
             .transpose()?;
 
+        let used_saved_method = payment_attempt.used_saved_method.unwrap_or(false);
+
         Ok((
             Box::new(self),
             PaymentData {
@@ -158,6 +176,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: None,
                 payment_method_data: None,
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -174,6 +193,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             None,
         ))
@@ -203,17 +226,23 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
         F: 'b + Send,
     {
         let cancellation_reason = payment_data.payment_attempt.cancellation_reason.clone();
-        let (intent_status_update, attempt_status_update) =
-            if payment_data.payment_intent.status != enums::IntentStatus::RequiresCapture {
-                let payment_intent_update = storage::PaymentIntentUpdate::PGStatusUpdate {
-                    status: enums::IntentStatus::Cancelled,
-                };
-                (Some(payment_intent_update), enums::AttemptStatus::Voided)
-            } else {
-                (None, enums::AttemptStatus::VoidInitiated)
+        let (intent_status_update, attempt_status_update) = if !matches!(
+            payment_data.payment_intent.status,
+            enums::IntentStatus::RequiresCapture | enums::IntentStatus::Processing
+        ) {
+            let payment_intent_update = storage::PaymentIntentUpdate::PGStatusUpdate {
+                status: enums::IntentStatus::Cancelled,
             };
+            (Some(payment_intent_update), enums::AttemptStatus::Voided)
+        } else {
+            (None, enums::AttemptStatus::VoidInitiated)
+        };
 
         if let Some(payment_intent_update) = intent_status_update {
+            helpers::validate_intent_status_transition(
+                payment_data.payment_intent.status,
+                &payment_intent_update,
+            )?;
             payment_data.payment_intent = db
                 .update_payment_intent(
                     payment_data.payment_intent,
@@ -262,3 +291,276 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{
+        configs::settings::{CaptureReversalSupportedConnectors, Settings},
+        core::{payment_methods::Oss, payments::test_utils},
+        db::{MasterKeyInterface, StorageImpl},
+    };
+
+    async fn insert_captured_payment(
+        state: &AppState,
+        payment_id: &str,
+        connector: &str,
+        intent_status: enums::IntentStatus,
+        attempt_status: enums::AttemptStatus,
+    ) {
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let attempt_id = format!("{payment_id}_1");
+        let current_time = common_utils::date_time::now();
+        state
+            .store
+            .insert_payment_intent(
+                storage::PaymentIntentNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    status: intent_status,
+                    amount: 1000,
+                    currency: Some(enums::Currency::USD),
+                    amount_captured: Some(1000),
+                    customer_id: None,
+                    description: None,
+                    return_url: None,
+                    metadata: None,
+                    connector_id: None,
+                    shipping_address_id: None,
+                    billing_address_id: None,
+                    statement_descriptor_name: None,
+                    statement_descriptor_suffix: None,
+                    created_at: None,
+                    modified_at: None,
+                    last_synced: None,
+                    setup_future_usage: None,
+                    off_session: None,
+                    client_secret: None,
+                    active_attempt: data_models::RemoteStorageObject::ForeignID(attempt_id.clone()),
+                    business_country: None,
+                    business_label: None,
+                    order_details: None,
+                    allowed_payment_method_types: None,
+                    connector_metadata: None,
+                    feature_metadata: None,
+                    attempt_count: 1,
+                    profile_id: None,
+                    merchant_decision: None,
+                    payment_link_id: None,
+                    payment_confirm_source: None,
+                    connector_routing_trail: None,
+                    statement_descriptor_city: None,
+                    shipping_amount: None,
+                    handling_amount: None,
+                    client_secret_expiry: None,
+                    success_url: None,
+                    failure_url: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: attempt_id.clone(),
+                    status: attempt_status,
+                    amount: 1000,
+                    currency: Some(enums::Currency::USD),
+                    connector: Some(connector.to_string()),
+                    payment_method: Some(enums::PaymentMethod::Card),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_connector_response(
+                storage::ConnectorResponseNew {
+                    payment_id: payment_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id,
+                    created_at: current_time,
+                    modified_at: current_time,
+                    connector_name: Some(connector.to_string()),
+                    connector_transaction_id: None,
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn cancel(
+        state: &AppState,
+        merchant_account: &domain::MerchantAccount,
+        key_store: &domain::MerchantKeyStore,
+        payment_id: &str,
+    ) -> RouterResult<PaymentData<api::Void>> {
+        let request = api::PaymentsCancelRequest {
+            payment_id: payment_id.to_string(),
+            ..Default::default()
+        };
+        let (_, payment_data, _) = GetTracker::<
+            api::Void,
+            PaymentData<api::Void>,
+            api::PaymentsCancelRequest,
+            Oss,
+        >::get_trackers(
+            &PaymentCancel,
+            state,
+            &api::PaymentIdType::PaymentIntentId(payment_id.to_string()),
+            &request,
+            None,
+            merchant_account,
+            key_store,
+            services::AuthFlow::Merchant,
+        )
+        .await?;
+        Ok(payment_data)
+    }
+
+    #[actix_rt::test]
+    async fn test_captures_being_processed_are_cancelled_only_if_reversible() {
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.capture_reversal_supported_connectors
+            .connector_list
+            .insert(api_models::enums::Connector::Adyen);
+        let state = AppState::with_storage(
+            conf,
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let key_store =
+            test_utils::get_merchant_key_store(state.store.get_master_key(), "merchant_1").await;
+        let merchant_account = test_utils::get_merchant_account();
+        insert_captured_payment(
+            &state,
+            "pay_settled",
+            "adyen",
+            enums::IntentStatus::Succeeded,
+            enums::AttemptStatus::Charged,
+        )
+        .await;
+        insert_captured_payment(
+            &state,
+            "pay_capture_initiated",
+            "adyen",
+            enums::IntentStatus::Processing,
+            enums::AttemptStatus::CaptureInitiated,
+        )
+        .await;
+        insert_captured_payment(
+            &state,
+            "pay_capture_not_reversible",
+            "stripe",
+            enums::IntentStatus::Processing,
+            enums::AttemptStatus::CaptureInitiated,
+        )
+        .await;
+
+        // The capture of a succeeded payment is settled, it can only be refunded
+        assert!(matches!(
+            cancel(&state, &merchant_account, &key_store, "pay_settled")
+                .await
+                .map(|_| ())
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+        // Captures can only be reversed by the connectors which support it
+        assert!(matches!(
+            cancel(
+                &state,
+                &merchant_account,
+                &key_store,
+                "pay_capture_not_reversible"
+            )
+            .await
+            .map(|_| ())
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+        let payment_data = cancel(
+            &state,
+            &merchant_account,
+            &key_store,
+            "pay_capture_initiated",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            payment_data.payment_attempt.status,
+            enums::AttemptStatus::CaptureInitiated
+        );
+    }
+
+    #[test]
+    fn test_pre_settlement_capture_reversal() {
+        use api_models::enums::{AttemptStatus, IntentStatus};
+
+        let capture_reversal_supported_connectors = CaptureReversalSupportedConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Adyen]),
+        };
+
+        // Succeeded and partially captured payments are settled, only captures which are still
+        // being processed can be reversed
+        assert!(
+            helpers::is_capture_settled(IntentStatus::Succeeded, AttemptStatus::Charged).unwrap()
+        );
+        assert!(helpers::is_capture_settled(
+            IntentStatus::PartiallyCaptured,
+            AttemptStatus::PartialCharged
+        )
+        .unwrap());
+        assert!(!helpers::is_capture_settled(
+            IntentStatus::Processing,
+            AttemptStatus::CaptureInitiated
+        )
+        .unwrap());
+        assert!(helpers::is_capture_reversible(
+            IntentStatus::Processing,
+            AttemptStatus::CaptureInitiated,
+            Some("adyen"),
+            &capture_reversal_supported_connectors,
+        ));
+        assert!(!helpers::is_capture_reversible(
+            IntentStatus::PartiallyCaptured,
+            AttemptStatus::PartialCharged,
+            Some("adyen"),
+            &capture_reversal_supported_connectors,
+        ));
+        assert!(helpers::is_capture_reversal_supported(
+            &api_models::enums::Connector::Adyen,
+            &capture_reversal_supported_connectors,
+        ));
+        // Connectors without a reversal flow fall back to a refund of the capture
+        assert!(!helpers::is_capture_reversal_supported(
+            &api_models::enums::Connector::Stripe,
+            &capture_reversal_supported_connectors,
+        ));
+        assert!(matches!(
+            helpers::is_capture_settled(IntentStatus::RequiresCapture, AttemptStatus::Authorized)
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+}
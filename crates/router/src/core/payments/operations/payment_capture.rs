@@ -1,10 +1,10 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, str::FromStr};
 
 use api_models::enums::FrmSuggestion;
 use async_trait::async_trait;
 use common_utils::ext_traits::AsyncExt;
 use diesel_models::connector_response::ConnectorResponse;
-use error_stack::ResultExt;
+use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
@@ -12,7 +12,10 @@ use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, types::MultipleCaptureData},
+        payments::{
+            self, helpers, operations,
+            types::{MultipleCaptureData, PaymentId},
+        },
     },
     db::StorageInterface,
     routes::AppState,
@@ -22,7 +25,7 @@ use crate::{
         domain,
         storage::{self, enums, payment_attempt::PaymentAttemptExt, ConnectorResponseExt},
     },
-    utils::OptionExt,
+    utils::{self, OptionExt},
 };
 
 #[derive(Debug, Clone, Copy, router_derive::PaymentOperation)]
@@ -55,10 +58,15 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
@@ -108,16 +116,54 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 .await
                 .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
-            let capture = db
-                .insert_capture(
-                    payment_attempt
-                        .make_new_capture(amount_to_capture, enums::CaptureStatus::Started)?,
+            let new_capture = payment_attempt
+                .make_new_capture(amount_to_capture, enums::CaptureStatus::Started)?;
+
+            // Another capture of the attempt could have been made since the attempt was read,
+            // the amount is reserved only if it is still capturable
+            payment_attempt.amount_capturable = db
+                .decrement_amount_capturable_with_attempt_id(
+                    payment_attempt.clone(),
+                    amount_to_capture,
                     storage_scheme,
                 )
                 .await
-                .to_not_found_response(errors::ApiErrorResponse::DuplicatePayment {
-                    payment_id: payment_id.clone(),
-                })?;
+                .map_err(|error| {
+                    let api_error = match error.current_context() {
+                        data_models::errors::StorageError::ValueNotFound(_) => {
+                            errors::ApiErrorResponse::InvalidRequestData {
+                                message: "amount_to_capture is greater than the amount capturable"
+                                    .to_string(),
+                            }
+                        }
+                        // Another capture of the attempt is reserving its amount
+                        data_models::errors::StorageError::DuplicateValue { .. } => {
+                            errors::ApiErrorResponse::ResourceBusy
+                        }
+                        _ => errors::ApiErrorResponse::InternalServerError,
+                    };
+                    error.change_context(api_error)
+                })?
+                .amount_capturable;
+
+            let capture = match db.insert_capture(new_capture, storage_scheme).await {
+                Ok(capture) => capture,
+                Err(error) => {
+                    // The capture is not made, so the amount reserved for it is capturable again
+                    db.increment_amount_capturable_with_attempt_id(
+                        payment_attempt.clone(),
+                        amount_to_capture,
+                        storage_scheme,
+                    )
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+                    return Err(error).to_not_found_response(
+                        errors::ApiErrorResponse::DuplicatePayment {
+                            payment_id: payment_id.to_string(),
+                        },
+                    );
+                }
+            };
             let new_connector_response = db
                 .insert_connector_response(
                     ConnectorResponse::make_new_connector_response(
@@ -129,7 +175,9 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                     storage_scheme,
                 )
                 .await
-                .to_not_found_response(errors::ApiErrorResponse::DuplicatePayment { payment_id })?;
+                .to_not_found_response(errors::ApiErrorResponse::DuplicatePayment {
+                    payment_id: payment_id.to_string(),
+                })?;
             (
                 Some(MultipleCaptureData::new_for_create(
                     previous_captures,
@@ -154,6 +202,56 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         amount = payment_attempt.amount.into();
 
+        if let Some(dcc_offer) = &request.dcc_offer {
+            utils::when(
+                capture_method == enums::CaptureMethod::ManualMultiple,
+                || {
+                    Err(report!(errors::ApiErrorResponse::NotImplemented {
+                        message: errors::api_error_response::NotImplementedMessage::Reason(
+                            "Dynamic currency conversion for multiple partial captures".to_string(),
+                        ),
+                    }))
+                },
+            )?;
+
+            let connector = payment_attempt
+                .connector
+                .as_deref()
+                .get_required_value("connector")?;
+            let connector = api_models::enums::Connector::from_str(connector)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!("unable to parse connector name {connector:?}")
+                })?;
+            let dcc_details = helpers::get_dcc_details_for_capture(
+                dcc_offer,
+                &connector,
+                &state.conf.dcc_supported_connectors,
+                currency,
+                request
+                    .amount_to_capture
+                    .or(payment_attempt.amount_to_capture)
+                    .unwrap_or(payment_attempt.amount),
+                state.conf.amount_rounding.mode,
+            )?;
+            let dcc_details =
+                utils::Encode::<api_models::payments::DccDetails>::encode_to_value(&dcc_details)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to serialize dcc details")?;
+
+            payment_attempt = db
+                .update_payment_attempt_with_attempt_id(
+                    payment_attempt,
+                    storage::PaymentAttemptUpdate::DccDetailsUpdate {
+                        dcc_details: Some(dcc_details),
+                    },
+                    storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+        }
+
         let shipping_address = helpers::create_or_find_address_for_payment_by_request(
             db,
             None,
@@ -196,6 +294,8 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             .await
             .transpose()?;
 
+        let used_saved_method = payment_attempt.used_saved_method.unwrap_or(false);
+
         Ok((
             Box::new(self),
             payments::PaymentData {
@@ -204,6 +304,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 payment_attempt,
                 currency,
                 force_sync: None,
+                force_refresh: false,
                 amount,
                 email: None,
                 mandate_id: None,
@@ -232,6 +333,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             None,
         ))
@@ -302,3 +407,122 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::configs::settings::{DccSupportedConnectors, RoundingMode};
+
+    #[test]
+    fn test_dcc_capture() {
+        let dcc_supported_connectors = DccSupportedConnectors {
+            connector_list: std::collections::HashSet::from([api_models::enums::Connector::Adyen]),
+        };
+        let dcc_offer = api_models::payments::DccOffer {
+            target_currency: api_models::enums::Currency::EUR,
+            exchange_rate: 0.92,
+        };
+
+        let dcc_details = helpers::get_dcc_details_for_capture(
+            &dcc_offer,
+            &api_models::enums::Connector::Adyen,
+            &dcc_supported_connectors,
+            api_models::enums::Currency::USD,
+            1050,
+            RoundingMode::HalfUp,
+        )
+        .unwrap();
+        assert_eq!(
+            dcc_details,
+            api_models::payments::DccDetails {
+                exchange_rate: 0.92,
+                original_currency: api_models::enums::Currency::USD,
+                original_amount: 1050,
+                target_currency: api_models::enums::Currency::EUR,
+                converted_amount: 966,
+            }
+        );
+
+        // Zero decimal target currency, 10.50 USD at 149.5 is 1569.75 JPY
+        let jpy_dcc_details = helpers::get_dcc_details_for_capture(
+            &api_models::payments::DccOffer {
+                target_currency: api_models::enums::Currency::JPY,
+                exchange_rate: 149.5,
+            },
+            &api_models::enums::Connector::Adyen,
+            &dcc_supported_connectors,
+            api_models::enums::Currency::USD,
+            1050,
+            RoundingMode::HalfUp,
+        )
+        .unwrap();
+        assert_eq!(jpy_dcc_details.converted_amount, 1570);
+
+        // Zero decimal original currency, 1000 JPY at 0.0067 is 6.70 USD
+        let usd_dcc_details = helpers::get_dcc_details_for_capture(
+            &api_models::payments::DccOffer {
+                target_currency: api_models::enums::Currency::USD,
+                exchange_rate: 0.0067,
+            },
+            &api_models::enums::Connector::Adyen,
+            &dcc_supported_connectors,
+            api_models::enums::Currency::JPY,
+            1000,
+            RoundingMode::HalfUp,
+        )
+        .unwrap();
+        assert_eq!(usd_dcc_details.converted_amount, 670);
+
+        // Three decimal target currency, 10.50 USD at 0.3075 is 3.22875 KWD
+        let kwd_dcc_offer = api_models::payments::DccOffer {
+            target_currency: api_models::enums::Currency::KWD,
+            exchange_rate: 0.3075,
+        };
+        let kwd_dcc_details = helpers::get_dcc_details_for_capture(
+            &kwd_dcc_offer,
+            &api_models::enums::Connector::Adyen,
+            &dcc_supported_connectors,
+            api_models::enums::Currency::USD,
+            1050,
+            RoundingMode::HalfUp,
+        )
+        .unwrap();
+        assert_eq!(kwd_dcc_details.converted_amount, 3229);
+
+        // 10.00 USD at 0.30725 is exactly halfway between 3.072 and 3.073 KWD
+        let halfway_dcc_offer = api_models::payments::DccOffer {
+            exchange_rate: 0.30725,
+            ..kwd_dcc_offer
+        };
+        for (rounding_mode, converted_amount) in
+            [(RoundingMode::HalfUp, 3073), (RoundingMode::HalfEven, 3072)]
+        {
+            let dcc_details = helpers::get_dcc_details_for_capture(
+                &halfway_dcc_offer,
+                &api_models::enums::Connector::Adyen,
+                &dcc_supported_connectors,
+                api_models::enums::Currency::USD,
+                1000,
+                rounding_mode,
+            )
+            .unwrap();
+            assert_eq!(dcc_details.converted_amount, converted_amount);
+        }
+
+        assert!(matches!(
+            helpers::get_dcc_details_for_capture(
+                &dcc_offer,
+                &api_models::enums::Connector::Stripe,
+                &dcc_supported_connectors,
+                api_models::enums::Currency::USD,
+                1050,
+                RoundingMode::HalfUp,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::NotImplemented {
+                message: errors::api_error_response::NotImplementedMessage::Reason(message)
+            }) if message == "Dynamic currency conversion for stripe"
+        ));
+    }
+}
@@ -11,7 +11,10 @@ use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress,
+            PaymentData,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -56,10 +59,15 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
         payment_intent.setup_future_usage = request
@@ -213,6 +221,8 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         // The operation merges mandate data from both request and payment_attempt
         let setup_mandate = setup_mandate.map(Into::into);
 
+        let used_saved_method = payment_attempt.used_saved_method.unwrap_or(false);
+
         Ok((
             Box::new(self),
             PaymentData {
@@ -234,6 +244,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: request.confirm,
                 payment_method_data: request.payment_method_data.clone(),
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -249,6 +260,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -378,11 +393,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
         };
 
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-                field_name: "merchant_id".to_string(),
-                expected_format: "merchant_id from merchant account".to_string(),
-            })?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         helpers::validate_payment_method_fields_present(request)?;
 
@@ -13,7 +13,10 @@ use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress,
+            PaymentData,
+        },
     },
     db::StorageInterface,
     routes::AppState,
@@ -56,12 +59,17 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         // Stage 1
 
         let payment_intent_fut = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .map(|x| x.change_context(errors::ApiErrorResponse::PaymentNotFound));
 
         let mandate_details_fut = helpers::get_token_pm_type_mandate_details(
@@ -75,6 +83,14 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             futures::try_join!(payment_intent_fut, mandate_details_fut)?;
 
         helpers::validate_customer_access(&payment_intent, auth_flow, request)?;
+        helpers::validate_client_secret_for_confirm(auth_flow, request)?;
+
+        helpers::validate_capture_method_override(&payment_intent.status, request.capture_method)?;
+
+        helpers::validate_intent_status_for_confirm(
+            &payment_intent.status,
+            request.retry_action.as_ref(),
+        )?;
 
         helpers::validate_payment_status_against_not_allowed_statuses(
             &payment_intent.status,
@@ -212,6 +228,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                             payment_attempt,
                             db,
                             storage_scheme,
+                            &state.conf.connector_request_reference_id_config,
                         )
                         .await?;
 
@@ -247,6 +264,21 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             mandate_connector,
         ) = mandate_details;
 
+        helpers::validate_browser_info_for_three_ds(
+            request
+                .authentication_type
+                .or(payment_attempt.authentication_type),
+            request.payment_method.or(payment_attempt.payment_method),
+            request.payment_method_data.as_ref(),
+            helpers::get_browser_information(
+                request
+                    .browser_info
+                    .as_ref()
+                    .or(payment_attempt.browser_info.as_ref()),
+            )?
+            .as_ref(),
+        )?;
+
         let browser_info = request
             .browser_info
             .clone()
@@ -257,6 +289,17 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 field_name: "browser_info",
             })?;
 
+        let sdk_information = request
+            .sdk_information
+            .as_ref()
+            .map(Encode::<api_models::payments::SdkInfo>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sdk_information",
+            })?
+            .map(masking::Secret::new)
+            .or(payment_attempt.sdk_information);
+
         helpers::validate_card_data(request.payment_method_data.clone())?;
 
         let token = token.or_else(|| payment_attempt.payment_token.clone());
@@ -271,6 +314,17 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         payment_attempt.payment_method = payment_method.or(payment_attempt.payment_method);
         payment_attempt.browser_info = browser_info;
+        payment_attempt.sdk_information = sdk_information;
+        payment_attempt.payment_channel =
+            request.payment_channel.or(payment_attempt.payment_channel);
+        let installments = helpers::get_installments(request.installments, &payment_attempt)?;
+        payment_attempt.installments = installments
+            .as_ref()
+            .map(Encode::<api_models::payments::InstallmentData>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "installments",
+            })?;
         payment_attempt.payment_method_type =
             payment_method_type.or(payment_attempt.payment_method_type);
 
@@ -278,7 +332,30 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             .payment_experience
             .or(payment_attempt.payment_experience);
 
-        payment_attempt.capture_method = request.capture_method.or(payment_attempt.capture_method);
+        payment_attempt.capture_method = helpers::get_capture_method_for_confirm(
+            payment_attempt.amount,
+            payment_attempt.amount_to_capture,
+            request.capture_method,
+            payment_attempt.capture_method,
+        )?;
+        payment_attempt.capture_on =
+            request
+                .capture_on
+                .or(payment_attempt.capture_on)
+                .map(|capture_on| {
+                    helpers::normalize_capture_on(
+                        capture_on,
+                        request.connector.as_deref(),
+                        &state.conf.date_only_capture_on_connectors,
+                    )
+                });
+
+        if let Some(api::PaymentMethodData::Card(card)) = &request.payment_method_data {
+            payment_attempt.payment_method_fingerprint = Some(helpers::generate_card_fingerprint(
+                &state.conf.card_fingerprint,
+                card,
+            )?);
+        }
 
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.amount.into();
@@ -306,6 +383,18 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             .map(|a| a.to_string())
             .or(payment_intent.return_url);
 
+        helpers::validate_return_url_for_redirection(
+            request
+                .authentication_type
+                .or(payment_attempt.authentication_type),
+            payment_attempt.payment_method,
+            payment_attempt.payment_experience,
+            payment_intent
+                .return_url
+                .as_ref()
+                .or(merchant_account.return_url.as_ref()),
+        )?;
+
         payment_intent.allowed_payment_method_types = request
             .get_allowed_payment_method_types_as_value()
             .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -335,6 +424,13 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             sm
         });
 
+        let used_saved_method = helpers::is_saved_payment_method_used(
+            &request.payment_method_data,
+            &token,
+            &request.mandate_id,
+        );
+        payment_attempt.used_saved_method = Some(used_saved_method);
+
         Ok((
             Box::new(self),
             PaymentData {
@@ -356,6 +452,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: request.confirm,
                 payment_method_data: request.payment_method_data.clone(),
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -371,6 +468,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method,
+                installments,
+                risk_data: request.risk_data.clone(),
+                next_sync_at: None,
             },
             Some(customer_details),
         ))
@@ -541,9 +642,23 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
             .payment_intent
             .statement_descriptor_suffix
             .take();
+        let statement_descriptor_city =
+            payment_data.payment_intent.statement_descriptor_city.take();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
+        // The connector is appended to the routing trail when the payment is routed
+        let connector_routing_trail = payment_data.payment_intent.connector_routing_trail.clone();
         let authorized_amount = payment_data.payment_attempt.amount;
+        let capture_method = payment_data.payment_attempt.capture_method;
+        let capture_on = payment_data.payment_attempt.capture_on;
+        let payment_method_fingerprint = payment_data
+            .payment_attempt
+            .payment_method_fingerprint
+            .clone();
+        let sdk_information = payment_data.payment_attempt.sdk_information.clone();
+        let payment_channel = payment_data.payment_attempt.payment_channel;
+        let used_saved_method = payment_data.payment_attempt.used_saved_method;
+        let installments = payment_data.payment_attempt.installments.clone();
         let payment_attempt_fut = db
             .update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt,
@@ -564,32 +679,51 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
                     error_code,
                     error_message,
                     amount_capturable: Some(authorized_amount),
+                    capture_method,
+                    capture_on,
+                    payment_method_fingerprint,
+                    sdk_information,
+                    payment_channel,
+                    used_saved_method,
+                    installments,
                 },
                 storage_scheme,
             )
             .map(|x| x.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound));
 
+        let payment_intent_update = storage::PaymentIntentUpdate::Update {
+            amount: payment_data.amount.into(),
+            currency: payment_data.currency,
+            setup_future_usage,
+            status: intent_status,
+            customer_id,
+            shipping_address_id: shipping_address,
+            billing_address_id: billing_address,
+            return_url,
+            business_country,
+            business_label,
+            description,
+            statement_descriptor_name,
+            statement_descriptor_suffix,
+            order_details,
+            metadata,
+            payment_confirm_source: header_payload.payment_confirm_source,
+            connector_routing_trail,
+            statement_descriptor_city,
+            shipping_amount: payment_data.payment_intent.shipping_amount,
+            handling_amount: payment_data.payment_intent.handling_amount,
+            success_url: payment_data.payment_intent.success_url.clone(),
+            failure_url: payment_data.payment_intent.failure_url.clone(),
+        };
+        helpers::validate_intent_status_transition(
+            payment_data.payment_intent.status,
+            &payment_intent_update,
+        )?;
+
         let payment_intent_fut = db
             .update_payment_intent(
                 payment_data.payment_intent,
-                storage::PaymentIntentUpdate::Update {
-                    amount: payment_data.amount.into(),
-                    currency: payment_data.currency,
-                    setup_future_usage,
-                    status: intent_status,
-                    customer_id,
-                    shipping_address_id: shipping_address,
-                    billing_address_id: billing_address,
-                    return_url,
-                    business_country,
-                    business_label,
-                    description,
-                    statement_descriptor_name,
-                    statement_descriptor_suffix,
-                    order_details,
-                    metadata,
-                    payment_confirm_source: header_payload.payment_confirm_source,
-                },
+                payment_intent_update,
                 storage_scheme,
             )
             .map(|x| x.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound));
@@ -641,16 +775,30 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
         };
 
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-                field_name: "merchant_id".to_string(),
-                expected_format: "merchant_id from merchant account".to_string(),
-            })?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         helpers::validate_payment_method_fields_present(request)?;
 
+        helpers::validate_risk_data(request.risk_data.as_ref())?;
+
+        helpers::validate_metadata_keys(request.metadata.as_ref())?;
+
+        helpers::validate_sdk_information(request.sdk_information.as_ref())?;
+
+        helpers::validate_setup_future_usage_and_off_session(
+            request.setup_future_usage,
+            request.off_session,
+        )?;
+
         let mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
+
+        helpers::validate_authentication_type_for_mit(
+            request.authentication_type,
+            request.off_session,
+            mandate_type.as_ref(),
+        )?;
+
         let payment_id =
             crate::core::utils::get_or_generate_id("payment_id", &given_payment_id, "pay")?;
 
@@ -669,3 +817,326 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::configs::settings::CardFingerprint;
+
+    #[test]
+    fn test_amount_to_capture_is_validated_against_the_capture_method_overridden_at_confirm() {
+        use api_models::enums::CaptureMethod;
+
+        // Overriding a manual capture with automatic capture at confirm is rejected if only a
+        // part of the amount is to be captured
+        assert!(helpers::get_capture_method_for_confirm(
+            6540,
+            Some(100),
+            Some(CaptureMethod::Automatic),
+            Some(CaptureMethod::Manual)
+        )
+        .is_err());
+        assert_eq!(
+            helpers::get_capture_method_for_confirm(
+                6540,
+                Some(6540),
+                Some(CaptureMethod::Automatic),
+                Some(CaptureMethod::Manual)
+            )
+            .unwrap(),
+            Some(CaptureMethod::Automatic)
+        );
+        // A partial amount_to_capture of a payment created without a capture_method is allowed
+        // once the capture_method is overridden with manual capture at confirm
+        assert_eq!(
+            helpers::get_capture_method_for_confirm(
+                6540,
+                Some(100),
+                Some(CaptureMethod::Manual),
+                None
+            )
+            .unwrap(),
+            Some(CaptureMethod::Manual)
+        );
+        assert!(helpers::get_capture_method_for_confirm(6540, Some(100), None, None).is_err());
+        assert_eq!(
+            helpers::get_capture_method_for_confirm(
+                6540,
+                Some(100),
+                None,
+                Some(CaptureMethod::Manual)
+            )
+            .unwrap(),
+            Some(CaptureMethod::Manual)
+        );
+    }
+
+    #[test]
+    fn test_three_ds_card_payment_without_browser_info() {
+        use api_models::enums::{AuthenticationType, PaymentMethod};
+
+        let result = helpers::validate_browser_info_for_three_ds(
+            Some(AuthenticationType::ThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            None,
+        );
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::MissingRequiredFields { field_names })
+                if field_names.contains(&"browser_info.user_agent")
+                    && field_names.contains(&"browser_info.java_script_enabled")
+        ));
+
+        // The screen details are only required when javascript is enabled in the browser
+        let browser_info_without_javascript = helpers::get_browser_information(Some(&serde_json::json!({
+            "user_agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/70.0.3538.110 Safari/537.36",
+            "accept_header": "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,image/apng,*/*;q=0.8",
+            "language": "nl-NL",
+            "java_script_enabled": false
+        })))
+        .unwrap()
+        .unwrap();
+        assert!(helpers::validate_browser_info_for_three_ds(
+            Some(AuthenticationType::ThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            Some(&browser_info_without_javascript),
+        )
+        .is_ok());
+        let browser_info_without_screen_details = crate::types::BrowserInformation {
+            java_script_enabled: Some(true),
+            ..browser_info_without_javascript
+        };
+        assert!(matches!(
+            helpers::validate_browser_info_for_three_ds(
+                Some(AuthenticationType::ThreeDs),
+                Some(PaymentMethod::Card),
+                None,
+                Some(&browser_info_without_screen_details),
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::MissingRequiredFields { field_names })
+                if field_names.contains(&"browser_info.screen_width")
+                    && !field_names.contains(&"browser_info.user_agent")
+        ));
+
+        let browser_info = helpers::get_browser_information(Some(&serde_json::json!({
+            "user_agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/70.0.3538.110 Safari/537.36",
+            "accept_header": "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,image/apng,*/*;q=0.8",
+            "language": "nl-NL",
+            "color_depth": 24,
+            "screen_height": 723,
+            "screen_width": 1536,
+            "time_zone": 0,
+            "java_enabled": true,
+            "java_script_enabled": true
+        })))
+        .unwrap();
+        assert!(helpers::validate_browser_info_for_three_ds(
+            Some(AuthenticationType::ThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            browser_info.as_ref(),
+        )
+        .is_ok());
+        assert!(helpers::validate_browser_info_for_three_ds(
+            Some(AuthenticationType::NoThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_three_ds_is_rejected_for_off_session_mit() {
+        use api_models::enums::AuthenticationType;
+
+        let recurring_mandate = api::MandateTransactionType::RecurringMandateTransaction;
+        assert!(matches!(
+            helpers::validate_authentication_type_for_mit(
+                Some(AuthenticationType::ThreeDs),
+                Some(true),
+                Some(&recurring_mandate),
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+        assert!(helpers::validate_authentication_type_for_mit(
+            Some(AuthenticationType::NoThreeDs),
+            Some(true),
+            Some(&recurring_mandate),
+        )
+        .is_ok());
+        assert!(helpers::validate_authentication_type_for_mit(
+            Some(AuthenticationType::ThreeDs),
+            Some(true),
+            Some(&api::MandateTransactionType::NewMandateTransaction),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_identical_cards_have_identical_fingerprints() {
+        let card_fingerprint = CardFingerprint {
+            hash_key: masking::Secret::new("fingerprint_hash_key".to_string()),
+        };
+        let card = api::Card {
+            card_number: "4242424242424242".parse().unwrap(),
+            card_exp_month: masking::Secret::new("10".to_string()),
+            card_exp_year: masking::Secret::new("2030".to_string()),
+            card_holder_name: masking::Secret::new("John Doe".to_string()),
+            ..Default::default()
+        };
+        let same_card = api::Card {
+            card_holder_name: masking::Secret::new("Jane Doe".to_string()),
+            ..card.clone()
+        };
+        let other_card = api::Card {
+            card_number: "4111111111111111".parse().unwrap(),
+            ..card.clone()
+        };
+
+        let fingerprint = helpers::generate_card_fingerprint(&card_fingerprint, &card).unwrap();
+        assert_eq!(
+            fingerprint,
+            helpers::generate_card_fingerprint(&card_fingerprint, &same_card).unwrap()
+        );
+        assert_ne!(
+            fingerprint,
+            helpers::generate_card_fingerprint(&card_fingerprint, &other_card).unwrap()
+        );
+        assert!(!fingerprint.contains("4242424242424242"));
+    }
+
+    #[test]
+    fn test_three_ds_payment_without_return_url() {
+        use api_models::enums::{AuthenticationType, PaymentMethod};
+
+        let result = helpers::validate_return_url_for_redirection(
+            Some(AuthenticationType::ThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            None,
+        );
+        assert!(matches!(
+            result.map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "return_url"
+            })
+        ));
+        assert!(helpers::validate_return_url_for_redirection(
+            Some(AuthenticationType::NoThreeDs),
+            Some(PaymentMethod::BankRedirect),
+            None,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_three_ds_payment_with_return_url() {
+        use api_models::enums::{AuthenticationType, PaymentMethod};
+
+        assert!(helpers::validate_return_url_for_redirection(
+            Some(AuthenticationType::ThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            Some(&"https://hyperswitch.io".to_string()),
+        )
+        .is_ok());
+        assert!(helpers::validate_return_url_for_redirection(
+            Some(AuthenticationType::NoThreeDs),
+            Some(PaymentMethod::Card),
+            None,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_confirm_is_rejected_for_processed_payments() {
+        for intent_status in [
+            api_models::enums::IntentStatus::Processing,
+            api_models::enums::IntentStatus::Succeeded,
+            api_models::enums::IntentStatus::Failed,
+        ] {
+            assert!(matches!(
+                helpers::validate_intent_status_for_confirm(&intent_status, None)
+                    .map_err(|err| err.current_context().clone()),
+                Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                    if message.contains(&intent_status.to_string())
+            ));
+        }
+        assert!(helpers::validate_intent_status_for_confirm(
+            &api_models::enums::IntentStatus::RequiresConfirmation,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_manual_retry_of_failed_payment_is_confirmed() {
+        assert!(helpers::validate_intent_status_for_confirm(
+            &api_models::enums::IntentStatus::Failed,
+            Some(&api_models::enums::RetryAction::ManualRetry),
+        )
+        .is_ok());
+        // Only failed payments can be confirmed again through a manual retry
+        assert!(matches!(
+            helpers::validate_intent_status_for_confirm(
+                &api_models::enums::IntentStatus::Succeeded,
+                Some(&api_models::enums::RetryAction::ManualRetry),
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_capture_method_override_before_authorization() {
+        for intent_status in [
+            api_models::enums::IntentStatus::RequiresPaymentMethod,
+            api_models::enums::IntentStatus::RequiresConfirmation,
+        ] {
+            assert!(helpers::validate_capture_method_override(
+                &intent_status,
+                Some(api_models::enums::CaptureMethod::Manual),
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_capture_method_override_after_authorization_is_rejected() {
+        assert!(matches!(
+            helpers::validate_capture_method_override(
+                &api_models::enums::IntentStatus::RequiresCapture,
+                Some(api_models::enums::CaptureMethod::Automatic),
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { message })
+                if message.contains("capture_method")
+        ));
+        assert!(helpers::validate_capture_method_override(
+            &api_models::enums::IntentStatus::RequiresCapture,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_confirm_is_rejected_when_customer_action_is_pending() {
+        assert!(matches!(
+            helpers::validate_intent_status_for_confirm(
+                &api_models::enums::IntentStatus::RequiresCustomerAction,
+                None,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                if message.contains("requires_customer_action") && message.contains("next_action")
+        ));
+    }
+}
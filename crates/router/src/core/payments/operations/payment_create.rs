@@ -15,7 +15,10 @@ use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress,
+            PaymentData,
+        },
         utils::{self as core_utils},
     },
     db::StorageInterface,
@@ -68,14 +71,15 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .and_then(PaymentId::try_from)?;
 
         let payment_link_data = if let Some(payment_link_object) = &request.payment_link_object {
             create_payment_link(
                 request,
                 payment_link_object.clone(),
                 merchant_id.clone(),
-                payment_id.clone(),
+                &payment_id,
                 db,
                 state,
                 amount,
@@ -112,6 +116,16 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let customer_details = helpers::get_customer_details_from_request(request);
 
+        helpers::validate_open_payment_intents_limit(
+            db,
+            &state.conf.open_payment_intents_limit,
+            customer_details.customer_id.as_ref(),
+            merchant_id,
+            merchant_account.intent_fulfillment_time,
+            storage_scheme,
+        )
+        .await?;
+
         let shipping_address = helpers::create_or_find_address_for_payment_by_request(
             db,
             request.shipping.as_ref(),
@@ -119,7 +133,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             merchant_id,
             customer_details.customer_id.as_ref(),
             merchant_key_store,
-            &payment_id,
+            payment_id.get_string_repr(),
             merchant_account.storage_scheme,
         )
         .await?;
@@ -131,7 +145,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             merchant_id,
             customer_details.customer_id.as_ref(),
             merchant_key_store,
-            &payment_id,
+            payment_id.get_string_repr(),
             merchant_account.storage_scheme,
         )
         .await?;
@@ -147,14 +161,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 field_name: "browser_info",
             })?;
 
-        let attempt_id = if core_utils::is_merchant_enabled_for_payment_id_as_connector_request_id(
-            &state.conf,
-            merchant_id,
-        ) {
-            payment_id.to_string()
-        } else {
-            utils::get_payment_attempt_id(payment_id.clone(), 1)
-        };
+        let attempt_id = Self::get_attempt_id(&payment_id, merchant_id, state)?;
 
         let payment_intent_new = Self::make_payment_intent(
             &payment_id,
@@ -164,35 +171,45 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             shipping_address.clone().map(|x| x.address_id),
             payment_link_data.clone(),
             billing_address.clone().map(|x| x.address_id),
-            attempt_id,
+            attempt_id.clone(),
             state,
         )
         .await?;
 
-        let payment_attempt_new = Self::make_payment_attempt(
-            &payment_id,
-            merchant_id,
-            money,
-            payment_method,
-            payment_method_type,
-            request,
-            browser_info,
-            state,
-        )
-        .await?;
+        let used_saved_method = helpers::is_saved_payment_method_used(
+            &request.payment_method_data,
+            &token,
+            &request.mandate_id,
+        );
+
+        let payment_attempt_new = storage::PaymentAttemptNew {
+            used_saved_method: Some(used_saved_method),
+            ..Self::make_payment_attempt(
+                &payment_id,
+                merchant_id,
+                money,
+                payment_method,
+                payment_method_type,
+                request,
+                browser_info,
+                attempt_id,
+                state,
+            )
+            .await?
+        };
 
         payment_intent = db
             .insert_payment_intent(payment_intent_new, storage_scheme)
             .await
             .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
-                payment_id: payment_id.clone(),
+                payment_id: payment_id.to_string(),
             })?;
 
         payment_attempt = db
             .insert_payment_attempt(payment_attempt_new, storage_scheme)
             .await
             .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
-                payment_id: payment_id.clone(),
+                payment_id: payment_id.to_string(),
             })?;
 
         connector_response = db
@@ -202,7 +219,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             )
             .await
             .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
-                payment_id: payment_id.clone(),
+                payment_id: payment_id.to_string(),
             })?;
 
         let mandate_id = request
@@ -299,6 +316,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 disputes: vec![],
                 attempts: None,
                 force_sync: None,
+                force_refresh: false,
                 connector_response,
                 sessions_token: vec![],
                 card_cvc: request.card_cvc.clone(),
@@ -312,6 +330,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data,
+                used_saved_method,
+                installments: request.installments,
+                risk_data: request.risk_data.clone(),
+                next_sync_at: None,
             },
             Some(customer_details),
         ))
@@ -428,6 +450,10 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
             .straight_through_algorithm
             .clone();
         let authorized_amount = payment_data.payment_attempt.amount;
+        let payment_method_fingerprint = payment_data
+            .payment_attempt
+            .payment_method_fingerprint
+            .clone();
 
         payment_data.payment_attempt = db
             .update_payment_attempt_with_attempt_id(
@@ -440,6 +466,7 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
                         true => Some(authorized_amount),
                         false => None,
                     },
+                    payment_method_fingerprint,
                 },
                 storage_scheme,
             )
@@ -448,16 +475,22 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
 
         let customer_id = payment_data.payment_intent.customer_id.clone();
 
+        let payment_intent_update = storage::PaymentIntentUpdate::ReturnUrlUpdate {
+            return_url: None,
+            status,
+            customer_id,
+            shipping_address_id: None,
+            billing_address_id: None,
+        };
+        helpers::validate_intent_status_transition(
+            payment_data.payment_intent.status,
+            &payment_intent_update,
+        )?;
+
         payment_data.payment_intent = db
             .update_payment_intent(
                 payment_data.payment_intent,
-                storage::PaymentIntentUpdate::ReturnUrlUpdate {
-                    return_url: None,
-                    status,
-                    customer_id,
-                    shipping_address_id: None,
-                    billing_address_id: None,
-                },
+                payment_intent_update,
                 storage_scheme,
             )
             .await
@@ -499,8 +532,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
         };
 
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         helpers::validate_request_amount_and_amount_to_capture(
             request.amount,
@@ -511,16 +543,88 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
             expected_format: "amount_to_capture lesser than amount".to_string(),
         })?;
 
+        // The capture_method of a payment which is not confirmed yet can be overridden at
+        // confirm, the amount_to_capture is validated against it when the payment is confirmed
+        if request.confirm.unwrap_or(false) {
+            helpers::validate_amount_to_capture_and_capture_method(
+                request.amount,
+                request.amount_to_capture,
+                request.capture_method,
+            )?;
+        }
+
         helpers::validate_card_data(request.payment_method_data.clone())?;
 
         helpers::validate_payment_method_fields_present(request)?;
 
-        let payment_id = core_utils::get_or_generate_id("payment_id", &given_payment_id, "pay")?;
+        helpers::validate_risk_data(request.risk_data.as_ref())?;
+
+        helpers::validate_metadata_keys(request.metadata.as_ref())?;
+
+        helpers::validate_sdk_information(request.sdk_information.as_ref())?;
+
+        helpers::validate_setup_future_usage_and_off_session(
+            request.setup_future_usage,
+            request.off_session,
+        )?;
+
+        helpers::validate_statement_descriptor_city(request.statement_descriptor_city.as_ref())?;
+
+        helpers::validate_statement_descriptor(
+            "statement_descriptor_name",
+            request.statement_descriptor_name.as_ref(),
+            request.payment_method,
+        )?;
+
+        helpers::validate_statement_descriptor(
+            "statement_descriptor_suffix",
+            request.statement_descriptor_suffix.as_ref(),
+            request.payment_method,
+        )?;
+
+        helpers::validate_additional_amounts(request.amount, request.surcharge_details.as_ref())?;
+
+        helpers::validate_shipping_and_handling_amounts(
+            request.amount,
+            request.shipping_amount,
+            request.handling_amount,
+            request.order_details.as_ref(),
+        )?;
+
+        let payment_id = given_payment_id
+            .map(PaymentId::try_from)
+            .transpose()?
+            .unwrap_or_else(PaymentId::generate);
 
         let mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
 
+        helpers::validate_authentication_type_for_mit(
+            request.authentication_type,
+            request.off_session,
+            mandate_type.as_ref(),
+        )?;
+
         if request.confirm.unwrap_or(false) {
+            helpers::validate_browser_info_for_three_ds(
+                request.authentication_type,
+                request.payment_method,
+                request.payment_method_data.as_ref(),
+                helpers::get_browser_information(request.browser_info.as_ref())?.as_ref(),
+            )?;
+
+            helpers::validate_return_url_for_redirection(
+                request.authentication_type,
+                request.payment_method,
+                request.payment_experience,
+                request
+                    .return_url
+                    .as_ref()
+                    .map(|return_url| return_url.to_string())
+                    .or(merchant_account.return_url.clone())
+                    .as_ref(),
+            )?;
+
             helpers::validate_pm_or_token_given(
                 &request.payment_method,
                 &request.payment_method_data,
@@ -545,7 +649,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
             Box::new(self),
             operations::ValidateResult {
                 merchant_id: &merchant_account.merchant_id,
-                payment_id: api::PaymentIdType::PaymentIntentId(payment_id),
+                payment_id: api::PaymentIdType::PaymentIntentId(payment_id.to_string()),
                 mandate_type,
                 storage_scheme: merchant_account.storage_scheme,
                 requeue: matches!(
@@ -558,16 +662,39 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
 }
 
 impl PaymentCreate {
+    /// Generates the id of the first attempt of the payment, the same id is stored as the active
+    /// attempt of the payment intent.
+    pub fn get_attempt_id(
+        payment_id: &PaymentId,
+        merchant_id: &str,
+        state: &AppState,
+    ) -> RouterResult<String> {
+        let attempt_id = if core_utils::is_merchant_enabled_for_payment_id_as_connector_request_id(
+            &state.conf,
+            merchant_id,
+        ) {
+            payment_id.to_string()
+        } else {
+            utils::get_payment_attempt_id(payment_id, 1)
+        };
+        core_utils::add_merchant_attempt_id_prefix(
+            &state.conf.connector_request_reference_id_config,
+            merchant_id,
+            attempt_id,
+        )
+    }
+
     #[instrument(skip_all)]
     #[allow(clippy::too_many_arguments)]
     pub async fn make_payment_attempt(
-        payment_id: &str,
+        payment_id: &PaymentId,
         merchant_id: &str,
         money: (api::Amount, enums::Currency),
         payment_method: Option<enums::PaymentMethod>,
         payment_method_type: Option<enums::PaymentMethodType>,
         request: &api::PaymentsRequest,
         browser_info: Option<serde_json::Value>,
+        attempt_id: String,
         state: &AppState,
     ) -> RouterResult<storage::PaymentAttemptNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
@@ -587,14 +714,29 @@ impl PaymentCreate {
             .transpose()
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to encode additional pm data")?;
-        let attempt_id = if core_utils::is_merchant_enabled_for_payment_id_as_connector_request_id(
-            &state.conf,
+        let sdk_information = request
+            .sdk_information
+            .as_ref()
+            .map(Encode::<api_models::payments::SdkInfo>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sdk_information",
+            })?
+            .map(masking::Secret::new);
+        let installments = request
+            .installments
+            .as_ref()
+            .map(Encode::<api_models::payments::InstallmentData>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "installments",
+            })?;
+        let connector_request_reference_id = core_utils::generate_connector_request_reference_id(
+            &state.conf.connector_request_reference_id_config,
             merchant_id,
-        ) {
-            payment_id.to_string()
-        } else {
-            utils::get_payment_attempt_id(payment_id, 1)
-        };
+            &payment_id.to_string(),
+            &attempt_id,
+        );
 
         Ok(storage::PaymentAttemptNew {
             payment_id: payment_id.to_string(),
@@ -605,7 +747,13 @@ impl PaymentCreate {
             amount: amount.into(),
             payment_method,
             capture_method: request.capture_method,
-            capture_on: request.capture_on,
+            capture_on: request.capture_on.map(|capture_on| {
+                helpers::normalize_capture_on(
+                    capture_on,
+                    request.connector.as_deref(),
+                    &state.conf.date_only_capture_on_connectors,
+                )
+            }),
             confirm: request.confirm.unwrap_or(false),
             created_at,
             modified_at,
@@ -623,6 +771,10 @@ impl PaymentCreate {
                 .mandate_data
                 .as_ref()
                 .and_then(|inner| inner.mandate_type.clone().map(Into::into)),
+            connector_request_reference_id: Some(connector_request_reference_id),
+            sdk_information,
+            payment_channel: Some(request.payment_channel.unwrap_or_default()),
+            installments,
             ..storage::PaymentAttemptNew::default()
         })
     }
@@ -630,7 +782,7 @@ impl PaymentCreate {
     #[instrument(skip_all)]
     #[allow(clippy::too_many_arguments)]
     async fn make_payment_intent(
-        payment_id: &str,
+        payment_id: &PaymentId,
         merchant_account: &types::domain::MerchantAccount,
         money: (api::Amount, enums::Currency),
         request: &api::PaymentsRequest,
@@ -640,7 +792,8 @@ impl PaymentCreate {
         active_attempt_id: String,
         state: &AppState,
     ) -> RouterResult<storage::PaymentIntentNew> {
-        let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
+        let current_time = common_utils::date_time::now();
+        let created_at @ modified_at @ last_synced = Some(current_time);
         let status =
             helpers::payment_intent_status_fsm(&request.payment_method_data, request.confirm);
         let client_secret =
@@ -680,6 +833,16 @@ impl PaymentCreate {
 
         let payment_link_id = payment_link_data.map(|pl_data| pl_data.payment_link_id);
 
+        // The client secret of payments created for a payment link is valid till the fulfilment
+        // time of the payment link, which is checked against the payment link itself
+        let client_secret_expiry = payment_link_id.is_none().then(|| {
+            current_time.saturating_add(time::Duration::seconds(
+                merchant_account
+                    .intent_fulfillment_time
+                    .unwrap_or(consts::DEFAULT_FULFILLMENT_TIME),
+            ))
+        });
+
         Ok(storage::PaymentIntentNew {
             payment_id: payment_id.to_string(),
             merchant_id: merchant_account.merchant_id.to_string(),
@@ -692,7 +855,7 @@ impl PaymentCreate {
             last_synced,
             client_secret: Some(client_secret),
             setup_future_usage: request.setup_future_usage,
-            off_session: request.off_session,
+            off_session: helpers::get_off_session(request.off_session, request.setup_future_usage),
             return_url: request.return_url.as_ref().map(|a| a.to_string()),
             shipping_address_id,
             billing_address_id,
@@ -714,6 +877,13 @@ impl PaymentCreate {
             merchant_decision: None,
             payment_link_id,
             payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: request.statement_descriptor_city.clone(),
+            shipping_amount: request.shipping_amount,
+            handling_amount: request.handling_amount,
+            client_secret_expiry,
+            success_url: request.success_url.as_ref().map(|url| url.to_string()),
+            failure_url: request.failure_url.as_ref().map(|url| url.to_string()),
         })
     }
 
@@ -731,6 +901,12 @@ impl PaymentCreate {
             connector_transaction_id: None,
             authentication_data: None,
             encoded_data: None,
+            connector_io_log: None,
+            three_ds_version: None,
+            liability_shift: None,
+            authorization_code: None,
+            settled_at: None,
+            challenge_required: None,
         }
     }
 
@@ -773,7 +949,7 @@ async fn create_payment_link(
     request: &api::PaymentsRequest,
     payment_link_object: api_models::payments::PaymentLinkObject,
     merchant_id: String,
-    payment_id: String,
+    payment_id: &PaymentId,
     db: &dyn StorageInterface,
     state: &AppState,
     amount: api::Amount,
@@ -790,11 +966,11 @@ async fn create_payment_link(
         "{}/payment_link/{}/{}",
         domain,
         merchant_id.clone(),
-        payment_id.clone()
+        payment_id
     );
     let payment_link_req = storage::PaymentLinkNew {
         payment_link_id: payment_link_id.clone(),
-        payment_id: payment_id.clone(),
+        payment_id: payment_id.to_string(),
         merchant_id: merchant_id.clone(),
         link_to_pay: payment_link.clone(),
         amount: amount.into(),
@@ -815,3 +991,533 @@ async fn create_payment_link(
         payment_link_id: payment_link_db.payment_link_id,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{
+        configs::settings::{OpenPaymentIntentsLimit, Settings},
+        core::payments::test_utils,
+        db::StorageImpl,
+    };
+
+    #[actix_rt::test]
+    async fn test_active_attempt_of_created_payment_has_merchant_attempt_id_prefix() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.connector_request_reference_id_config
+            .merchant_attempt_id_prefixes
+            .insert("merchant_1".to_string(), "acme".to_string());
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let state = AppState::with_storage(
+            conf,
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let current_time = common_utils::date_time::now();
+
+        state
+            .store
+            .insert_business_profile(storage::business_profile::BusinessProfileNew {
+                profile_id: "pro_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                profile_name: "default".to_string(),
+                created_at: current_time,
+                modified_at: current_time,
+                return_url: None,
+                enable_payment_response_hash: false,
+                payment_response_hash_key: None,
+                redirect_to_merchant_with_http_post: false,
+                webhook_details: None,
+                metadata: None,
+                routing_algorithm: None,
+                intent_fulfillment_time: None,
+                frm_routing_algorithm: None,
+                payout_routing_algorithm: None,
+                is_recon_enabled: false,
+                applepay_verified_domains: None,
+            })
+            .await
+            .unwrap();
+        let merchant_account = domain::MerchantAccount {
+            default_profile: Some("pro_1".to_string()),
+            ..test_utils::get_merchant_account()
+        };
+
+        let payment_id = PaymentId::try_from("pay_1".to_string()).unwrap();
+        let money = (api::Amount::from(1000), enums::Currency::USD);
+        let request = api::PaymentsRequest::default();
+        let attempt_id = PaymentCreate::get_attempt_id(&payment_id, "merchant_1", &state).unwrap();
+        assert_eq!(attempt_id, "acme_pay_1_1");
+
+        let payment_intent_new = PaymentCreate::make_payment_intent(
+            &payment_id,
+            &merchant_account,
+            money,
+            &request,
+            None,
+            None,
+            None,
+            attempt_id.clone(),
+            &state,
+        )
+        .await
+        .unwrap();
+        let payment_attempt_new = PaymentCreate::make_payment_attempt(
+            &payment_id,
+            "merchant_1",
+            money,
+            None,
+            None,
+            &request,
+            None,
+            attempt_id,
+            &state,
+        )
+        .await
+        .unwrap();
+        let payment_intent = state
+            .store
+            .insert_payment_intent(payment_intent_new, storage_scheme)
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_payment_attempt(payment_attempt_new, storage_scheme)
+            .await
+            .unwrap();
+
+        let active_attempt = state
+            .store
+            .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+                &payment_intent.payment_id,
+                &payment_intent.merchant_id,
+                payment_intent.active_attempt.get_id().as_str(),
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        assert_eq!(active_attempt.attempt_id, "acme_pay_1_1");
+        assert_eq!(active_attempt.payment_id, "pay_1");
+    }
+
+    #[test]
+    fn test_off_session_derived_from_setup_future_usage() {
+        use api_models::enums::FutureUsage;
+
+        assert_eq!(
+            helpers::get_off_session(None, Some(FutureUsage::OffSession)),
+            Some(true)
+        );
+        assert_eq!(
+            helpers::get_off_session(None, Some(FutureUsage::OnSession)),
+            Some(false)
+        );
+        assert_eq!(helpers::get_off_session(None, None), None);
+    }
+
+    #[test]
+    fn test_explicit_off_session_is_not_overridden() {
+        use api_models::enums::FutureUsage;
+
+        assert_eq!(
+            helpers::get_off_session(Some(false), Some(FutureUsage::OffSession)),
+            Some(false)
+        );
+        assert_eq!(
+            helpers::get_off_session(Some(true), Some(FutureUsage::OnSession)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_setup_future_usage_consistent_with_off_session() {
+        use api_models::enums::FutureUsage;
+
+        for (setup_future_usage, off_session) in [
+            (Some(FutureUsage::OnSession), Some(false)),
+            (Some(FutureUsage::OnSession), None),
+            (Some(FutureUsage::OffSession), Some(true)),
+            (Some(FutureUsage::OffSession), Some(false)),
+            (None, Some(true)),
+        ] {
+            assert!(helpers::validate_setup_future_usage_and_off_session(
+                setup_future_usage,
+                off_session
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_on_session_future_usage_conflicts_with_off_session() {
+        use api_models::enums::FutureUsage;
+
+        assert!(matches!(
+            helpers::validate_setup_future_usage_and_off_session(
+                Some(FutureUsage::OnSession),
+                Some(true)
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_amount_to_capture_is_rejected_for_automatic_capture() {
+        use api_models::enums::CaptureMethod;
+
+        let amount = Some(api_models::payments::Amount::from(6540));
+        assert!(helpers::validate_amount_to_capture_and_capture_method(
+            amount,
+            Some(100),
+            Some(CaptureMethod::Automatic)
+        )
+        .is_err());
+        assert!(
+            helpers::validate_amount_to_capture_and_capture_method(amount, Some(100), None)
+                .is_err()
+        );
+        assert!(helpers::validate_amount_to_capture_and_capture_method(
+            amount,
+            Some(6540),
+            Some(CaptureMethod::Automatic)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_amount_to_capture_is_allowed_for_manual_capture() {
+        use api_models::enums::CaptureMethod;
+
+        let amount = Some(api_models::payments::Amount::from(6540));
+        assert!(helpers::validate_amount_to_capture_and_capture_method(
+            amount,
+            Some(100),
+            Some(CaptureMethod::Manual)
+        )
+        .is_ok());
+        assert!(helpers::validate_amount_to_capture_and_capture_method(
+            amount,
+            Some(100),
+            Some(CaptureMethod::Scheduled)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_negative_additional_amount_is_rejected() {
+        let amount = Some(api::Amount::Value(std::num::NonZeroI64::new(1000).unwrap()));
+        let surcharge_details = api_models::payments::RequestSurchargeDetails {
+            surcharge_amount: 100,
+            tax_amount: Some(-10),
+        };
+
+        let result = helpers::validate_additional_amounts(amount, Some(&surcharge_details))
+            .map_err(|err| err.current_context().clone());
+        assert!(matches!(
+            result,
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "surcharge_details.tax_amount"
+        ));
+
+        let surcharge_details = api_models::payments::RequestSurchargeDetails {
+            surcharge_amount: 100,
+            tax_amount: Some(10),
+        };
+        assert!(helpers::validate_additional_amounts(amount, Some(&surcharge_details)).is_ok());
+    }
+
+    #[test]
+    fn test_overflowing_additional_amount_is_rejected() {
+        let amount = Some(api::Amount::Value(std::num::NonZeroI64::new(1000).unwrap()));
+        let surcharge_details = api_models::payments::RequestSurchargeDetails {
+            surcharge_amount: i64::MAX,
+            tax_amount: None,
+        };
+
+        let result = helpers::validate_additional_amounts(amount, Some(&surcharge_details))
+            .map_err(|err| err.current_context().clone());
+        assert!(matches!(
+            result,
+            Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                if message.contains("overflows")
+        ));
+
+        let surcharge_details = api_models::payments::RequestSurchargeDetails {
+            surcharge_amount: i64::MAX,
+            tax_amount: Some(1),
+        };
+        let result = helpers::validate_additional_amounts(amount, Some(&surcharge_details))
+            .map_err(|err| err.current_context().clone());
+        assert!(matches!(
+            result,
+            Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                if message.contains("surcharge_details.tax_amount")
+        ));
+    }
+
+    #[test]
+    fn test_metadata_with_reserved_key_is_rejected() {
+        let metadata = |metadata: serde_json::Value| Some(masking::Secret::new(metadata));
+
+        assert!(helpers::validate_metadata_keys(
+            metadata(serde_json::json!({ "udf1": "some-value" })).as_ref()
+        )
+        .is_ok());
+        assert!(helpers::validate_metadata_keys(None).is_ok());
+        for reserved_key in ["hyperswitch_routing", "connector_response"] {
+            assert!(matches!(
+                helpers::validate_metadata_keys(
+                    metadata(serde_json::json!({ "udf1": "some-value", (reserved_key): "value" }))
+                        .as_ref()
+                )
+                .map_err(|err| err.current_context().clone()),
+                Err(errors::ApiErrorResponse::InvalidRequestData { message })
+                    if message.contains(reserved_key)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_statement_descriptor_city_length() {
+        assert!(helpers::validate_statement_descriptor_city(Some(&"Berlin".to_string())).is_ok());
+        assert!(helpers::validate_statement_descriptor_city(None).is_ok());
+        assert!(matches!(
+            helpers::validate_statement_descriptor_city(Some(&"Llanfairpwllgwyngyll".to_string()))
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "statement_descriptor_city"
+        ));
+    }
+
+    #[test]
+    fn test_card_statement_descriptor() {
+        let validate = |descriptor: &str| {
+            helpers::validate_statement_descriptor(
+                "statement_descriptor_suffix",
+                Some(&descriptor.to_string()),
+                Some(api::enums::PaymentMethod::Card),
+            )
+            .map_err(|err| err.current_context().clone())
+        };
+
+        assert!(validate("Shoes order 1234").is_ok());
+        assert!(matches!(
+            validate("Shoes <order>"),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, expected_format })
+                if field_name == "statement_descriptor_suffix"
+                    && expected_format.ends_with("for card payments")
+        ));
+        assert!(validate("12345").is_err());
+        assert!(validate("Payment for shoes purchase").is_err());
+    }
+
+    #[test]
+    fn test_bank_debit_statement_descriptor() {
+        let validate = |descriptor: &str| {
+            helpers::validate_statement_descriptor(
+                "statement_descriptor_name",
+                Some(&descriptor.to_string()),
+                Some(api::enums::PaymentMethod::BankDebit),
+            )
+            .map_err(|err| err.current_context().clone())
+        };
+
+        // Longer than a card descriptor, but within the limit of the remittance reference
+        assert!(validate("Hyperswitch subscription 2023/10").is_ok());
+        assert!(validate("12345").is_ok());
+        assert!(matches!(
+            validate("Shoes & socks"),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, expected_format })
+                if field_name == "statement_descriptor_name"
+                    && expected_format.ends_with("for bank debit payments")
+        ));
+    }
+
+    #[test]
+    fn test_order_total_includes_shipping_and_handling() {
+        let order_details = vec![api_models::payments::OrderDetailsWithAmount {
+            product_name: "shirt".to_string(),
+            quantity: 2,
+            amount: 1000,
+        }];
+
+        assert_eq!(
+            helpers::get_order_total(Some(&order_details), Some(500), Some(200)),
+            Some(2700)
+        );
+        assert!(helpers::validate_shipping_and_handling_amounts(
+            Some(api::Amount::from(2700)),
+            Some(500),
+            Some(200),
+            Some(&order_details),
+        )
+        .is_ok());
+        assert!(matches!(
+            helpers::validate_shipping_and_handling_amounts(
+                Some(api::Amount::from(2000)),
+                Some(500),
+                Some(200),
+                Some(&order_details),
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+        assert!(matches!(
+            helpers::validate_shipping_and_handling_amounts(
+                Some(api::Amount::from(2700)),
+                Some(-500),
+                None,
+                None,
+            )
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                if field_name == "shipping_amount"
+        ));
+    }
+
+    #[test]
+    fn test_open_payment_intents_limit() {
+        let config = OpenPaymentIntentsLimit {
+            enabled: true,
+            max_open_intents_per_customer: 2,
+        };
+
+        assert!(helpers::check_open_payment_intents_limit(&config, 1).is_ok());
+        assert!(matches!(
+            helpers::check_open_payment_intents_limit(&config, 2)
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+        assert!(helpers::check_open_payment_intents_limit(&config, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_open_payment_intents_outside_fulfillment_time_are_not_counted() {
+        use storage_impl::MockDb;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let config = OpenPaymentIntentsLimit {
+            enabled: true,
+            max_open_intents_per_customer: 2,
+        };
+        let customer_id = "customer_1".to_string();
+        let payment_intent = storage::PaymentIntent {
+            customer_id: Some(customer_id.clone()),
+            ..test_utils::get_payment_intent(
+                "pay_open_1",
+                enums::IntentStatus::RequiresPaymentMethod,
+            )
+        };
+        // One open payment within the fulfillment time and one created before it
+        mock_db.payment_intents.lock().await.extend([
+            payment_intent.clone(),
+            storage::PaymentIntent {
+                id: 2,
+                payment_id: "pay_open_2".to_string(),
+                created_at: payment_intent.created_at
+                    - time::Duration::seconds(consts::DEFAULT_FULFILLMENT_TIME + 60),
+                ..payment_intent.clone()
+            },
+        ]);
+
+        assert!(helpers::validate_open_payment_intents_limit(
+            &mock_db,
+            &config,
+            Some(&customer_id),
+            "merchant_1",
+            None,
+            enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .is_ok());
+
+        mock_db
+            .payment_intents
+            .lock()
+            .await
+            .push(storage::PaymentIntent {
+                id: 3,
+                payment_id: "pay_open_3".to_string(),
+                ..payment_intent
+            });
+        assert!(matches!(
+            helpers::validate_open_payment_intents_limit(
+                &mock_db,
+                &config,
+                Some(&customer_id),
+                "merchant_1",
+                None,
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    fn get_sdk_information() -> api_models::payments::SdkInfo {
+        api_models::payments::SdkInfo {
+            sdk_app_id: Some("9063b12c-fcde-43c7-b28e-8d0af5520e8a".to_string()),
+            sdk_enc_data: Some(masking::Secret::new(
+                "eyJhbGciOiJSU0EtT0FFUC0yNTYifQ".to_string(),
+            )),
+            sdk_ephem_pub_key: Some(std::collections::HashMap::from([
+                ("kty".to_string(), "EC".to_string()),
+                ("crv".to_string(), "P-256".to_string()),
+            ])),
+            sdk_trans_id: Some("b60c9879-ac77-4918-a317-7b01c4317053".to_string()),
+            sdk_reference_number: Some("3DS_LOA_SDK_ADBV_020200_00245".to_string()),
+            sdk_max_timeout: Some(60),
+        }
+    }
+
+    #[test]
+    fn test_sdk_information_without_sdk_app_id_is_rejected() {
+        let sdk_information = get_sdk_information();
+        assert!(helpers::validate_sdk_information(Some(&sdk_information)).is_ok());
+        assert!(helpers::validate_sdk_information(None).is_ok());
+
+        let sdk_information = api_models::payments::SdkInfo {
+            sdk_app_id: None,
+            ..sdk_information
+        };
+        assert!(matches!(
+            helpers::validate_sdk_information(Some(&sdk_information))
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "sdk_information.sdk_app_id"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_sdk_max_timeout_outside_the_allowed_range_is_rejected() {
+        let sdk_information = api_models::payments::SdkInfo {
+            sdk_max_timeout: Some(5),
+            ..get_sdk_information()
+        };
+        assert!(helpers::validate_sdk_information(Some(&sdk_information)).is_ok());
+
+        for sdk_max_timeout in [0, 4, 100] {
+            let sdk_information = api_models::payments::SdkInfo {
+                sdk_max_timeout: Some(sdk_max_timeout),
+                ..sdk_information.clone()
+            };
+            assert!(matches!(
+                helpers::validate_sdk_information(Some(&sdk_information))
+                    .map_err(|err| err.current_context().clone()),
+                Err(errors::ApiErrorResponse::InvalidDataFormat { field_name, .. })
+                    if field_name == "sdk_information.sdk_max_timeout"
+            ));
+        }
+    }
+}
@@ -45,8 +45,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Verify
         operations::ValidateResult<'a>,
     )> {
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         let mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
@@ -104,7 +103,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                     request.payment_method,
                     request,
                     state,
-                ),
+                )?,
                 storage_scheme,
             )
             .await
@@ -183,6 +182,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: Some(true),
                 address: types::PaymentAddress::default(),
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -198,6 +198,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method: false,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             Some(payments::CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -237,16 +241,22 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve> UpdateTracker<F, PaymentData<F>, api:
 
         let customer_id = payment_data.payment_intent.customer_id.clone();
 
+        let payment_intent_update = storage::PaymentIntentUpdate::ReturnUrlUpdate {
+            return_url: None,
+            status,
+            customer_id,
+            shipping_address_id: None,
+            billing_address_id: None,
+        };
+        helpers::validate_intent_status_transition(
+            payment_data.payment_intent.status,
+            &payment_intent_update,
+        )?;
+
         payment_data.payment_intent = db
             .update_payment_intent(
                 payment_data.payment_intent,
-                storage::PaymentIntentUpdate::ReturnUrlUpdate {
-                    return_url: None,
-                    status,
-                    customer_id,
-                    shipping_address_id: None,
-                    billing_address_id: None,
-                },
+                payment_intent_update,
                 storage_scheme,
             )
             .await
@@ -321,7 +331,7 @@ impl PaymentMethodValidate {
         payment_method: Option<api_enums::PaymentMethod>,
         _request: &api::VerifyRequest,
         state: &AppState,
-    ) -> storage::PaymentAttemptNew {
+    ) -> RouterResult<storage::PaymentAttemptNew> {
         let created_at @ modified_at @ last_synced = Some(date_time::now());
         let status = storage_enums::AttemptStatus::Pending;
         let attempt_id = if core_utils::is_merchant_enabled_for_payment_id_as_connector_request_id(
@@ -332,8 +342,13 @@ impl PaymentMethodValidate {
         } else {
             utils::get_payment_attempt_id(payment_id, 1)
         };
+        let attempt_id = core_utils::add_merchant_attempt_id_prefix(
+            &state.conf.connector_request_reference_id_config,
+            merchant_id,
+            attempt_id,
+        )?;
 
-        storage::PaymentAttemptNew {
+        Ok(storage::PaymentAttemptNew {
             payment_id: payment_id.to_string(),
             merchant_id: merchant_id.to_string(),
             attempt_id,
@@ -348,7 +363,7 @@ impl PaymentMethodValidate {
             modified_at,
             last_synced,
             ..Default::default()
-        }
+        })
     }
 
     fn make_payment_intent(
@@ -395,6 +410,13 @@ impl PaymentMethodValidate {
             profile_id: Default::default(),
             merchant_decision: Default::default(),
             payment_confirm_source: Default::default(),
+            connector_routing_trail: Default::default(),
+            statement_descriptor_city: Default::default(),
+            shipping_amount: Default::default(),
+            handling_amount: Default::default(),
+            client_secret_expiry: Default::default(),
+            success_url: Default::default(),
+            failure_url: Default::default(),
             payment_link_id: Default::default(),
         }
     }
@@ -11,7 +11,9 @@ use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress, PaymentData,
+        },
     },
     db::StorageInterface,
     routes::AppState,
@@ -52,10 +54,15 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         let storage_scheme = merchant_account.storage_scheme;
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         let payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
@@ -124,6 +131,8 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             format!("Error while retrieving frm_response, merchant_id: {}, payment_id: {attempt_id}", &merchant_account.merchant_id)
         });
 
+        let used_saved_method = payment_attempt.used_saved_method.unwrap_or(false);
+
         Ok((
             Box::new(self),
             PaymentData {
@@ -144,6 +153,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: None,
                 payment_method_data: None,
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -160,6 +170,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: frm_response.ok(),
                 payment_link_data: None,
+                used_saved_method,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             None,
         ))
@@ -208,6 +222,10 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
             error_message,
         };
 
+        helpers::validate_intent_status_transition(
+            payment_data.payment_intent.status,
+            &intent_status_update,
+        )?;
         payment_data.payment_intent = db
             .update_payment_intent(
                 payment_data.payment_intent,
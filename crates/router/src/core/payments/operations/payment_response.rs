@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use error_stack::ResultExt;
 use futures::FutureExt;
 use router_derive;
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
 
 use super::{Operation, PostUpdateTracker};
 use crate::{
@@ -12,7 +12,7 @@ use crate::{
         errors::{self, RouterResult, StorageErrorExt},
         mandate,
         payment_methods::PaymentMethodRetrieve,
-        payments::{types::MultipleCaptureData, PaymentData},
+        payments::{helpers, types::MultipleCaptureData, PaymentData},
         utils as core_utils,
     },
     db::StorageInterface,
@@ -25,7 +25,7 @@ use crate::{
             payment_attempt::{AttemptStatusExt, PaymentAttemptExt},
         },
         transformers::ForeignTryFrom,
-        CaptureSyncResponse,
+        CaptureSyncResponse, FromConnectorResponse,
     },
     utils,
 };
@@ -296,6 +296,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     router_data: types::RouterData<F, T, types::PaymentsResponseData>,
     storage_scheme: enums::MerchantStorageScheme,
 ) -> RouterResult<PaymentData<F>> {
+    let connector_outcome = types::ConnectorPaymentOutcome::from_connector_response(&router_data);
     let (capture_update, mut payment_attempt_update, connector_response_update) = match router_data
         .response
         .clone()
@@ -320,20 +321,11 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 }
                 None => {
                     let flow_name = core_utils::get_flow_name::<F>()?;
-                    let status =
-                        // mark previous attempt status for technical failures in PSync flow
-                        if flow_name == "PSync" {
-                            match err.status_code {
-                                // marking failure for 2xx because this is genuine payment failure
-                                200..=299 => storage::enums::AttemptStatus::Failure,
-                                _ => payment_data.payment_attempt.status,
-                            }
-                        } else {
-                            match err.status_code {
-                                500..=511 => storage::enums::AttemptStatus::Pending,
-                                _ => storage::enums::AttemptStatus::Failure,
-                            }
-                        };
+                    let status = helpers::get_attempt_status_for_connector_error(
+                        &flow_name,
+                        &err,
+                        payment_data.payment_attempt.status,
+                    );
                     (
                         None,
                         Some(storage::PaymentAttemptUpdate::ErrorUpdate {
@@ -351,6 +343,9 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                             } else {
                                 None
                             },
+                            latency_ms: helpers::get_latency_in_milliseconds(
+                                router_data.external_latency,
+                            ),
                         }),
                     )
                 }
@@ -360,6 +355,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 attempt_update,
                 Some(storage::ConnectorResponseUpdate::ErrorUpdate {
                     connector_name: Some(router_data.connector.clone()),
+                    connector_io_log: router_data.connector_io_log.clone(),
                 }),
             )
         }
@@ -370,12 +366,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 connector_response_reference_id,
                 ..
             } => {
-                let connector_transaction_id = match pre_processing_id.to_owned() {
-                    types::PreprocessingResponseId::PreProcessingId(_) => None,
-                    types::PreprocessingResponseId::ConnectorTransactionId(connector_txn_id) => {
-                        Some(connector_txn_id)
-                    }
-                };
+                let connector_transaction_id = connector_outcome.connector_transaction_id;
                 let preprocessing_step_id = match pre_processing_id {
                     types::PreprocessingResponseId::PreProcessingId(pre_processing_id) => {
                         Some(pre_processing_id)
@@ -394,17 +385,12 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 (None, Some(payment_attempt_update), None)
             }
             types::PaymentsResponseData::TransactionResponse {
-                resource_id,
                 redirection_data,
                 connector_metadata,
                 connector_response_reference_id,
                 ..
             } => {
-                let connector_transaction_id = match resource_id {
-                    types::ResponseId::NoResponseId => None,
-                    types::ResponseId::ConnectorTransactionId(id)
-                    | types::ResponseId::EncodedData(id) => Some(id),
-                };
+                let connector_transaction_id = connector_outcome.connector_transaction_id;
 
                 let encoded_data = payment_data.connector_response.encoded_data.clone();
                 let connector_name = router_data.connector.clone();
@@ -426,6 +412,19 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                     metrics::SUCCESSFUL_PAYMENT.add(&metrics::CONTEXT, 1, &[]);
                 }
 
+                let amount_mismatch = helpers::get_connector_amount_mismatch(
+                    router_data.connector_authorized_amount,
+                    payment_data.amount.into(),
+                );
+                if amount_mismatch == Some(true) {
+                    logger::warn!(
+                        "Connector authorized amount {:?} differs from the payment amount {} for attempt {}",
+                        router_data.connector_authorized_amount,
+                        i64::from(payment_data.amount),
+                        payment_data.payment_attempt.attempt_id
+                    );
+                }
+
                 utils::add_apple_pay_payment_status_metrics(
                     router_data.status,
                     router_data.apple_pay_flow,
@@ -474,6 +473,13 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                                 } else {
                                     None
                                 },
+                                latency_ms: helpers::get_latency_in_milliseconds(
+                                    router_data.external_latency,
+                                ),
+                                connector_amount: router_data.connector_authorized_amount,
+                                amount_mismatch,
+                                avs_result: router_data.avs_result.clone(),
+                                cvv_result: router_data.cvv_result.clone(),
                             }),
                         ),
                     };
@@ -483,6 +489,27 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                     authentication_data,
                     encoded_data,
                     connector_name: Some(connector_name),
+                    connector_io_log: router_data.connector_io_log.clone(),
+                    three_ds_version: router_data
+                        .three_ds_authentication_result
+                        .as_ref()
+                        .and_then(|result| result.three_ds_version.clone()),
+                    liability_shift: router_data
+                        .three_ds_authentication_result
+                        .as_ref()
+                        .and_then(|result| result.liability_shift),
+                    authorization_code: router_data
+                        .connector_response_metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.authorization_code.clone()),
+                    settled_at: router_data
+                        .connector_response_metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.settled_at),
+                    challenge_required: router_data
+                        .three_ds_authentication_result
+                        .as_ref()
+                        .and_then(|result| result.challenge_required),
                 };
 
                 (
@@ -492,15 +519,11 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 )
             }
             types::PaymentsResponseData::TransactionUnresolvedResponse {
-                resource_id,
                 reason,
                 connector_response_reference_id,
+                ..
             } => {
-                let connector_transaction_id = match resource_id {
-                    types::ResponseId::NoResponseId => None,
-                    types::ResponseId::ConnectorTransactionId(id)
-                    | types::ResponseId::EncodedData(id) => Some(id),
-                };
+                let connector_transaction_id = connector_outcome.connector_transaction_id;
                 (
                     None,
                     Some(storage::PaymentAttemptUpdate::UnresolvedResponseUpdate {
@@ -539,28 +562,65 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
             },
         },
     };
+    // The status transition of the payment intent is checked before anything is written. A
+    // response which cannot move the payment intent from its current status is stale, for example
+    // a sync response which arrives after a webhook has already moved the payment forward, and it
+    // is not applied.
+    let attempt_status = get_attempt_status_for_response(
+        &payment_data.payment_attempt,
+        capture_update.as_ref(),
+        payment_attempt_update.as_ref(),
+    );
+    let mut updated_payment_attempt = payment_data.payment_attempt.clone();
+    updated_payment_attempt.status = attempt_status;
+    let intent_status =
+        updated_payment_attempt.get_intent_status(payment_data.payment_intent.amount_captured);
+    if !helpers::is_valid_transition(payment_data.payment_intent.status, intent_status) {
+        logger::warn!(
+            "Ignoring the connector response for attempt {}, the payment intent cannot move from {} to {}",
+            payment_data.payment_attempt.attempt_id,
+            payment_data.payment_intent.status,
+            intent_status
+        );
+        return Ok(payment_data);
+    }
+
     payment_data.multiple_capture_data = match capture_update {
         Some((mut multiple_capture_data, capture_updates)) => {
             for (capture, capture_update) in capture_updates {
+                let was_failed = capture.status == storage::enums::CaptureStatus::Failed;
                 let updated_capture = db
                     .update_capture_with_capture_id(capture, capture_update, storage_scheme)
                     .await
                     .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+                // The amount reserved for a capture which failed is capturable again
+                if !was_failed && updated_capture.status == storage::enums::CaptureStatus::Failed {
+                    payment_data.payment_attempt = db
+                        .increment_amount_capturable_with_attempt_id(
+                            payment_data.payment_attempt.clone(),
+                            updated_capture.amount,
+                            storage_scheme,
+                        )
+                        .await
+                        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+                }
                 multiple_capture_data.update_capture(updated_capture);
             }
-
-            let authorized_amount = payment_data.payment_attempt.get_total_amount();
-
-            payment_attempt_update = Some(storage::PaymentAttemptUpdate::AmountToCaptureUpdate {
-                status: multiple_capture_data.get_attempt_status(authorized_amount),
-                amount_capturable: payment_data.payment_attempt.amount
-                    - multiple_capture_data.get_total_blocked_amount(),
-            });
             Some(multiple_capture_data)
         }
         None => None,
     };
 
+    // The amount capturable of the attempt is updated only when the amount of a capture is
+    // reserved or released, so that concurrent captures do not overwrite each other's reservation
+    if let Some(multiple_capture_data) = &payment_data.multiple_capture_data {
+        let authorized_amount = payment_data.payment_attempt.get_total_amount();
+
+        payment_attempt_update = Some(storage::PaymentAttemptUpdate::StatusUpdate {
+            status: multiple_capture_data.get_attempt_status(authorized_amount),
+        });
+    }
+
     // Stage 1
 
     let payment_attempt = payment_data.payment_attempt.clone();
@@ -603,7 +663,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
         router_data.request,
         router_data.amount_captured,
         router_data.status,
-        &payment_data,
+        payment_data.multiple_capture_data.as_ref(),
     );
     let payment_intent_update = match &router_data.response {
         Err(_) => storage::PaymentIntentUpdate::PGStatusUpdate {
@@ -617,9 +677,12 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 .get_intent_status(payment_data.payment_intent.amount_captured),
             return_url: router_data.return_url.clone(),
             amount_captured,
+            metadata: helpers::get_intent_metadata_with_connector_response(
+                payment_data.payment_intent.metadata.as_ref(),
+                router_data.connector_response_metadata.as_ref(),
+            )?,
         },
     };
-
     let payment_intent_fut = db
         .update_payment_intent(
             payment_data.payment_intent.clone(),
@@ -642,6 +705,30 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     Ok(payment_data)
 }
 
+/// Status of the payment attempt once the updates built from the connector response are applied
+fn get_attempt_status_for_response(
+    payment_attempt: &storage::PaymentAttempt,
+    capture_update: Option<&(
+        MultipleCaptureData,
+        Vec<(storage::Capture, storage::CaptureUpdate)>,
+    )>,
+    payment_attempt_update: Option<&storage::PaymentAttemptUpdate>,
+) -> enums::AttemptStatus {
+    match capture_update {
+        Some((multiple_capture_data, capture_updates)) => {
+            let mut multiple_capture_data = multiple_capture_data.clone();
+            for (capture, capture_update) in capture_updates {
+                multiple_capture_data
+                    .update_capture(capture_update.clone().apply_changeset(capture.clone()));
+            }
+            multiple_capture_data.get_attempt_status(payment_attempt.get_total_amount())
+        }
+        None => payment_attempt_update
+            .and_then(|payment_attempt_update| payment_attempt_update.get_status())
+            .unwrap_or(payment_attempt.status),
+    }
+}
+
 fn response_to_capture_update(
     multiple_capture_data: &MultipleCaptureData,
     response_list: HashMap<String, CaptureSyncResponse>,
@@ -695,13 +782,13 @@ fn get_capture_update_for_unmapped_capture_responses(
     Ok(result)
 }
 
-fn get_total_amount_captured<F: Clone, T: types::Capturable>(
+fn get_total_amount_captured<T: types::Capturable>(
     request: T,
     amount_captured: Option<i64>,
     router_data_status: enums::AttemptStatus,
-    payment_data: &PaymentData<F>,
+    multiple_capture_data: Option<&MultipleCaptureData>,
 ) -> Option<i64> {
-    match &payment_data.multiple_capture_data {
+    match multiple_capture_data {
         Some(multiple_capture_data) => {
             //multiple capture
             Some(multiple_capture_data.get_total_blocked_amount())
@@ -719,3 +806,193 @@ fn get_total_amount_captured<F: Clone, T: types::Capturable>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_success_webhook_for_processing_payment_populates_amount_captured() {
+        // The status of a processing payment is synced with the connector when a webhook is
+        // received, the amount captured is taken from the sync request when the connector does
+        // not return it
+        let sync_request = types::PaymentsSyncData {
+            amount: 1000,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_total_amount_captured(
+                sync_request.clone(),
+                None,
+                enums::AttemptStatus::Charged,
+                None,
+            ),
+            Some(1000)
+        );
+        assert_eq!(
+            get_total_amount_captured(
+                sync_request.clone(),
+                Some(800),
+                enums::AttemptStatus::Charged,
+                None,
+            ),
+            Some(800)
+        );
+        assert_eq!(
+            get_total_amount_captured(sync_request, None, enums::AttemptStatus::Pending, None),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_sync_response_does_not_move_succeeded_payment() {
+        use data_models::payments::payment_attempt::PaymentAttemptInterface;
+
+        let mockdb = storage_impl::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        // A webhook has already moved the payment to succeeded when a sync response, which was
+        // fetched before the payment was captured, reports the attempt as failed
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    status: enums::AttemptStatus::Charged,
+                    amount: 1000,
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        let payment_attempt_update = storage::PaymentAttemptUpdate::ErrorUpdate {
+            connector: None,
+            status: enums::AttemptStatus::Failure,
+            error_code: None,
+            error_message: None,
+            error_reason: None,
+            amount_capturable: Some(0),
+            latency_ms: None,
+        };
+        let attempt_status =
+            get_attempt_status_for_response(&payment_attempt, None, Some(&payment_attempt_update));
+        assert_eq!(attempt_status, enums::AttemptStatus::Failure);
+
+        let mut updated_payment_attempt = payment_attempt.clone();
+        updated_payment_attempt.status = attempt_status;
+        assert!(!helpers::is_valid_transition(
+            enums::IntentStatus::Succeeded,
+            updated_payment_attempt.get_intent_status(Some(1000)),
+        ));
+
+        // Responses which do not update the attempt keep its current status
+        assert_eq!(
+            get_attempt_status_for_response(&payment_attempt, None, None),
+            enums::AttemptStatus::Charged
+        );
+    }
+
+    #[test]
+    fn test_connector_amount_mismatch() {
+        assert_eq!(
+            helpers::get_connector_amount_mismatch(Some(1001), 1000),
+            Some(true)
+        );
+        assert_eq!(
+            helpers::get_connector_amount_mismatch(Some(1000), 1000),
+            Some(false)
+        );
+        assert_eq!(helpers::get_connector_amount_mismatch(None, 1000), None);
+    }
+
+    #[test]
+    fn test_connector_timeout_is_distinguished_from_decline() {
+        let timeout = types::ErrorResponse {
+            code: crate::consts::REQUEST_TIMEOUT_ERROR_CODE.to_string(),
+            message: crate::consts::REQUEST_TIMEOUT_ERROR_MESSAGE.to_string(),
+            reason: None,
+            status_code: 504,
+        };
+        let decline = types::ErrorResponse {
+            code: "card_declined".to_string(),
+            message: "Your card was declined".to_string(),
+            reason: None,
+            status_code: 402,
+        };
+
+        assert!(helpers::is_connector_timeout_error(&timeout));
+        assert!(!helpers::is_connector_timeout_error(&decline));
+        assert_eq!(
+            helpers::get_attempt_status_for_connector_error(
+                "Authorize",
+                &timeout,
+                enums::AttemptStatus::Pending
+            ),
+            enums::AttemptStatus::Pending
+        );
+        assert_eq!(
+            helpers::get_attempt_status_for_connector_error(
+                "Authorize",
+                &decline,
+                enums::AttemptStatus::Pending
+            ),
+            enums::AttemptStatus::Failure
+        );
+        assert_eq!(
+            helpers::get_attempt_status_for_connector_error(
+                "PSync",
+                &timeout,
+                enums::AttemptStatus::Authorized
+            ),
+            enums::AttemptStatus::Authorized
+        );
+    }
+
+    #[test]
+    fn test_connector_auth_code_is_added_to_intent_metadata() {
+        let metadata = common_utils::pii::SecretSerdeValue::new(serde_json::json!({
+            "order_id": "order_1"
+        }));
+        let connector_response_metadata = types::ConnectorResponseMetadata {
+            authorization_code: Some("A1B2C3".to_string()),
+            settled_at: None,
+        };
+
+        let updated_metadata = helpers::get_intent_metadata_with_connector_response(
+            Some(&metadata),
+            Some(&connector_response_metadata),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            masking::PeekInterface::peek(&updated_metadata),
+            &serde_json::json!({
+                "order_id": "order_1",
+                "connector_response": {
+                    "authorization_code": "A1B2C3"
+                }
+            })
+        );
+        assert!(
+            helpers::get_intent_metadata_with_connector_response(Some(&metadata), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_connector_latency_is_recorded() {
+        let (_, latency) = crate::routes::metrics::utils::time_future(tokio::time::sleep(
+            std::time::Duration::from_millis(10),
+        ))
+        .await;
+
+        let latency_ms = helpers::get_latency_in_milliseconds(Some(latency.as_millis())).unwrap();
+        assert!(latency_ms >= 10);
+        assert_eq!(helpers::get_latency_in_milliseconds(None), None);
+    }
+}
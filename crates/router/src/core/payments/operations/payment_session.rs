@@ -12,7 +12,7 @@ use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, PaymentData},
+        payments::{self, helpers, operations, types::PaymentId, PaymentData},
     },
     db::StorageInterface,
     routes::AppState,
@@ -50,14 +50,19 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
     )> {
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
 
         let db = &*state.store;
         let merchant_id = &merchant_account.merchant_id;
         let storage_scheme = merchant_account.storage_scheme;
 
         let mut payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
@@ -186,6 +191,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: None,
                 payment_method_data: None,
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -202,6 +208,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method: false,
+                installments: None,
+                risk_data: None,
+                next_sync_at: None,
             },
             Some(customer_details),
         ))
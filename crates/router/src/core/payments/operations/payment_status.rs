@@ -367,6 +367,9 @@ async fn get_tracker_for_sync<
         })
         .await
         .transpose()?;
+
+    let used_saved_method = payment_attempt.used_saved_method.unwrap_or(false);
+
     Ok((
         Box::new(operation),
         PaymentData {
@@ -396,6 +399,7 @@ async fn get_tracker_for_sync<
                     && (helpers::check_force_psync_precondition(&payment_attempt.status)
                         || contains_encoded_data),
             ),
+            force_refresh: request.force_refresh,
             payment_attempt,
             refunds,
             disputes,
@@ -410,6 +414,10 @@ async fn get_tracker_for_sync<
             multiple_capture_data,
             redirect_response: None,
             payment_link_data: None,
+            used_saved_method,
+            installments: None,
+            risk_data: None,
+            next_sync_at: None,
             surcharge_details: None,
             frm_message: frm_response.ok(),
         },
@@ -429,11 +437,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         operations::ValidateResult<'a>,
     )> {
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-                field_name: "merchant_id".to_string(),
-                expected_format: "merchant_id from merchant account".to_string(),
-            })?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         Ok((
             Box::new(self),
@@ -12,7 +12,10 @@ use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::PaymentMethodRetrieve,
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, helpers, operations, types::PaymentId, CustomerDetails, PaymentAddress,
+            PaymentData,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -53,14 +56,19 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         let payment_id = payment_id
             .get_payment_intent_id()
-            .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)
+            .map(PaymentId::from_existing)?;
         let merchant_id = &merchant_account.merchant_id;
         let storage_scheme = merchant_account.storage_scheme;
 
         let db = &*state.store;
 
         payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
@@ -72,6 +80,14 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
 
         helpers::validate_card_data(request.payment_method_data.clone())?;
 
+        if request.confirm.unwrap_or(false) {
+            helpers::validate_intent_status_for_confirm(
+                &payment_intent.status,
+                request.retry_action.as_ref(),
+            )?;
+            helpers::validate_client_secret_for_confirm(auth_flow, request)?;
+        }
+
         helpers::validate_payment_status_against_not_allowed_statuses(
             &payment_intent.status,
             &[
@@ -110,7 +126,11 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         .await?;
 
         payment_intent = db
-            .find_payment_intent_by_payment_id_merchant_id(&payment_id, merchant_id, storage_scheme)
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id.get_string_repr(),
+                merchant_id,
+                storage_scheme,
+            )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
@@ -138,6 +158,14 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         payment_attempt.payment_method = payment_method.or(payment_attempt.payment_method);
         payment_attempt.payment_method_type =
             payment_method_type.or(payment_attempt.payment_method_type);
+        let installments = helpers::get_installments(request.installments, &payment_attempt)?;
+        payment_attempt.installments = installments
+            .as_ref()
+            .map(Encode::<api_models::payments::InstallmentData>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "installments",
+            })?;
         let customer_details = helpers::get_customer_details_from_request(request);
 
         let amount = request
@@ -297,6 +325,17 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         };
 
         Self::populate_payment_attempt_with_request(&mut payment_attempt, request);
+        payment_attempt.capture_on =
+            request
+                .capture_on
+                .or(payment_attempt.capture_on)
+                .map(|capture_on| {
+                    helpers::normalize_capture_on(
+                        capture_on,
+                        request.connector.as_deref(),
+                        &state.conf.date_only_capture_on_connectors,
+                    )
+                });
 
         let creds_identifier = request
             .merchant_connector_details
@@ -319,6 +358,13 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
         // The operation merges mandate data from both request and payment_attempt
         let setup_mandate = setup_mandate.map(Into::into);
 
+        let used_saved_method = helpers::is_saved_payment_method_used(
+            &request.payment_method_data,
+            &token,
+            &request.mandate_id,
+        );
+        payment_attempt.used_saved_method = Some(used_saved_method);
+
         Ok((
             next_operation,
             PaymentData {
@@ -339,6 +385,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 confirm: request.confirm,
                 payment_method_data: request.payment_method_data.clone(),
                 force_sync: None,
+                force_refresh: false,
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
@@ -355,6 +402,10 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 surcharge_details: None,
                 frm_message: None,
                 payment_link_data: None,
+                used_saved_method,
+                installments,
+                risk_data: request.risk_data.clone(),
+                next_sync_at: None,
             },
             Some(customer_details),
         ))
@@ -482,6 +533,9 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
         let payment_experience = payment_data.payment_attempt.payment_experience;
         let amount_to_capture = payment_data.payment_attempt.amount_to_capture;
         let capture_method = payment_data.payment_attempt.capture_method;
+        let capture_on = payment_data.payment_attempt.capture_on;
+        let used_saved_method = payment_data.payment_attempt.used_saved_method;
+        let installments = payment_data.payment_attempt.installments.clone();
         payment_data.payment_attempt = db
             .update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt,
@@ -498,6 +552,9 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
                     business_sub_label,
                     amount_to_capture,
                     capture_method,
+                    capture_on,
+                    installments,
+                    used_saved_method,
                 },
                 storage_scheme,
             )
@@ -537,30 +594,46 @@ impl<F: Clone, Ctx: PaymentMethodRetrieve>
             .payment_intent
             .statement_descriptor_suffix
             .clone();
+        let statement_descriptor_city = payment_data
+            .payment_intent
+            .statement_descriptor_city
+            .clone();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
 
+        let payment_intent_update = storage::PaymentIntentUpdate::Update {
+            amount: payment_data.amount.into(),
+            currency: payment_data.currency,
+            setup_future_usage,
+            status: intent_status,
+            customer_id: customer_id.clone(),
+            shipping_address_id: shipping_address,
+            billing_address_id: billing_address,
+            return_url,
+            business_country,
+            business_label,
+            description,
+            statement_descriptor_name,
+            statement_descriptor_suffix,
+            order_details,
+            metadata,
+            payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city,
+            shipping_amount: payment_data.payment_intent.shipping_amount,
+            handling_amount: payment_data.payment_intent.handling_amount,
+            success_url: payment_data.payment_intent.success_url.clone(),
+            failure_url: payment_data.payment_intent.failure_url.clone(),
+        };
+        helpers::validate_intent_status_transition(
+            payment_data.payment_intent.status,
+            &payment_intent_update,
+        )?;
+
         payment_data.payment_intent = db
             .update_payment_intent(
                 payment_data.payment_intent,
-                storage::PaymentIntentUpdate::Update {
-                    amount: payment_data.amount.into(),
-                    currency: payment_data.currency,
-                    setup_future_usage,
-                    status: intent_status,
-                    customer_id: customer_id.clone(),
-                    shipping_address_id: shipping_address,
-                    billing_address_id: billing_address,
-                    return_url,
-                    business_country,
-                    business_label,
-                    description,
-                    statement_descriptor_name,
-                    statement_descriptor_suffix,
-                    order_details,
-                    metadata,
-                    payment_confirm_source: None,
-                },
+                payment_intent_update,
                 storage_scheme,
             )
             .await
@@ -598,11 +671,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
         };
 
         let request_merchant_id = request.merchant_id.as_deref();
-        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
-            .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-                field_name: "merchant_id".to_string(),
-                expected_format: "merchant_id from merchant account".to_string(),
-            })?;
+        helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)?;
 
         helpers::validate_request_amount_and_amount_to_capture(
             request.amount,
@@ -615,6 +684,38 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve> ValidateRequest<F, api::Paymen
 
         helpers::validate_payment_method_fields_present(request)?;
 
+        helpers::validate_risk_data(request.risk_data.as_ref())?;
+
+        helpers::validate_metadata_keys(request.metadata.as_ref())?;
+
+        helpers::validate_setup_future_usage_and_off_session(
+            request.setup_future_usage,
+            request.off_session,
+        )?;
+
+        helpers::validate_statement_descriptor_city(request.statement_descriptor_city.as_ref())?;
+
+        helpers::validate_statement_descriptor(
+            "statement_descriptor_name",
+            request.statement_descriptor_name.as_ref(),
+            request.payment_method,
+        )?;
+
+        helpers::validate_statement_descriptor(
+            "statement_descriptor_suffix",
+            request.statement_descriptor_suffix.as_ref(),
+            request.payment_method,
+        )?;
+
+        helpers::validate_additional_amounts(request.amount, request.surcharge_details.as_ref())?;
+
+        helpers::validate_shipping_and_handling_amounts(
+            request.amount,
+            request.shipping_amount,
+            request.handling_amount,
+            request.order_details.as_ref(),
+        )?;
+
         let mandate_type = helpers::validate_mandate(request, false)?;
         let payment_id = core_utils::get_or_generate_id("payment_id", &given_payment_id, "pay")?;
 
@@ -665,6 +766,16 @@ impl PaymentUpdate {
             .clone()
             .map(|i| payment_intent.return_url.replace(i.to_string()));
 
+        request
+            .success_url
+            .clone()
+            .map(|i| payment_intent.success_url.replace(i.to_string()));
+
+        request
+            .failure_url
+            .clone()
+            .map(|i| payment_intent.failure_url.replace(i.to_string()));
+
         payment_intent.business_country = request.business_country;
 
         payment_intent.business_label = request.business_label.clone();
@@ -684,6 +795,19 @@ impl PaymentUpdate {
             .clone()
             .map(|i| payment_intent.statement_descriptor_suffix.replace(i));
 
+        request
+            .statement_descriptor_city
+            .clone()
+            .map(|i| payment_intent.statement_descriptor_city.replace(i));
+
+        request
+            .shipping_amount
+            .map(|i| payment_intent.shipping_amount.replace(i));
+
+        request
+            .handling_amount
+            .map(|i| payment_intent.handling_amount.replace(i));
+
         request
             .client_secret
             .clone()
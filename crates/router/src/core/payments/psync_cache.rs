@@ -0,0 +1,327 @@
+use std::future::Future;
+
+use router_env::logger;
+
+use crate::{
+    configs::settings::PsyncCache,
+    core::errors::RouterResult,
+    db::StorageInterface,
+    services,
+    types::{self, api::enums as api_enums, storage::enums},
+};
+
+pub const PSYNC_RESULT_KEY_PREFIX: &str = "PSYNC_RESULT";
+
+/// Sync results are shared by all instances of the application, keyed by the merchant, the
+/// connector and the transaction at the connector
+fn get_redis_psync_result_key(
+    merchant_id: &str,
+    connector: &str,
+    connector_transaction_id: &str,
+) -> String {
+    format!("{PSYNC_RESULT_KEY_PREFIX}_{merchant_id}_{connector}_{connector_transaction_id}")
+}
+
+/// Status of the payment and the response returned by the connector on sync
+#[derive(Debug, Clone)]
+pub struct SyncResult {
+    pub status: enums::AttemptStatus,
+    pub response: types::PaymentsResponseData,
+}
+
+/// The sync result stored in redis. Only the responses a connector returns on sync are stored.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CachedSyncResult {
+    status: enums::AttemptStatus,
+    response: CachedSyncResponse,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CachedSyncResponse {
+    TransactionResponse {
+        resource_id: types::ResponseId,
+        redirection_data: Option<services::RedirectForm>,
+        mandate_reference: Option<types::MandateReference>,
+        connector_metadata: Option<serde_json::Value>,
+        network_txn_id: Option<String>,
+        connector_response_reference_id: Option<String>,
+    },
+    TransactionUnresolvedResponse {
+        resource_id: types::ResponseId,
+        reason: Option<api_enums::UnresolvedResponseReason>,
+        connector_response_reference_id: Option<String>,
+    },
+}
+
+impl CachedSyncResult {
+    fn from_sync_result(result: SyncResult) -> Option<Self> {
+        let response = match result.response {
+            types::PaymentsResponseData::TransactionResponse {
+                resource_id,
+                redirection_data,
+                mandate_reference,
+                connector_metadata,
+                network_txn_id,
+                connector_response_reference_id,
+            } => CachedSyncResponse::TransactionResponse {
+                resource_id,
+                redirection_data,
+                mandate_reference,
+                connector_metadata,
+                network_txn_id,
+                connector_response_reference_id,
+            },
+            types::PaymentsResponseData::TransactionUnresolvedResponse {
+                resource_id,
+                reason,
+                connector_response_reference_id,
+            } => CachedSyncResponse::TransactionUnresolvedResponse {
+                resource_id,
+                reason,
+                connector_response_reference_id,
+            },
+            _ => return None,
+        };
+        Some(Self {
+            status: result.status,
+            response,
+        })
+    }
+
+    fn into_sync_result(self) -> SyncResult {
+        let response = match self.response {
+            CachedSyncResponse::TransactionResponse {
+                resource_id,
+                redirection_data,
+                mandate_reference,
+                connector_metadata,
+                network_txn_id,
+                connector_response_reference_id,
+            } => types::PaymentsResponseData::TransactionResponse {
+                resource_id,
+                redirection_data,
+                mandate_reference,
+                connector_metadata,
+                network_txn_id,
+                connector_response_reference_id,
+            },
+            CachedSyncResponse::TransactionUnresolvedResponse {
+                resource_id,
+                reason,
+                connector_response_reference_id,
+            } => types::PaymentsResponseData::TransactionUnresolvedResponse {
+                resource_id,
+                reason,
+                connector_response_reference_id,
+            },
+        };
+        SyncResult {
+            status: self.status,
+            response,
+        }
+    }
+}
+
+pub trait CacheableSyncResult: Sized {
+    /// Returns the result to be cached, error responses from the connector are not cached
+    fn get_sync_result(&self) -> Option<SyncResult>;
+
+    /// Updates the data with a cached result, as the connector would have
+    fn apply_sync_result(self, result: SyncResult) -> Self;
+}
+
+impl<F, Req> CacheableSyncResult for types::RouterData<F, Req, types::PaymentsResponseData> {
+    fn get_sync_result(&self) -> Option<SyncResult> {
+        self.response.as_ref().ok().map(|response| SyncResult {
+            status: self.status,
+            response: response.clone(),
+        })
+    }
+
+    fn apply_sync_result(mut self, result: SyncResult) -> Self {
+        self.status = result.status;
+        self.response = Ok(result.response);
+        self
+    }
+}
+
+/// Returns the cached sync result of the transaction if it was synced within the configured
+/// window, otherwise calls `sync` and caches its result. `force_refresh` always calls `sync`.
+/// Failures of the cache only cause the connector to be called.
+#[allow(clippy::too_many_arguments)]
+pub async fn sync_with_cache<T, Op, Fut>(
+    db: &dyn StorageInterface,
+    config: &PsyncCache,
+    merchant_id: &str,
+    connector: &str,
+    connector_transaction_id: &types::ResponseId,
+    force_refresh: bool,
+    data: T,
+    sync: Op,
+) -> RouterResult<T>
+where
+    T: CacheableSyncResult,
+    Op: FnOnce(T) -> Fut,
+    Fut: Future<Output = RouterResult<T>>,
+{
+    let redis_key = match connector_transaction_id {
+        types::ResponseId::ConnectorTransactionId(id) if config.enabled => {
+            get_redis_psync_result_key(merchant_id, connector, id)
+        }
+        _ => return sync(data).await,
+    };
+    let redis_conn = match db.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection for the sync cache");
+            return sync(data).await;
+        }
+    };
+
+    if !force_refresh {
+        match redis_conn
+            .get_and_deserialize_key::<CachedSyncResult>(&redis_key, "CachedSyncResult")
+            .await
+        {
+            Ok(cached_result) => {
+                logger::info!("Using cached sync result {redis_key}");
+                return Ok(data.apply_sync_result(cached_result.into_sync_result()));
+            }
+            Err(error) => {
+                if !matches!(
+                    error.current_context(),
+                    redis_interface::errors::RedisError::NotFound
+                ) {
+                    logger::error!(?error, "Failed to read cached sync result {redis_key}");
+                }
+            }
+        }
+    }
+
+    let data = sync(data).await?;
+    if let Some(cached_result) = data
+        .get_sync_result()
+        .and_then(CachedSyncResult::from_sync_result)
+    {
+        redis_conn
+            .serialize_and_set_key_with_expiry(
+                &redis_key,
+                cached_result,
+                i64::try_from(config.ttl_in_seconds).unwrap_or(i64::MAX),
+            )
+            .await
+            .map_err(|error| logger::error!(?error, "Failed to cache sync result {redis_key}"))
+            .ok();
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use storage_impl::MockDb;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestSyncData(enums::AttemptStatus);
+
+    impl CacheableSyncResult for TestSyncData {
+        fn get_sync_result(&self) -> Option<SyncResult> {
+            Some(SyncResult {
+                status: self.0,
+                response: types::PaymentsResponseData::TransactionUnresolvedResponse {
+                    resource_id: types::ResponseId::NoResponseId,
+                    reason: None,
+                    connector_response_reference_id: None,
+                },
+            })
+        }
+
+        fn apply_sync_result(self, result: SyncResult) -> Self {
+            Self(result.status)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_sync_within_the_window_skips_the_connector_call() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let config = PsyncCache {
+            enabled: true,
+            ttl_in_seconds: 60,
+        };
+        let connector_transaction_id = types::ResponseId::ConnectorTransactionId(format!(
+            "psync_cache_test_txn_{}",
+            uuid::Uuid::new_v4()
+        ));
+        let connector_calls = AtomicU32::new(0);
+        let calls = &connector_calls;
+        let sync = |_: TestSyncData| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(TestSyncData(enums::AttemptStatus::Charged))
+        };
+
+        let first = sync_with_cache(
+            &mock_db,
+            &config,
+            "merchant_1",
+            "stripe",
+            &connector_transaction_id,
+            false,
+            TestSyncData(enums::AttemptStatus::Pending),
+            sync,
+        )
+        .await
+        .unwrap();
+        let second = sync_with_cache(
+            &mock_db,
+            &config,
+            "merchant_1",
+            "stripe",
+            &connector_transaction_id,
+            false,
+            TestSyncData(enums::AttemptStatus::Pending),
+            sync,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(first.0, enums::AttemptStatus::Charged);
+        assert_eq!(second.0, enums::AttemptStatus::Charged);
+        assert_eq!(connector_calls.load(Ordering::SeqCst), 1);
+
+        // The same transaction id of another merchant is not shared
+        sync_with_cache(
+            &mock_db,
+            &config,
+            "merchant_2",
+            "stripe",
+            &connector_transaction_id,
+            false,
+            TestSyncData(enums::AttemptStatus::Pending),
+            sync,
+        )
+        .await
+        .unwrap();
+        assert_eq!(connector_calls.load(Ordering::SeqCst), 2);
+
+        sync_with_cache(
+            &mock_db,
+            &config,
+            "merchant_1",
+            "stripe",
+            &connector_transaction_id,
+            true,
+            TestSyncData(enums::AttemptStatus::Pending),
+            sync,
+        )
+        .await
+        .unwrap();
+        assert_eq!(connector_calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,114 @@
+use error_stack::{report, ResultExt};
+use router_env::logger;
+
+use crate::{
+    configs::settings::PaymentCreateRateLimit,
+    core::errors::{self, RouterResult},
+    db::StorageInterface,
+};
+
+pub const PAYMENT_CREATE_RATE_LIMIT_PREFIX: &str = "PAYMENT_CREATE_RATE_LIMIT";
+
+/// The requests of a merchant are counted in a separate key for every window, so that the count
+/// is shared by all the application instances and starts over in the next window
+fn get_redis_rate_limit_key(merchant_id: &str, window_in_seconds: u32) -> String {
+    let window =
+        common_utils::date_time::now_unix_timestamp() / i64::from(window_in_seconds.max(1));
+    format!("{PAYMENT_CREATE_RATE_LIMIT_PREFIX}_{merchant_id}_{window}")
+}
+
+/// Counts the payment create request against the limit configured for the merchant, failing with
+/// `RateLimited` if the merchant has exceeded it in the current window.
+pub async fn check_payment_create_rate_limit(
+    db: &dyn StorageInterface,
+    config: &PaymentCreateRateLimit,
+    merchant_id: &str,
+) -> RouterResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let limit = config.get_limit(merchant_id);
+    let redis_key = get_redis_rate_limit_key(merchant_id, limit.window_in_seconds);
+    let redis_conn = db
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    // The counter of the window is created along with its expiry before it is incremented, so that
+    // the counter expires even if the request fails right after incrementing it
+    redis_conn
+        .set_key_if_not_exists_with_expiry(
+            &redis_key,
+            0,
+            Some(i64::from(limit.window_in_seconds.max(1))),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to create the payment create rate limit counter")?;
+    let request_count = redis_conn
+        .increment_key(&redis_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to count the payment create request of the merchant")?;
+
+    if request_count <= i64::from(limit.max_requests) {
+        Ok(())
+    } else {
+        logger::warn!("Payment create rate limit exceeded for merchant {merchant_id}");
+        Err(report!(errors::ApiErrorResponse::RateLimited))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::collections::HashMap;
+
+    use storage_impl::MockDb;
+
+    use super::*;
+    use crate::configs::settings::RateLimit;
+
+    #[tokio::test]
+    async fn test_requests_exceeding_the_rate_limit_of_the_merchant_are_rejected() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let merchant_id = format!("merchant_{}", uuid::Uuid::new_v4().simple());
+        let other_merchant_id = format!("merchant_{}", uuid::Uuid::new_v4().simple());
+        let config = PaymentCreateRateLimit {
+            enabled: true,
+            default_limit: RateLimit {
+                max_requests: 100,
+                window_in_seconds: 3600,
+            },
+            merchant_limits: HashMap::from([(
+                merchant_id.clone(),
+                RateLimit {
+                    max_requests: 2,
+                    window_in_seconds: 3600,
+                },
+            )]),
+        };
+
+        for _ in 0..2 {
+            assert!(
+                check_payment_create_rate_limit(&mock_db, &config, &merchant_id)
+                    .await
+                    .is_ok()
+            );
+        }
+        assert!(matches!(
+            check_payment_create_rate_limit(&mock_db, &config, &merchant_id)
+                .await
+                .map_err(|err| err.current_context().clone()),
+            Err(errors::ApiErrorResponse::RateLimited)
+        ));
+        // Other merchants are limited by the default limit
+        assert!(
+            check_payment_create_rate_limit(&mock_db, &config, &other_merchant_id)
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::{future::Future, time::Duration};
+
+use router_env::logger;
+
+use crate::{
+    configs::settings::ConnectorRetry,
+    core::{errors::RouterResult, utils as core_utils},
+    types,
+};
+
+/// Flows which can be sent to the connector more than once without side effects
+const IDEMPOTENT_FLOWS: &[&str] = &["PSync"];
+
+/// Returns true if the connector call for the flow `F` is safe to be retried
+pub fn is_idempotent_flow<F>() -> bool {
+    core_utils::get_flow_name::<F>()
+        .map(|flow_name| IDEMPOTENT_FLOWS.contains(&flow_name.as_str()))
+        .unwrap_or(false)
+}
+
+/// A connector error is considered transient if the connector responded with a 5xx status code
+/// or if the request timed out, in which case the status code is set to 504
+pub fn is_transient_connector_error<F, Req, Resp>(
+    router_data_res: &RouterResult<types::RouterData<F, Req, Resp>>,
+) -> bool {
+    match router_data_res {
+        Ok(router_data) => router_data
+            .response
+            .as_ref()
+            .err()
+            .map(|error_response| (500..=599).contains(&error_response.status_code))
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+/// Calls `operation` until `should_retry` returns false for its result or the configured number
+/// of attempts is exhausted. The delay between attempts doubles after every retry, starting from
+/// `base_delay_in_milliseconds`.
+pub async fn retry_with_backoff<T, Fut, Op, P>(
+    config: &ConnectorRetry,
+    should_retry: P,
+    mut operation: Op,
+) -> T
+where
+    Op: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+    P: Fn(&T) -> bool,
+{
+    let mut attempt = 1;
+    loop {
+        let result = operation().await;
+
+        if attempt >= config.max_attempts || !should_retry(&result) {
+            return result;
+        }
+
+        let delay = config
+            .base_delay_in_milliseconds
+            .saturating_mul(2u64.saturating_pow(attempt - 1));
+        logger::info!(
+            "Transient connector error on attempt {}, retrying in {} ms",
+            attempt,
+            delay
+        );
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let config = ConnectorRetry {
+            max_attempts: 3,
+            base_delay_in_milliseconds: 1,
+        };
+        let attempts = AtomicU32::new(0);
+
+        let result = retry_with_backoff(
+            &config,
+            |status_code: &u16| (500..=599).contains(status_code),
+            || async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => 503,
+                    _ => 200,
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result, 200);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_retry_stops_when_attempts_are_exhausted() {
+        let config = ConnectorRetry {
+            max_attempts: 2,
+            base_delay_in_milliseconds: 1,
+        };
+        let attempts = AtomicU32::new(0);
+
+        let result = retry_with_backoff(
+            &config,
+            |status_code: &u16| (500..=599).contains(status_code),
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                500
+            },
+        )
+        .await;
+
+        assert_eq!(result, 500);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_only_psync_is_idempotent() {
+        assert!(is_idempotent_flow::<crate::types::api::PSync>());
+        assert!(!is_idempotent_flow::<crate::types::api::Authorize>());
+    }
+}
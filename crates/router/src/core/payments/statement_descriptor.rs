@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use crate::types::api::enums as api_enums;
+
+/// How the statement descriptor name and suffix of a payment are sent to a connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DescriptorFormat {
+    /// The connector accepts the name and the suffix as separate fields and composes the
+    /// descriptor itself
+    Separate,
+    /// The connector accepts a single descriptor, the name is used as the prefix and is joined
+    /// with the suffix by `separator`. The descriptor is truncated to `max_length` characters.
+    Combined {
+        separator: &'static str,
+        max_length: usize,
+    },
+}
+
+fn get_descriptor_format(connector_name: &str) -> DescriptorFormat {
+    match api_enums::Connector::from_str(connector_name) {
+        Ok(api_enums::Connector::Checkout) => DescriptorFormat::Combined {
+            separator: "*",
+            max_length: 25,
+        },
+        _ => DescriptorFormat::Separate,
+    }
+}
+
+/// Statement descriptor of a payment formatted as per the rules of the connector
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormattedStatementDescriptor {
+    pub statement_descriptor: Option<String>,
+    pub statement_descriptor_suffix: Option<String>,
+}
+
+/// Composes the statement descriptor sent to the connector from the statement descriptor name
+/// and suffix of the payment
+pub fn format_statement_descriptor(
+    connector_name: &str,
+    statement_descriptor_name: Option<String>,
+    statement_descriptor_suffix: Option<String>,
+) -> FormattedStatementDescriptor {
+    match get_descriptor_format(connector_name) {
+        DescriptorFormat::Separate => FormattedStatementDescriptor {
+            statement_descriptor: statement_descriptor_name,
+            statement_descriptor_suffix,
+        },
+        DescriptorFormat::Combined {
+            separator,
+            max_length,
+        } => {
+            let statement_descriptor =
+                match (statement_descriptor_name, statement_descriptor_suffix) {
+                    (Some(name), Some(suffix)) => Some(format!("{name}{separator}{suffix}")),
+                    (name, suffix) => name.or(suffix),
+                };
+            FormattedStatementDescriptor {
+                statement_descriptor: statement_descriptor
+                    .map(|descriptor| descriptor.chars().take(max_length).collect()),
+                statement_descriptor_suffix: None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_is_sent_separately_to_stripe() {
+        assert_eq!(
+            format_statement_descriptor(
+                "stripe",
+                Some("HYPERSWITCH".to_string()),
+                Some("ORDER 1234".to_string()),
+            ),
+            FormattedStatementDescriptor {
+                statement_descriptor: Some("HYPERSWITCH".to_string()),
+                statement_descriptor_suffix: Some("ORDER 1234".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_descriptor_is_combined_for_checkout() {
+        assert_eq!(
+            format_statement_descriptor(
+                "checkout",
+                Some("HYPERSWITCH".to_string()),
+                Some("ORDER 1234".to_string()),
+            ),
+            FormattedStatementDescriptor {
+                statement_descriptor: Some("HYPERSWITCH*ORDER 1234".to_string()),
+                statement_descriptor_suffix: None,
+            }
+        );
+        assert_eq!(
+            format_statement_descriptor(
+                "checkout",
+                Some("HYPERSWITCH PAYMENTS".to_string()),
+                Some("ORDER 1234".to_string()),
+            )
+            .statement_descriptor,
+            Some("HYPERSWITCH PAYMENTS*ORDE".to_string())
+        );
+        assert_eq!(
+            format_statement_descriptor("checkout", None, Some("ORDER 1234".to_string()))
+                .statement_descriptor,
+            Some("ORDER 1234".to_string())
+        );
+    }
+}
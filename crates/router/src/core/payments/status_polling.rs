@@ -0,0 +1,251 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use api_models::payments::HeaderPayload;
+use error_stack::report;
+use router_env::logger;
+
+use super::{operations::PaymentStatus, payments_core, CallConnectorAction};
+use crate::{
+    core::{
+        errors::{self, RouterResponse, RouterResult},
+        payment_methods::PaymentMethodRetrieve,
+    },
+    routes::AppState,
+    services,
+    types::{api, domain, storage::enums},
+    utils,
+};
+
+/// Parameters controlling how often the status of a payment is synced with the connector
+#[derive(Debug, Clone)]
+pub struct StatusPollingParams {
+    /// Maximum number of times the connector is polled
+    pub max_polls: u32,
+    /// Delay before the second poll, the delay doubles after every subsequent poll
+    pub interval_in_milliseconds: u64,
+    /// Polling is stopped once this much time has elapsed since the first poll
+    pub timeout_in_milliseconds: u64,
+}
+
+impl StatusPollingParams {
+    const DEFAULT_MAX_POLLS: u32 = 5;
+    const MAX_POLLS_LIMIT: u32 = 10;
+    const DEFAULT_INTERVAL_IN_MILLISECONDS: u64 = 500;
+    const MIN_INTERVAL_IN_MILLISECONDS: u64 = 100;
+    const MAX_INTERVAL_IN_MILLISECONDS: u64 = 5000;
+    /// Polling holds the request open, so it is stopped well before clients time out
+    const TIMEOUT_IN_MILLISECONDS: u64 = 30_000;
+
+    /// The polling parameters requested by the merchant, within the limits allowed for a request
+    pub fn from_request(req: &api::PaymentsStatusPollingRequest) -> RouterResult<Self> {
+        let max_polls = req.max_polls.unwrap_or(Self::DEFAULT_MAX_POLLS);
+        utils::when(!(1..=Self::MAX_POLLS_LIMIT).contains(&max_polls), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("max_polls must be between 1 and {}", Self::MAX_POLLS_LIMIT),
+            }))
+        })?;
+        let interval_in_milliseconds = req
+            .interval_in_milliseconds
+            .unwrap_or(Self::DEFAULT_INTERVAL_IN_MILLISECONDS);
+        utils::when(
+            !(Self::MIN_INTERVAL_IN_MILLISECONDS..=Self::MAX_INTERVAL_IN_MILLISECONDS)
+                .contains(&interval_in_milliseconds),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "interval_in_milliseconds must be between {} and {}",
+                        Self::MIN_INTERVAL_IN_MILLISECONDS,
+                        Self::MAX_INTERVAL_IN_MILLISECONDS
+                    ),
+                }))
+            },
+        )?;
+        Ok(Self {
+            max_polls,
+            interval_in_milliseconds,
+            timeout_in_milliseconds: Self::TIMEOUT_IN_MILLISECONDS,
+        })
+    }
+}
+
+/// Only payments which are being processed by the connector can change their status by syncing,
+/// every other status requires an action from the merchant or the customer.
+pub fn should_continue_polling(status: &enums::IntentStatus) -> bool {
+    matches!(status, enums::IntentStatus::Processing)
+}
+
+/// Calls `poll` until the status returned by `get_status` no longer requires polling, the
+/// maximum number of polls is reached or the next poll would go beyond the deadline. The result
+/// of the last poll is returned.
+pub async fn poll_with_backoff<T, Fut, Op, S>(
+    params: &StatusPollingParams,
+    get_status: S,
+    mut poll: Op,
+) -> RouterResult<T>
+where
+    Op: FnMut() -> Fut,
+    Fut: Future<Output = RouterResult<T>>,
+    S: Fn(&T) -> Option<enums::IntentStatus>,
+{
+    let deadline = Instant::now() + Duration::from_millis(params.timeout_in_milliseconds);
+    let mut poll_count = 1;
+    loop {
+        let result = poll().await?;
+
+        let status = get_status(&result);
+        let is_status_pending = status
+            .as_ref()
+            .map(should_continue_polling)
+            .unwrap_or(false);
+        if !is_status_pending || poll_count >= params.max_polls {
+            return Ok(result);
+        }
+
+        let delay = Duration::from_millis(
+            params
+                .interval_in_milliseconds
+                .saturating_mul(2u64.saturating_pow(poll_count - 1)),
+        );
+        if Instant::now() + delay > deadline {
+            logger::info!("Status polling deadline reached after {} polls", poll_count);
+            return Ok(result);
+        }
+
+        logger::info!(
+            "Payment is in {:?} status after poll {}, polling again in {} ms",
+            status,
+            poll_count,
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+        poll_count += 1;
+    }
+}
+
+/// Syncs the payment with the connector until it reaches a status which no longer changes by
+/// syncing, the payment intent is updated on every poll.
+pub async fn payments_status_polling_core<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentsStatusPollingRequest,
+) -> RouterResponse<api::PaymentsResponse> {
+    let params = StatusPollingParams::from_request(&req)?;
+    let retrieve_request = api::PaymentsRetrieveRequest {
+        resource_id: api::PaymentIdType::PaymentIntentId(req.payment_id),
+        merchant_id: Some(merchant_account.merchant_id.clone()),
+        force_sync: true,
+        force_refresh: true,
+        ..Default::default()
+    };
+    poll_with_backoff(
+        &params,
+        |response: &services::ApplicationResponse<api::PaymentsResponse>| match response {
+            services::ApplicationResponse::Json(payments_response)
+            | services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+                Some(payments_response.status)
+            }
+            _ => None,
+        },
+        || {
+            payments_core::<api::PSync, api::PaymentsResponse, _, _, _, Ctx>(
+                state.clone(),
+                merchant_account.clone(),
+                key_store.clone(),
+                PaymentStatus,
+                retrieve_request.clone(),
+                services::AuthFlow::Merchant,
+                CallConnectorAction::Trigger,
+                HeaderPayload::default(),
+            )
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_polling_stops_when_payment_succeeds() {
+        let params = StatusPollingParams {
+            max_polls: 5,
+            interval_in_milliseconds: 1,
+            timeout_in_milliseconds: 1000,
+        };
+        let polls = AtomicU32::new(0);
+
+        let status = poll_with_backoff(
+            &params,
+            |status: &enums::IntentStatus| Some(*status),
+            || async {
+                match polls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Ok(enums::IntentStatus::Processing),
+                    _ => Ok(enums::IntentStatus::Succeeded),
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, enums::IntentStatus::Succeeded);
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_polling_params_of_request_are_bounded() {
+        let params = StatusPollingParams::from_request(&api::PaymentsStatusPollingRequest {
+            payment_id: "pay_1".to_string(),
+            max_polls: Some(3),
+            interval_in_milliseconds: None,
+        })
+        .unwrap();
+        assert_eq!(params.max_polls, 3);
+        assert_eq!(params.interval_in_milliseconds, 500);
+
+        for (max_polls, interval_in_milliseconds) in
+            [(Some(0), None), (Some(11), None), (None, Some(10))]
+        {
+            assert!(matches!(
+                StatusPollingParams::from_request(&api::PaymentsStatusPollingRequest {
+                    payment_id: "pay_1".to_string(),
+                    max_polls,
+                    interval_in_milliseconds,
+                })
+                .map_err(|err| err.current_context().clone()),
+                Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+            ));
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_polling_stops_when_max_polls_are_exhausted() {
+        let params = StatusPollingParams {
+            max_polls: 2,
+            interval_in_milliseconds: 1,
+            timeout_in_milliseconds: 1000,
+        };
+        let polls = AtomicU32::new(0);
+
+        let status = poll_with_backoff(
+            &params,
+            |status: &enums::IntentStatus| Some(*status),
+            || async {
+                polls.fetch_add(1, Ordering::SeqCst);
+                Ok(enums::IntentStatus::Processing)
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, enums::IntentStatus::Processing);
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,142 @@
+use masking::PeekInterface;
+
+use crate::{
+    core::utils as core_utils,
+    types::{self, api, storage::enums},
+};
+
+/// Outcome of a payment made with a test card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestCardOutcome {
+    /// The payment is sent to the connector, which authorizes it in test mode, so that it can be
+    /// captured, synced and refunded like any other payment
+    Success,
+    Decline {
+        code: &'static str,
+        message: &'static str,
+    },
+}
+
+impl TestCardOutcome {
+    /// Only declines are simulated, the connector is not called for them
+    pub fn is_simulated(&self) -> bool {
+        matches!(self, Self::Decline { .. })
+    }
+}
+
+/// Cards which always produce the same outcome when the merchant connector account is in test
+/// mode
+const TEST_CARDS: &[(&str, TestCardOutcome)] = &[
+    ("4000000000000077", TestCardOutcome::Success),
+    (
+        "4000000000000002",
+        TestCardOutcome::Decline {
+            code: "card_declined",
+            message: "Your card was declined",
+        },
+    ),
+    (
+        "4000000000009995",
+        TestCardOutcome::Decline {
+            code: "insufficient_funds",
+            message: "Your card has insufficient funds",
+        },
+    ),
+    (
+        "4000000000000069",
+        TestCardOutcome::Decline {
+            code: "expired_card",
+            message: "Your card has expired",
+        },
+    ),
+];
+
+/// Test cards only replace the authorization of the payment, every other flow such as sync,
+/// capture or void is sent to the connector
+pub fn is_test_card_flow<F>() -> bool {
+    core_utils::get_flow_name::<F>()
+        .map(|flow_name| flow_name == "Authorize")
+        .unwrap_or(false)
+}
+
+/// Returns the outcome of the payment if the payment method is one of the test cards
+pub fn get_test_card_outcome(
+    payment_method_data: &api::PaymentMethodData,
+) -> Option<TestCardOutcome> {
+    match payment_method_data {
+        api::PaymentMethodData::Card(card) => {
+            let card_number = card.card_number.peek();
+            TEST_CARDS
+                .iter()
+                .find(|(test_card_number, _)| test_card_number == card_number)
+                .map(|(_, outcome)| *outcome)
+        }
+        _ => None,
+    }
+}
+
+/// Updates the router data with the outcome of the test card, as the connector would have. Only
+/// simulated outcomes change the router data.
+pub fn apply_test_card_outcome<F, Req>(
+    mut router_data: types::RouterData<F, Req, types::PaymentsResponseData>,
+    outcome: TestCardOutcome,
+) -> types::RouterData<F, Req, types::PaymentsResponseData> {
+    if let TestCardOutcome::Decline { code, message } = outcome {
+        router_data.status = enums::AttemptStatus::Failure;
+        router_data.response = Err(types::ErrorResponse {
+            code: code.to_string(),
+            message: message.to_string(),
+            reason: Some(message.to_string()),
+            status_code: 402,
+        });
+    }
+    router_data
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn get_card(card_number: &str) -> api::PaymentMethodData {
+        api::PaymentMethodData::Card(api::Card {
+            card_number: cards::CardNumber::from_str(card_number).unwrap(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_success_test_card() {
+        let outcome = get_test_card_outcome(&get_card("4000000000000077"));
+        assert_eq!(outcome, Some(TestCardOutcome::Success));
+        // The payment is authorized by the connector, so that it can be captured and refunded
+        assert!(!TestCardOutcome::Success.is_simulated());
+    }
+
+    #[test]
+    fn test_decline_test_card() {
+        assert_eq!(
+            get_test_card_outcome(&get_card("4000000000000002")),
+            Some(TestCardOutcome::Decline {
+                code: "card_declined",
+                message: "Your card was declined",
+            })
+        );
+        assert!(TestCardOutcome::Decline {
+            code: "card_declined",
+            message: "Your card was declined",
+        }
+        .is_simulated());
+        assert_eq!(get_test_card_outcome(&get_card("4111111111111111")), None);
+    }
+
+    #[test]
+    fn test_test_cards_only_replace_authorize() {
+        assert!(is_test_card_flow::<api::Authorize>());
+        assert!(!is_test_card_flow::<api::PSync>());
+        assert!(!is_test_card_flow::<api::Capture>());
+        assert!(!is_test_card_flow::<api::SetupMandate>());
+    }
+}
@@ -0,0 +1,109 @@
+//! Fixtures shared by the tests of the payments core
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    services,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+};
+
+/// Merchant account of `merchant_1`, using the default business profile
+pub fn get_merchant_account() -> domain::MerchantAccount {
+    let current_time = common_utils::date_time::now();
+    domain::MerchantAccount {
+        id: None,
+        merchant_id: "merchant_1".to_string(),
+        return_url: None,
+        enable_payment_response_hash: false,
+        payment_response_hash_key: None,
+        redirect_to_merchant_with_http_post: false,
+        merchant_name: None,
+        merchant_details: None,
+        webhook_details: None,
+        sub_merchants_enabled: None,
+        parent_merchant_id: None,
+        publishable_key: None,
+        storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+        locker_id: None,
+        metadata: None,
+        routing_algorithm: None,
+        primary_business_details: serde_json::json!([]),
+        frm_routing_algorithm: None,
+        created_at: current_time,
+        modified_at: current_time,
+        intent_fulfillment_time: None,
+        payout_routing_algorithm: None,
+        organization_id: "org_1".to_string(),
+        is_recon_enabled: false,
+        default_profile: None,
+        recon_status: diesel_models::enums::ReconStatus::NotRequested,
+        debug_connector_io: false,
+    }
+}
+
+/// Key store of `merchant_id` with a newly generated key, encrypted with the master key of the
+/// store
+pub async fn get_merchant_key_store(
+    master_key: &[u8],
+    merchant_id: &str,
+) -> domain::MerchantKeyStore {
+    domain::MerchantKeyStore {
+        merchant_id: merchant_id.to_string(),
+        key: domain::types::encrypt(
+            services::generate_aes256_key().unwrap().to_vec().into(),
+            master_key,
+        )
+        .await
+        .unwrap(),
+        created_at: common_utils::date_time::now(),
+    }
+}
+
+/// USD 10.00 payment of `merchant_1` with a single attempt, `{payment_id}_1`
+pub fn get_payment_intent(payment_id: &str, status: enums::IntentStatus) -> storage::PaymentIntent {
+    storage::PaymentIntent {
+        id: 1,
+        payment_id: payment_id.to_string(),
+        merchant_id: "merchant_1".to_string(),
+        status,
+        amount: 1000,
+        currency: Some(enums::Currency::USD),
+        amount_captured: None,
+        customer_id: None,
+        description: None,
+        return_url: None,
+        metadata: None,
+        connector_id: None,
+        shipping_address_id: None,
+        billing_address_id: None,
+        statement_descriptor_name: None,
+        statement_descriptor_suffix: None,
+        created_at: common_utils::date_time::now(),
+        modified_at: common_utils::date_time::now(),
+        last_synced: None,
+        setup_future_usage: None,
+        off_session: None,
+        client_secret: None,
+        active_attempt: data_models::RemoteStorageObject::ForeignID(format!("{payment_id}_1")),
+        business_country: None,
+        business_label: None,
+        order_details: None,
+        allowed_payment_method_types: None,
+        connector_metadata: None,
+        feature_metadata: None,
+        attempt_count: 1,
+        payment_link_id: None,
+        profile_id: None,
+        merchant_decision: None,
+        payment_confirm_source: None,
+        connector_routing_trail: None,
+        statement_descriptor_city: None,
+        shipping_amount: None,
+        handling_amount: None,
+        client_secret_expiry: None,
+        success_url: None,
+        failure_url: None,
+    }
+}
@@ -33,7 +33,7 @@ where
     FData: mandate::MandateBehaviour,
 {
     match resp.response {
-        Ok(_) => {
+        Ok(_) if should_save_payment_method(resp.status) => {
             let db = &*state.store;
             let token_store = state
                 .conf
@@ -144,7 +144,7 @@ where
                     };
                 } else {
                     let pm_metadata = create_payment_method_metadata(None, connector_token)?;
-                    payment_methods::cards::create_payment_method(
+                    let payment_method = payment_methods::cards::create_payment_method(
                         db,
                         &payment_method_create_request,
                         &customer.customer_id,
@@ -154,7 +154,19 @@ where
                         pm_data_encrypted,
                         key_store,
                     )
-                    .await?;
+                    .await;
+                    if let Err(error) = payment_method {
+                        // The card was stored in the locker by this payment, it is deleted so
+                        // that no card is left in the locker without a payment method
+                        delete_saved_card_from_locker(
+                            state,
+                            &customer.customer_id,
+                            merchant_id,
+                            &locker_response.0.payment_method_id,
+                        )
+                        .await;
+                        return Err(error);
+                    }
                 };
                 Some(locker_response.0.payment_method_id)
             } else {
@@ -162,10 +174,55 @@ where
             };
             Ok(pm_id)
         }
-        Err(_) => Ok(None),
+        Ok(_) | Err(_) => Ok(None),
     }
 }
 
+/// Payment methods are saved only once the connector has authorized or charged the payment.
+/// Payments which are declined or still pending, like the ones awaiting 3DS authentication, do
+/// not save the payment method.
+pub fn should_save_payment_method(status: storage_enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        storage_enums::AttemptStatus::Authorized
+            | storage_enums::AttemptStatus::Charged
+            | storage_enums::AttemptStatus::PartialCharged
+    )
+}
+
+async fn delete_saved_card_from_locker(
+    state: &AppState,
+    customer_id: &str,
+    merchant_id: &str,
+    payment_method_id: &str,
+) {
+    payment_methods::cards::delete_card_from_locker(
+        state,
+        customer_id,
+        merchant_id,
+        payment_method_id,
+    )
+    .await
+    .map_err(|error| {
+        logger::error!(
+            ?error,
+            "Failed to delete the saved card {payment_method_id} from the locker"
+        )
+    })
+    .ok();
+}
+
+/// Mandates are not created for payments which the connector has declined
+pub fn is_payment_declined(status: storage_enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        storage_enums::AttemptStatus::AuthenticationFailed
+            | storage_enums::AttemptStatus::RouterDeclined
+            | storage_enums::AttemptStatus::AuthorizationFailed
+            | storage_enums::AttemptStatus::Failure
+    )
+}
+
 pub async fn save_in_locker(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
@@ -313,3 +370,166 @@ pub async fn add_payment_method_token<F: Clone, T: types::Tokenizable + Clone>(
         _ => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{
+        configs::settings::Settings,
+        core::payments::test_utils,
+        db::{MasterKeyInterface, StorageImpl},
+    };
+
+    fn get_setup_mandate_router_data(
+        status: storage_enums::AttemptStatus,
+    ) -> types::SetupMandateRouterData {
+        types::RouterData {
+            flow: std::marker::PhantomData,
+            merchant_id: "merchant_1".to_string(),
+            customer_id: Some("cus_1".to_string()),
+            connector_customer: None,
+            connector: "stripe".to_string(),
+            payment_id: "pay_1".to_string(),
+            attempt_id: "pay_1_1".to_string(),
+            status,
+            payment_method: storage_enums::PaymentMethod::Card,
+            connector_auth_type: types::ConnectorAuthType::default(),
+            description: None,
+            return_url: None,
+            address: payments::PaymentAddress::default(),
+            auth_type: storage_enums::AuthenticationType::NoThreeDs,
+            connector_meta_data: None,
+            amount_captured: None,
+            access_token: None,
+            session_token: None,
+            reference_id: None,
+            payment_method_token: None,
+            recurring_mandate_payment_data: None,
+            preprocessing_id: None,
+            payment_method_balance: None,
+            connector_api_version: None,
+            request: types::SetupMandateRequestData {
+                currency: storage_enums::Currency::USD,
+                payment_method_data: api::PaymentMethodData::Card(api::Card {
+                    card_number: "4000000000000002".parse().unwrap(),
+                    card_exp_month: masking::Secret::new("10".to_string()),
+                    card_exp_year: masking::Secret::new("2035".to_string()),
+                    card_cvc: masking::Secret::new("123".to_string()),
+                    ..Default::default()
+                }),
+                amount: Some(1000),
+                confirm: true,
+                statement_descriptor_suffix: None,
+                mandate_id: None,
+                setup_future_usage: Some(storage_enums::FutureUsage::OffSession),
+                off_session: None,
+                setup_mandate_details: None,
+                router_return_url: None,
+                browser_info: None,
+                email: None,
+                return_url: None,
+                payment_method_type: None,
+            },
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId("pi_1".to_string()),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: None,
+            }),
+            payment_method_id: None,
+            connector_request_reference_id: "pay_1_1".to_string(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            test_mode: None,
+            connector_http_status_code: Some(200),
+            external_latency: None,
+            debug_connector_io: false,
+            connector_io_log: None,
+            connector_authorized_amount: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_metadata: None,
+            three_ds_authentication_result: None,
+            apple_pay_flow: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_method_is_not_saved_for_declined_or_pending_payment() {
+        let conf = Settings::new().expect("invalid settings");
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let state = AppState::with_storage(
+            conf,
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        )
+        .await;
+        let key_store =
+            test_utils::get_merchant_key_store(state.store.get_master_key(), "merchant_1").await;
+        let merchant_account = test_utils::get_merchant_account();
+        let connector = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            "stripe",
+            api::GetToken::Connector,
+        )
+        .unwrap();
+
+        // Saving the payment method would fail without a customer, the save is skipped before
+        for status in [
+            storage_enums::AttemptStatus::Failure,
+            storage_enums::AttemptStatus::AuthorizationFailed,
+            storage_enums::AttemptStatus::Pending,
+            storage_enums::AttemptStatus::AuthenticationPending,
+        ] {
+            let payment_method_id = save_payment_method(
+                &state,
+                &connector,
+                get_setup_mandate_router_data(status),
+                &None,
+                &merchant_account,
+                None,
+                &key_store,
+            )
+            .await
+            .unwrap();
+            assert_eq!(payment_method_id, None);
+        }
+        assert!(state
+            .store
+            .find_payment_method_by_customer_id_merchant_id_list("cus_1", "merchant_1")
+            .await
+            .is_err());
+
+        assert!(save_payment_method(
+            &state,
+            &connector,
+            get_setup_mandate_router_data(storage_enums::AttemptStatus::Charged),
+            &None,
+            &merchant_account,
+            None,
+            &key_store,
+        )
+        .await
+        .is_err());
+    }
+
+    #[test]
+    fn test_mandate_is_not_created_for_declined_payment() {
+        assert!(is_payment_declined(storage_enums::AttemptStatus::Failure));
+        assert!(is_payment_declined(
+            storage_enums::AttemptStatus::AuthorizationFailed
+        ));
+        assert!(!is_payment_declined(storage_enums::AttemptStatus::Charged));
+        assert!(!is_payment_declined(
+            storage_enums::AttemptStatus::AuthenticationPending
+        ));
+    }
+}
@@ -4,15 +4,17 @@ use api_models::payments::FrmMessage;
 use common_utils::{consts::X_HS_LATENCY, fp_utils};
 use diesel_models::ephemeral_key;
 use error_stack::{IntoReport, ResultExt};
+use masking::ExposeInterface;
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use super::{flows::Feature, PaymentData};
 use crate::{
-    configs::settings::{ConnectorRequestReferenceIdConfig, Server},
+    configs::settings::{ConnectorRequestReferenceIdConfig, ResponseMaskingPolicyConfig, Server},
     connector::{Helcim, Nexinets},
     core::{
         errors::{self, RouterResponse, RouterResult},
-        payments::{self, helpers},
+        payments::{self, helpers, statement_descriptor},
         utils as core_utils,
     },
     routes::{metrics, AppState},
@@ -23,7 +25,7 @@ use crate::{
         transformers::{ForeignFrom, ForeignInto, ForeignTryFrom},
         MultipleCaptureRequestData,
     },
-    utils::{OptionExt, ValueExt},
+    utils::{self, OptionExt, ValueExt},
 };
 
 #[instrument(skip_all)]
@@ -162,6 +164,13 @@ where
         connector_api_version,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: merchant_account.debug_connector_io,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow,
     };
 
@@ -182,6 +191,7 @@ where
         server: &Server,
         operation: Op,
         connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+        response_masking_policy_config: &ResponseMaskingPolicyConfig,
         connector_http_status_code: Option<u16>,
         external_latency: Option<u128>,
         is_latency_header_enabled: Option<bool>,
@@ -202,27 +212,15 @@ where
         server: &Server,
         operation: Op,
         connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+        response_masking_policy_config: &ResponseMaskingPolicyConfig,
         connector_http_status_code: Option<u16>,
         external_latency: Option<u128>,
         is_latency_header_enabled: Option<bool>,
     ) -> RouterResponse<Self> {
-        let captures =
-            payment_data
-                .multiple_capture_data
-                .clone()
-                .and_then(|multiple_capture_data| {
-                    multiple_capture_data
-                        .expand_captures
-                        .and_then(|should_expand| {
-                            should_expand.then_some(
-                                multiple_capture_data
-                                    .get_all_captures()
-                                    .into_iter()
-                                    .cloned()
-                                    .collect(),
-                            )
-                        })
-                });
+        let captures = payment_data
+            .multiple_capture_data
+            .as_ref()
+            .and_then(|multiple_capture_data| multiple_capture_data.get_captures_for_response());
 
         payments_to_payments_response(
             req,
@@ -233,6 +231,7 @@ where
             server,
             &operation,
             connector_request_reference_id_config,
+            response_masking_policy_config,
             connector_http_status_code,
             external_latency,
             is_latency_header_enabled,
@@ -255,6 +254,7 @@ where
         _server: &Server,
         _operation: Op,
         _connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+        _response_masking_policy_config: &ResponseMaskingPolicyConfig,
         _connector_http_status_code: Option<u16>,
         _external_latency: Option<u128>,
         _is_latency_header_enabled: Option<bool>,
@@ -289,6 +289,7 @@ where
         _server: &Server,
         _operation: Op,
         _connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+        _response_masking_policy_config: &ResponseMaskingPolicyConfig,
         _connector_http_status_code: Option<u16>,
         _external_latency: Option<u128>,
         _is_latency_header_enabled: Option<bool>,
@@ -344,6 +345,7 @@ pub fn payments_to_payments_response<R, Op, F: Clone>(
     server: &Server,
     operation: &Op,
     connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
+    response_masking_policy_config: &ResponseMaskingPolicyConfig,
     connector_http_status_code: Option<u16>,
     external_latency: Option<u128>,
     is_latency_header_enabled: Option<bool>,
@@ -351,6 +353,7 @@ pub fn payments_to_payments_response<R, Op, F: Clone>(
 where
     Op: Debug,
 {
+    let amount_capturable = payment_data.remaining_capturable();
     let payment_attempt = payment_data.payment_attempt;
     let payment_intent = payment_data.payment_intent;
     let payment_link_data = payment_data.payment_link_data;
@@ -365,17 +368,21 @@ where
         .change_context(errors::ApiErrorResponse::InvalidDataValue {
             field_name: "amount",
         })?;
+    let formatted_amount = utils::format_amount_in_currency(payment_attempt.amount, *currency);
     let mandate_id = payment_attempt.mandate_id.clone();
-    let refunds_response = if payment_data.refunds.is_empty() {
+    let mut refunds = payment_data.refunds;
+    helpers::sort_refunds(&mut refunds);
+    let amount_refunded = helpers::get_amount_refunded(&refunds);
+    let refund_status = helpers::get_payment_refund_status(
+        payment_intent
+            .amount_captured
+            .unwrap_or(payment_intent.amount),
+        &refunds,
+    );
+    let refunds_response = if refunds.is_empty() {
         None
     } else {
-        Some(
-            payment_data
-                .refunds
-                .into_iter()
-                .map(ForeignInto::foreign_into)
-                .collect(),
-        )
+        Some(refunds.into_iter().map(ForeignInto::foreign_into).collect())
     };
 
     let disputes_response = if payment_data.disputes.is_empty() {
@@ -428,8 +435,14 @@ where
     let merchant_decision = payment_intent.merchant_decision.to_owned();
     let frm_message = payment_data.frm_message.map(FrmMessage::foreign_from);
 
-    let payment_method_data_response =
-        additional_payment_method_data.map(api::PaymentMethodDataResponse::from);
+    let payment_method_data_response = additional_payment_method_data
+        .map(api::PaymentMethodDataResponse::from)
+        .map(|payment_method_data| {
+            helpers::mask_payment_method_data_response(
+                payment_method_data,
+                response_masking_policy_config.get_policy(&merchant_id),
+            )
+        });
 
     let mut headers = connector_http_status_code
         .map(|status_code| {
@@ -509,6 +522,7 @@ where
                             api_models::payments::NextActionData::QrCodeInformation {
                                 image_data_url: qr_code_data.image_data_url,
                                 display_to_timestamp: qr_code_data.display_to_timestamp,
+                                qr_code_data: qr_code_data.qr_code_data,
                             }
                         }))
                         .or(next_action_containing_wait_screen.map(|wait_screen_data| {
@@ -517,16 +531,20 @@ where
                                 display_to_timestamp: wait_screen_data.display_to_timestamp,
                             }
                         }))
-                        .or(payment_data
-                            .connector_response
-                            .authentication_data
-                            .map(|_| api_models::payments::NextActionData::RedirectToUrl {
+                        .or(is_three_ds_challenge_required(
+                            payment_data.connector_response.authentication_data.as_ref(),
+                            payment_data.connector_response.challenge_required,
+                            payment_intent.status,
+                        )
+                        .then(|| {
+                            api_models::payments::NextActionData::RedirectToUrl {
                                 redirect_to_url: helpers::create_startpay_url(
                                     server,
                                     &payment_attempt,
                                     &payment_intent,
                                 ),
-                            }));
+                            }
+                        }));
                 };
 
                 // next action check for third party sdk session (for ex: Apple pay through trustpay has third party sdk session response)
@@ -538,8 +556,24 @@ where
                     )
                 }
 
+                let next_action_at = get_next_action_at(
+                    payment_intent.status,
+                    next_action_response.as_ref(),
+                    payment_intent.client_secret_expiry,
+                    payment_data.next_sync_at,
+                );
+
                 let mut response: api::PaymentsResponse = Default::default();
                 let routed_through = payment_attempt.connector.clone();
+                let error = helpers::get_error_details(
+                    payment_attempt.status,
+                    payment_attempt.error_code.clone(),
+                    payment_attempt
+                        .error_reason
+                        .clone()
+                        .or(payment_attempt.error_message.clone()),
+                    routed_through.clone(),
+                );
 
                 let connector_label = routed_through.as_ref().and_then(|connector_name| {
                     core_utils::get_connector_label(
@@ -552,11 +586,17 @@ where
                 services::ApplicationResponse::JsonWithHeaders((
                     response
                         .set_payment_id(Some(payment_attempt.payment_id))
-                        .set_merchant_id(Some(payment_attempt.merchant_id))
+                        .set_merchant_id(Some(payment_intent.merchant_id))
                         .set_status(payment_intent.status)
                         .set_amount(payment_attempt.amount)
-                        .set_amount_capturable(Some(payment_attempt.amount_capturable))
+                        .set_formatted_amount(Some(formatted_amount))
+                        .set_amount_capturable(Some(amount_capturable))
                         .set_amount_received(payment_intent.amount_captured)
+                        .set_amount_captured(payment_intent.amount_captured)
+                        .set_amount_refunded(amount_refunded)
+                        .set_refund_status(refund_status)
+                        .set_shipping_amount(payment_intent.shipping_amount)
+                        .set_handling_amount(payment_intent.handling_amount)
                         .set_connector(routed_through)
                         .set_client_secret(payment_intent.client_secret.map(masking::Secret::new))
                         .set_created(Some(payment_intent.created_at))
@@ -626,10 +666,10 @@ where
                             auth_flow == services::AuthFlow::Merchant,
                         )
                         .set_description(payment_intent.description)
-                        .set_refunds(refunds_response) // refunds.iter().map(refund_to_refund_response),
-                        .set_disputes(disputes_response)
-                        .set_attempts(attempts_response)
-                        .set_captures(captures_response)
+                        .set_refunds(refunds_response, auth_flow == services::AuthFlow::Merchant)
+                        .set_disputes(disputes_response, auth_flow == services::AuthFlow::Merchant)
+                        .set_attempts(attempts_response, auth_flow == services::AuthFlow::Merchant)
+                        .set_captures(captures_response, auth_flow == services::AuthFlow::Merchant)
                         .set_payment_method(
                             payment_attempt.payment_method,
                             auth_flow == services::AuthFlow::Merchant,
@@ -645,45 +685,120 @@ where
                                 .or(payment_attempt.error_message),
                         )
                         .set_error_code(payment_attempt.error_code)
+                        .set_error(error)
                         .set_shipping(payment_data.address.shipping)
                         .set_billing(payment_data.address.billing)
                         .set_next_action(next_action_response)
+                        .set_next_action_at(next_action_at)
                         .set_return_url(payment_intent.return_url)
+                        .set_success_url(payment_intent.success_url)
+                        .set_failure_url(payment_intent.failure_url)
                         .set_cancellation_reason(payment_attempt.cancellation_reason)
                         .set_authentication_type(payment_attempt.authentication_type)
                         .set_statement_descriptor_name(payment_intent.statement_descriptor_name)
                         .set_statement_descriptor_suffix(payment_intent.statement_descriptor_suffix)
+                        .set_statement_descriptor_city(payment_intent.statement_descriptor_city)
                         .set_setup_future_usage(payment_intent.setup_future_usage)
                         .set_capture_method(payment_attempt.capture_method)
                         .set_payment_experience(payment_attempt.payment_experience)
                         .set_payment_method_type(payment_attempt.payment_method_type)
-                        .set_metadata(payment_intent.metadata)
+                        .set_metadata(
+                            payment_intent.metadata,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .set_order_details(payment_intent.order_details)
-                        .set_connector_label(connector_label)
-                        .set_business_country(payment_intent.business_country)
-                        .set_business_label(payment_intent.business_label)
-                        .set_business_sub_label(payment_attempt.business_sub_label)
+                        .set_connector_label(
+                            connector_label,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_business_country(
+                            payment_intent.business_country,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_business_label(
+                            payment_intent.business_label,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_business_sub_label(
+                            payment_attempt.business_sub_label,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .set_allowed_payment_method_types(
                             payment_intent.allowed_payment_method_types,
                         )
                         .set_ephemeral_key(
                             payment_data.ephemeral_key.map(ForeignFrom::foreign_from),
                         )
-                        .set_frm_message(frm_message)
-                        .set_merchant_decision(merchant_decision)
+                        .set_frm_message(frm_message, auth_flow == services::AuthFlow::Merchant)
+                        .set_merchant_decision(
+                            merchant_decision,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .set_manual_retry_allowed(helpers::is_manual_retry_allowed(
                             &payment_intent.status,
                             &payment_attempt.status,
                             connector_request_reference_id_config,
                             &merchant_id,
                         ))
-                        .set_connector_transaction_id(payment_attempt.connector_transaction_id)
-                        .set_feature_metadata(payment_intent.feature_metadata)
-                        .set_connector_metadata(payment_intent.connector_metadata)
-                        .set_reference_id(payment_attempt.connector_response_reference_id)
+                        .set_connector_transaction_id(
+                            payment_attempt.connector_transaction_id,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_feature_metadata(
+                            payment_intent.feature_metadata,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_connector_metadata(
+                            payment_intent.connector_metadata,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_reference_id(
+                            payment_attempt.connector_response_reference_id,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .set_payment_link(payment_link_data)
-                        .set_profile_id(payment_intent.profile_id)
+                        .set_profile_id(
+                            payment_intent.profile_id,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .set_attempt_count(payment_intent.attempt_count)
+                        .set_used_saved_method(payment_data.used_saved_method)
+                        .set_connector_routing_trail(
+                            payment_intent.connector_routing_trail,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_connector_io_log(
+                            payment_data.connector_response.connector_io_log,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_three_ds_version(
+                            payment_data.connector_response.three_ds_version,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_liability_shift(
+                            payment_data.connector_response.liability_shift,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_authorization_code(
+                            payment_data.connector_response.authorization_code,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_settled_at(
+                            payment_data.connector_response.settled_at,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_avs_result(
+                            payment_attempt.avs_result,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_cvv_result(
+                            payment_attempt.cvv_result,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
+                        .set_payment_channel(
+                            payment_attempt.payment_channel,
+                            auth_flow == services::AuthFlow::Merchant,
+                        )
                         .to_owned(),
                     headers,
                 ))
@@ -692,11 +807,17 @@ where
         None => services::ApplicationResponse::JsonWithHeaders((
             api::PaymentsResponse {
                 payment_id: Some(payment_attempt.payment_id),
-                merchant_id: Some(payment_attempt.merchant_id),
+                merchant_id: Some(payment_intent.merchant_id),
                 status: payment_intent.status,
                 amount: payment_attempt.amount,
+                formatted_amount: Some(formatted_amount),
                 amount_capturable: None,
                 amount_received: payment_intent.amount_captured,
+                amount_captured: payment_intent.amount_captured,
+                amount_refunded,
+                refund_status,
+                shipping_amount: payment_intent.shipping_amount,
+                handling_amount: payment_intent.handling_amount,
                 client_secret: payment_intent.client_secret.map(masking::Secret::new),
                 created: Some(payment_intent.created_at),
                 currency: currency.to_string(),
@@ -708,6 +829,15 @@ where
                 captures: captures_response,
                 payment_method: payment_attempt.payment_method,
                 capture_method: payment_attempt.capture_method,
+                error: helpers::get_error_details(
+                    payment_attempt.status,
+                    payment_attempt.error_code.clone(),
+                    payment_attempt
+                        .error_reason
+                        .clone()
+                        .or(payment_attempt.error_message.clone()),
+                    payment_attempt.connector.clone(),
+                ),
                 error_message: payment_attempt
                     .error_reason
                     .or(payment_attempt.error_message),
@@ -743,6 +873,13 @@ where
                 reference_id: payment_attempt.connector_response_reference_id,
                 attempt_count: payment_intent.attempt_count,
                 payment_link: payment_link_data,
+                used_saved_method: payment_data.used_saved_method,
+                next_action_at: get_next_action_at(
+                    payment_intent.status,
+                    None,
+                    payment_intent.client_secret_expiry,
+                    payment_data.next_sync_at,
+                ),
                 ..Default::default()
             },
             headers,
@@ -763,6 +900,59 @@ where
     output
 }
 
+/// The customer is redirected to the 3DS challenge if the connector returned the data to
+/// authenticate the customer. The redirection is dropped for a frictionless authentication only
+/// once the payment no longer waits for the customer, since the connector may still expect the
+/// customer to visit the authentication page while it does.
+pub fn is_three_ds_challenge_required(
+    authentication_data: Option<&serde_json::Value>,
+    challenge_required: Option<bool>,
+    intent_status: enums::IntentStatus,
+) -> bool {
+    authentication_data.is_some()
+        && (intent_status == enums::IntentStatus::RequiresCustomerAction
+            || challenge_required != Some(false))
+}
+
+/// Returns the time at which the next action of the customer expires, if the connector set one
+fn get_next_action_expiry(
+    next_action: &api_models::payments::NextActionData,
+) -> Option<PrimitiveDateTime> {
+    let expiry = match next_action {
+        api_models::payments::NextActionData::QrCodeInformation {
+            display_to_timestamp,
+            ..
+        } => display_to_timestamp
+            .and_then(|timestamp| time::OffsetDateTime::from_unix_timestamp(timestamp).ok()),
+        api_models::payments::NextActionData::WaitScreenInformation {
+            display_to_timestamp,
+            ..
+        } => display_to_timestamp
+            .and_then(|timestamp| time::OffsetDateTime::from_unix_timestamp_nanos(timestamp).ok()),
+        _ => None,
+    }?;
+    Some(PrimitiveDateTime::new(expiry.date(), expiry.time()))
+}
+
+/// Estimates when the payment is expected to move on, so that clients know when to poll it.
+/// Payments waiting for the customer move on when the next action expires, or at the latest
+/// when the client secret expires. Payments settled asynchronously move on when they are next
+/// synced with the connector, as per the sync schedule configured for the connector.
+pub fn get_next_action_at(
+    intent_status: enums::IntentStatus,
+    next_action: Option<&api_models::payments::NextActionData>,
+    client_secret_expiry: Option<PrimitiveDateTime>,
+    next_sync_at: Option<PrimitiveDateTime>,
+) -> Option<PrimitiveDateTime> {
+    match intent_status {
+        enums::IntentStatus::RequiresCustomerAction => next_action
+            .and_then(get_next_action_expiry)
+            .or(client_secret_expiry),
+        enums::IntentStatus::Processing => next_sync_at,
+        _ => None,
+    }
+}
+
 pub fn third_party_sdk_session_next_action<Op>(
     payment_attempt: &storage::PaymentAttempt,
     operation: &Op,
@@ -945,6 +1135,14 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "browser_info",
             })?;
+        let sdk_information: Option<api_models::payments::SdkInfo> = attempt
+            .sdk_information
+            .clone()
+            .map(|sdk_information| sdk_information.expose().parse_value("SdkInfo"))
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "sdk_information",
+            })?;
 
         let order_category = additional_data
             .payment_data
@@ -996,13 +1194,27 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
         ));
 
         // payment_method_data is not required during recurring mandate payment, in such case keep default PaymentMethodData as MandatePayment
+        let resolved_payment_method = payment_data.get_resolved_payment_method();
         let payment_method_data = payment_data.payment_method_data.or_else(|| {
-            if payment_data.mandate_id.is_some() {
-                Some(api_models::payments::PaymentMethodData::MandatePayment)
+            (resolved_payment_method == payments::ResolvedPaymentMethod::Mandate)
+                .then_some(api_models::payments::PaymentMethodData::MandatePayment)
+        });
+        let minimum_three_ds_version =
+            if attempt.authentication_type == Some(enums::AuthenticationType::ThreeDs) {
+                helpers::get_minimum_three_ds_version(
+                    &additional_data.state.conf.minimum_three_ds_version,
+                    connector_name,
+                    payment_data.payment_intent.business_country,
+                )
+                .cloned()
             } else {
                 None
-            }
-        });
+            };
+        let formatted_statement_descriptor = statement_descriptor::format_statement_descriptor(
+            connector_name,
+            payment_data.payment_intent.statement_descriptor_name,
+            payment_data.payment_intent.statement_descriptor_suffix,
+        );
         Ok(Self {
             payment_method_data: payment_method_data.get_required_value("payment_method_data")?,
             setup_future_usage: payment_data.payment_intent.setup_future_usage,
@@ -1010,8 +1222,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             off_session: payment_data.mandate_id.as_ref().map(|_| true),
             setup_mandate_details: payment_data.setup_mandate.clone(),
             confirm: payment_data.payment_attempt.confirm,
-            statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
-            statement_descriptor: payment_data.payment_intent.statement_descriptor_name,
+            statement_descriptor_suffix: formatted_statement_descriptor.statement_descriptor_suffix,
+            statement_descriptor: formatted_statement_descriptor.statement_descriptor,
+            statement_descriptor_city: payment_data.payment_intent.statement_descriptor_city,
             capture_method: payment_data.payment_attempt.capture_method,
             amount: payment_data.amount.into(),
             currency: payment_data.currency,
@@ -1029,6 +1242,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             complete_authorize_url,
             customer_id: None,
             surcharge_details: payment_data.surcharge_details,
+            risk_data: payment_data.risk_data,
+            sdk_information,
+            installments: payment_data.installments,
+            minimum_three_ds_version,
         })
     }
 }
@@ -1038,6 +1255,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
 
     fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data;
+        let amount = payment_data
+            .payment_attempt
+            .amount_to_capture
+            .unwrap_or(payment_data.payment_attempt.amount);
         Ok(Self {
             mandate_id: payment_data.mandate_id.clone(),
             connector_transaction_id: match payment_data.payment_attempt.connector_transaction_id {
@@ -1055,6 +1276,8 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
                 ),
                 None => types::SyncRequestType::SinglePaymentSync,
             },
+            force_refresh: payment_data.force_refresh,
+            amount,
         })
     }
 }
@@ -1098,6 +1321,22 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
             .payment_attempt
             .amount_to_capture
             .map_or(payment_data.amount.into(), |capture_amount| capture_amount);
+        // The amount is captured in the currency chosen by the customer, if a dynamic currency
+        // conversion offer was accepted for the capture
+        let dcc_details = payment_data
+            .payment_attempt
+            .dcc_details
+            .clone()
+            .map(|dcc_details| {
+                dcc_details.parse_value::<api_models::payments::DccDetails>("DccDetails")
+            })
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse dcc details of the payment attempt")?;
+        let (amount_to_capture, currency) = match dcc_details {
+            Some(dcc_details) => (dcc_details.converted_amount, dcc_details.target_currency),
+            None => (amount_to_capture, payment_data.currency),
+        };
         let browser_info: Option<types::BrowserInformation> = payment_data
             .payment_attempt
             .browser_info
@@ -1110,7 +1349,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
 
         Ok(Self {
             amount_to_capture,
-            currency: payment_data.currency,
+            currency,
             connector_transaction_id: connector
                 .connector
                 .connector_transaction_id(payment_data.payment_attempt.clone())?
@@ -1436,3 +1675,289 @@ impl ForeignFrom<payments::FraudCheck> for FrmMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::configs::settings::ResponseMaskingPolicy;
+
+    #[test]
+    fn test_challenge_is_surfaced_only_when_required() {
+        let authentication_data = serde_json::json!({
+            "Form": {
+                "endpoint": "https://acs.issuer.com/challenge",
+                "method": "POST",
+                "form_fields": {}
+            }
+        });
+
+        // Frictionless authentication of a payment which has moved past the customer action
+        assert!(!is_three_ds_challenge_required(
+            Some(&authentication_data),
+            Some(false),
+            enums::IntentStatus::Succeeded,
+        ));
+        // Frictionless authentication of a payment still waiting for the customer, the connector
+        // expects the customer to be redirected
+        assert!(is_three_ds_challenge_required(
+            Some(&authentication_data),
+            Some(false),
+            enums::IntentStatus::RequiresCustomerAction,
+        ));
+        // Challenge required by the issuer
+        assert!(is_three_ds_challenge_required(
+            Some(&authentication_data),
+            Some(true),
+            enums::IntentStatus::RequiresCustomerAction,
+        ));
+        // The connector did not report whether a challenge is required
+        assert!(is_three_ds_challenge_required(
+            Some(&authentication_data),
+            None,
+            enums::IntentStatus::RequiresCustomerAction,
+        ));
+        assert!(!is_three_ds_challenge_required(
+            None,
+            Some(true),
+            enums::IntentStatus::RequiresCustomerAction,
+        ));
+    }
+
+    #[test]
+    fn test_next_action_at_for_payment_requiring_customer_action() {
+        let client_secret_expiry = time::macros::datetime!(2023-10-27 10:15:00);
+        let qr_code = api_models::payments::NextActionData::QrCodeInformation {
+            image_data_url: url::Url::parse("data:image/png;base64,iVBORw0KGgo").unwrap(),
+            // 2023-10-27 10:05:00 UTC
+            display_to_timestamp: Some(1_698_401_100),
+            qr_code_data: None,
+        };
+
+        assert_eq!(
+            get_next_action_at(
+                enums::IntentStatus::RequiresCustomerAction,
+                Some(&qr_code),
+                Some(client_secret_expiry),
+                None,
+            ),
+            Some(time::macros::datetime!(2023-10-27 10:05:00))
+        );
+        // The redirection does not expire by itself
+        let redirection = api_models::payments::NextActionData::RedirectToUrl {
+            redirect_to_url: "https://checkout.example.com/redirect".to_string(),
+        };
+        assert_eq!(
+            get_next_action_at(
+                enums::IntentStatus::RequiresCustomerAction,
+                Some(&redirection),
+                Some(client_secret_expiry),
+                None,
+            ),
+            Some(client_secret_expiry)
+        );
+        assert_eq!(
+            get_next_action_at(
+                enums::IntentStatus::Succeeded,
+                None,
+                Some(client_secret_expiry),
+                None,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_action_at_for_processing_payment_is_the_next_sync() {
+        let next_sync_at = time::macros::datetime!(2023-10-27 10:02:00);
+
+        assert_eq!(
+            get_next_action_at(
+                enums::IntentStatus::Processing,
+                None,
+                None,
+                Some(next_sync_at),
+            ),
+            Some(next_sync_at)
+        );
+    }
+
+    fn get_refund(
+        refund_id: &str,
+        refund_amount: i64,
+        refund_status: enums::RefundStatus,
+    ) -> storage::Refund {
+        storage::Refund {
+            id: 1,
+            internal_reference_id: format!("internal_{refund_id}"),
+            refund_id: refund_id.to_string(),
+            payment_id: "pay_123".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            connector_transaction_id: "txn_123".to_string(),
+            connector: "stripe".to_string(),
+            connector_refund_id: None,
+            external_reference_id: None,
+            refund_type: enums::RefundType::InstantRefund,
+            total_amount: 4000,
+            currency: enums::Currency::USD,
+            refund_amount,
+            refund_status,
+            sent_to_gateway: true,
+            refund_error_message: None,
+            metadata: None,
+            refund_arn: None,
+            created_at: common_utils::date_time::now(),
+            updated_at: common_utils::date_time::now(),
+            description: None,
+            attempt_id: "pay_123_1".to_string(),
+            refund_reason: None,
+            refund_error_code: None,
+            profile_id: None,
+        }
+    }
+
+    #[test]
+    fn test_amounts_after_partial_capture_and_partial_refund() {
+        // 4000 out of 6540 was captured, and then 1000 out of the captured amount was refunded
+        let refunds = vec![
+            get_refund("ref_1", 1000, enums::RefundStatus::Success),
+            get_refund("ref_2", 500, enums::RefundStatus::Failure),
+        ];
+
+        assert_eq!(helpers::get_amount_refunded(&refunds), Some(1000));
+        assert_eq!(helpers::get_amount_refunded(&[]), None);
+
+        // Two refunds of 2000 each, one succeeded and the other is still pending with the connector
+        let mut refunds = vec![
+            get_refund("ref_3", 2000, enums::RefundStatus::Success),
+            get_refund("ref_4", 2000, enums::RefundStatus::Pending),
+        ];
+        assert_eq!(
+            helpers::get_payment_refund_status(4000, &refunds),
+            Some(api::enums::PaymentRefundStatus::PartiallyRefunded)
+        );
+
+        refunds[1].refund_status = enums::RefundStatus::Success;
+        assert_eq!(
+            helpers::get_payment_refund_status(4000, &refunds),
+            Some(api::enums::PaymentRefundStatus::Refunded)
+        );
+        assert_eq!(helpers::get_payment_refund_status(4000, &[]), None);
+    }
+
+    #[test]
+    fn test_refunds_are_ordered_by_creation_time_and_id() {
+        let created_at = time::macros::datetime!(2023-10-27 10:11:12.000001);
+        let make_refund = |id, created_at| storage::Refund {
+            id,
+            created_at,
+            updated_at: created_at,
+            ..get_refund(&format!("ref_{id}"), 100, enums::RefundStatus::Success)
+        };
+        let expected_order = vec!["ref_1", "ref_2", "ref_3", "ref_4"];
+
+        // Refunds 2 and 3 were created in the same microsecond, refund 4 a microsecond later
+        let mut refunds = vec![
+            make_refund(4, created_at + time::Duration::microseconds(1)),
+            make_refund(3, created_at),
+            make_refund(1, created_at - time::Duration::microseconds(1)),
+            make_refund(2, created_at),
+        ];
+        for _ in 0..2 {
+            helpers::sort_refunds(&mut refunds);
+            assert_eq!(
+                refunds
+                    .iter()
+                    .map(|refund| refund.refund_id.as_str())
+                    .collect::<Vec<_>>(),
+                expected_order
+            );
+            refunds.reverse();
+        }
+    }
+
+    #[test]
+    fn test_error_details_on_decline() {
+        assert_eq!(
+            helpers::get_error_details(
+                enums::AttemptStatus::Failure,
+                Some("card_declined".to_string()),
+                Some("Your card was declined".to_string()),
+                Some("stripe".to_string()),
+            ),
+            Some(api_models::payments::ErrorDetails {
+                code: Some("card_declined".to_string()),
+                message: Some("Your card was declined".to_string()),
+                decline_category: api_models::payments::DeclineCategory::Declined,
+                connector: Some("stripe".to_string()),
+            })
+        );
+        assert_eq!(
+            helpers::get_error_details(
+                enums::AttemptStatus::Charged,
+                None,
+                None,
+                Some("stripe".to_string()),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_card_details_are_masked_as_per_response_masking_policy() {
+        let card_response =
+            api::PaymentMethodDataResponse::Card(api_models::payments::CardResponse {
+                last4: Some("4242".to_string()),
+                card_type: Some("CREDIT".to_string()),
+                card_network: Some(api_models::enums::CardNetwork::Visa),
+                card_issuer: Some("Issuer Bank".to_string()),
+                card_issuing_country: Some("US".to_string()),
+                card_isin: Some("424242".to_string()),
+                card_exp_month: Some(masking::Secret::new("10".to_string())),
+                card_exp_year: Some(masking::Secret::new("2030".to_string())),
+                card_holder_name: Some(masking::Secret::new("John Doe".to_string())),
+            });
+        let config = ResponseMaskingPolicyConfig {
+            default_policy: ResponseMaskingPolicy::Minimal,
+            merchant_policies: std::collections::HashMap::from([(
+                "merchant_last4".to_string(),
+                ResponseMaskingPolicy::Last4,
+            )]),
+        };
+
+        assert_eq!(
+            helpers::mask_payment_method_data_response(
+                card_response.clone(),
+                config.get_policy("merchant_default"),
+            ),
+            api::PaymentMethodDataResponse::Card(api_models::payments::CardResponse {
+                last4: None,
+                card_type: None,
+                card_network: None,
+                card_issuer: None,
+                card_issuing_country: None,
+                card_isin: None,
+                card_exp_month: None,
+                card_exp_year: None,
+                card_holder_name: None,
+            })
+        );
+        assert_eq!(
+            helpers::mask_payment_method_data_response(
+                card_response,
+                config.get_policy("merchant_last4"),
+            ),
+            api::PaymentMethodDataResponse::Card(api_models::payments::CardResponse {
+                last4: Some("4242".to_string()),
+                card_type: None,
+                card_network: Some(api_models::enums::CardNetwork::Visa),
+                card_issuer: None,
+                card_issuing_country: None,
+                card_isin: None,
+                card_exp_month: None,
+                card_exp_year: None,
+                card_holder_name: None,
+            })
+        );
+    }
+}
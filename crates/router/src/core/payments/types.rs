@@ -3,8 +3,10 @@ use std::collections::HashMap;
 use error_stack::{IntoReport, ResultExt};
 
 use crate::{
+    consts,
     core::errors::{self, RouterResult},
     types::storage::{self, enums as storage_enums},
+    utils,
 };
 
 #[derive(Clone, Debug)]
@@ -76,6 +78,13 @@ impl MultipleCaptureData {
                 }
         })
     }
+    /// The part of the authorized amount which is neither captured nor being captured by a
+    /// pending capture, it can still be captured through another partial capture
+    pub fn get_remaining_capturable_amount(&self, authorized_amount: i64) -> i64 {
+        authorized_amount
+            .saturating_sub(self.get_total_blocked_amount())
+            .max(0)
+    }
     pub fn get_total_charged_amount(&self) -> i64 {
         self.all_captures.iter().fold(0, |accumulator, capture| {
             accumulator
@@ -134,6 +143,22 @@ impl MultipleCaptureData {
             .map(|key_value| key_value.1)
             .collect()
     }
+    /// Captures of the attempt in the order they were made
+    pub fn get_captures_in_sequence(&self) -> Vec<&storage::Capture> {
+        let mut captures = self.get_all_captures();
+        captures.sort_by_key(|capture| capture.capture_sequence);
+        captures
+    }
+    /// Captures listed in the payments response. They are listed unless the merchant has
+    /// explicitly opted out with `expand_captures=false`.
+    pub fn get_captures_for_response(&self) -> Option<Vec<storage::Capture>> {
+        self.expand_captures.unwrap_or(true).then(|| {
+            self.get_captures_in_sequence()
+                .into_iter()
+                .cloned()
+                .collect()
+        })
+    }
     pub fn get_capture_by_capture_id(&self, capture_id: String) -> Option<&storage::Capture> {
         self.all_captures.get(&capture_id)
     }
@@ -164,3 +189,230 @@ impl MultipleCaptureData {
             .collect()
     }
 }
+
+/// Identifier of a payment. Ids of new payments are validated to have the `pay_` prefix and to be
+/// within the maximum allowed id length.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentId(String);
+
+impl PaymentId {
+    const PREFIX: &'static str = "pay";
+
+    pub fn generate() -> Self {
+        Self(utils::generate_id(consts::ID_LENGTH, Self::PREFIX))
+    }
+
+    /// Wraps the id of an existing payment without validating it, payments created before the
+    /// `pay_` prefix was enforced have ids without it and must still be found by their id.
+    pub fn from_existing(payment_id: String) -> Self {
+        Self(payment_id)
+    }
+
+    pub fn get_string_repr(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for PaymentId {
+    type Error = error_stack::Report<errors::ApiErrorResponse>;
+
+    fn try_from(payment_id: String) -> Result<Self, Self::Error> {
+        if payment_id.len() > consts::MAX_ID_LENGTH
+            || !payment_id.starts_with(&format!("{}_", Self::PREFIX))
+        {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "payment_id".to_string(),
+                expected_format: format!(
+                    "should start with `{}_` and have at most {} characters",
+                    Self::PREFIX,
+                    consts::MAX_ID_LENGTH
+                ),
+            })
+            .into_report()
+        } else {
+            Ok(Self(payment_id))
+        }
+    }
+}
+
+impl std::fmt::Display for PaymentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::core::payments::helpers;
+
+    fn make_capture(capture_sequence: i16, amount: i64) -> storage::Capture {
+        let now = common_utils::date_time::now();
+        storage::Capture {
+            capture_id: format!("pay_123_1_{capture_sequence}"),
+            payment_id: "pay_123".to_string(),
+            merchant_id: "merchant_123".to_string(),
+            status: storage_enums::CaptureStatus::Started,
+            amount,
+            currency: Some(storage_enums::Currency::USD),
+            connector: "stripe".to_string(),
+            error_message: None,
+            error_code: None,
+            error_reason: None,
+            tax_amount: None,
+            created_at: now,
+            modified_at: now,
+            authorized_attempt_id: "pay_123_1".to_string(),
+            connector_capture_id: None,
+            capture_sequence,
+            connector_response_reference_id: None,
+        }
+    }
+
+    #[test]
+    fn test_partial_captures_summing_to_authorized_amount() {
+        let authorized_amount = 1000;
+
+        let mut first_capture = make_capture(1, 400);
+        let mut multiple_capture_data =
+            MultipleCaptureData::new_for_create(vec![], first_capture.clone());
+        first_capture.status = storage_enums::CaptureStatus::Charged;
+        multiple_capture_data.update_capture(first_capture.clone());
+        assert_eq!(
+            authorized_amount - multiple_capture_data.get_total_blocked_amount(),
+            600
+        );
+        assert_eq!(
+            multiple_capture_data.get_attempt_status(authorized_amount),
+            storage_enums::AttemptStatus::PartialCharged
+        );
+
+        let mut second_capture = make_capture(2, 600);
+        let mut multiple_capture_data =
+            MultipleCaptureData::new_for_create(vec![first_capture], second_capture.clone());
+        second_capture.status = storage_enums::CaptureStatus::Charged;
+        multiple_capture_data.update_capture(second_capture);
+        assert_eq!(
+            authorized_amount - multiple_capture_data.get_total_blocked_amount(),
+            0
+        );
+        assert_eq!(
+            multiple_capture_data.get_attempt_status(authorized_amount),
+            storage_enums::AttemptStatus::Charged
+        );
+    }
+
+    #[test]
+    fn test_captures_are_listed_after_two_partial_captures() {
+        let mut first_capture = make_capture(1, 400);
+        first_capture.status = storage_enums::CaptureStatus::Charged;
+        let mut second_capture = make_capture(2, 300);
+        second_capture.status = storage_enums::CaptureStatus::Charged;
+        let multiple_capture_data =
+            MultipleCaptureData::new_for_create(vec![first_capture], second_capture);
+
+        let captures = multiple_capture_data.get_captures_for_response();
+        assert_eq!(
+            captures.as_ref().map(|captures| captures
+                .iter()
+                .map(|capture| (capture.capture_sequence, capture.amount))
+                .collect::<Vec<_>>()),
+            Some(vec![(1, 400), (2, 300)])
+        );
+
+        let multiple_capture_data =
+            MultipleCaptureData::new_for_sync(captures.unwrap_or_default(), Some(false)).unwrap();
+        assert_eq!(multiple_capture_data.get_captures_for_response(), None);
+    }
+
+    #[test]
+    fn test_second_partial_capture_while_first_is_pending() {
+        let authorized_amount = 1000;
+
+        let mut first_capture = make_capture(1, 400);
+        let mut multiple_capture_data =
+            MultipleCaptureData::new_for_create(vec![], first_capture.clone());
+        first_capture.status = storage_enums::CaptureStatus::Pending;
+        multiple_capture_data.update_capture(first_capture.clone());
+        assert_eq!(
+            multiple_capture_data.get_attempt_status(authorized_amount),
+            storage_enums::AttemptStatus::CaptureInitiated
+        );
+        assert_eq!(
+            multiple_capture_data.get_remaining_capturable_amount(authorized_amount),
+            600
+        );
+
+        let mut second_capture = make_capture(2, 500);
+        let mut multiple_capture_data =
+            MultipleCaptureData::new_for_create(vec![first_capture], second_capture.clone());
+        second_capture.status = storage_enums::CaptureStatus::Pending;
+        multiple_capture_data.update_capture(second_capture.clone());
+        assert_eq!(
+            multiple_capture_data.get_remaining_capturable_amount(authorized_amount),
+            100
+        );
+
+        // A failed capture releases its amount
+        second_capture.status = storage_enums::CaptureStatus::Failed;
+        multiple_capture_data.update_capture(second_capture);
+        assert_eq!(
+            multiple_capture_data.get_remaining_capturable_amount(authorized_amount),
+            600
+        );
+    }
+
+    #[test]
+    fn test_payment_id_prefix_validation() {
+        assert!(PaymentId::try_from("pay_mbabizu24mvu3mela5njyhpit10".to_string()).is_ok());
+        assert!(PaymentId::try_from("mbabizu24mvu3mela5njyhpit10".to_string()).is_err());
+        assert!(PaymentId::try_from("ref_mbabizu24mvu3mela5njyhpit10".to_string()).is_err());
+        assert!(PaymentId::try_from(PaymentId::generate().to_string()).is_ok());
+        assert!(PaymentId::try_from(format!("pay_{}", "a".repeat(consts::MAX_ID_LENGTH))).is_err());
+        // Existing payments are found by their id even if it doesn't have the prefix
+        assert_eq!(
+            PaymentId::from_existing("mbabizu24mvu3mela5njyhpit10".to_string()).get_string_repr(),
+            "mbabizu24mvu3mela5njyhpit10"
+        );
+    }
+
+    #[test]
+    fn test_remaining_capturable_amount() {
+        // Fully capturable
+        assert_eq!(
+            helpers::get_remaining_capturable_amount(
+                storage_enums::AttemptStatus::Authorized,
+                6540,
+                0
+            ),
+            6540
+        );
+        // Partially captured
+        assert_eq!(
+            helpers::get_remaining_capturable_amount(
+                storage_enums::AttemptStatus::PartialCharged,
+                6540,
+                1000
+            ),
+            5540
+        );
+        // Fully captured
+        assert_eq!(
+            helpers::get_remaining_capturable_amount(
+                storage_enums::AttemptStatus::Charged,
+                6540,
+                6540
+            ),
+            0
+        );
+        assert_eq!(
+            helpers::get_remaining_capturable_amount(
+                storage_enums::AttemptStatus::PartialCharged,
+                6540,
+                6540
+            ),
+            0
+        );
+    }
+}
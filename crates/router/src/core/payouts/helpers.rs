@@ -11,7 +11,10 @@ use crate::{
             transformers::{StoreCardReq, StoreGenericReq, StoreLockerReq},
             vault,
         },
-        payments::{customers::get_connector_customer_details_if_present, CustomerDetails},
+        payments::{
+            customers::get_connector_customer_details_if_present, helpers as payments_helpers,
+            CustomerDetails,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -300,7 +303,7 @@ pub async fn get_or_create_customer_details(
             };
 
             Ok(Some(
-                db.insert_customer(customer, key_store)
+                payments_helpers::insert_customer_if_not_exists(db, customer, key_store)
                     .await
                     .change_context(errors::ApiErrorResponse::InternalServerError)?,
             ))
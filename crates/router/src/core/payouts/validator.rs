@@ -5,13 +5,12 @@ use super::helpers;
 use crate::{
     core::{
         errors::{self, RouterResult},
-        utils as core_utils,
+        payments, utils as core_utils,
     },
     db::StorageInterface,
     logger,
     routes::AppState,
     types::{api::payouts, domain, storage},
-    utils,
 };
 
 #[cfg(feature = "payouts")]
@@ -61,14 +60,7 @@ pub async fn validate_create_request(
     let merchant_id = &merchant_account.merchant_id;
 
     // Merchant ID
-    let predicate = req.merchant_id.as_ref().map(|mid| mid != merchant_id);
-    utils::when(predicate.unwrap_or(false), || {
-        Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
-            field_name: "merchant_id".to_string(),
-            expected_format: "merchant_id from merchant account".to_string(),
-        })
-        .attach_printable("invalid merchant_id in request"))
-    })?;
+    payments::helpers::validate_merchant_id(merchant_id, req.merchant_id.as_deref())?;
 
     // Payout ID
     let db: &dyn StorageInterface = &*state.store;
@@ -50,7 +50,10 @@ pub async fn refund_create_core(
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
     utils::when(
-        payment_intent.status != enums::IntentStatus::Succeeded,
+        !matches!(
+            payment_intent.status,
+            enums::IntentStatus::Succeeded | enums::IntentStatus::PartiallyCaptured
+        ),
         || {
             Err(report!(errors::ApiErrorResponse::PaymentNotSucceeded)
                 .attach_printable("unable to refund for a unsuccessful payment intent"))
@@ -520,18 +523,10 @@ pub async fn validate_and_create_refund(
 
     refund_id = core_utils::get_or_generate_id("refund_id", &req.refund_id, "ref")?;
 
-    let predicate = req
-        .merchant_id
-        .as_ref()
-        .map(|merchant_id| merchant_id != &merchant_account.merchant_id);
-
-    utils::when(predicate.unwrap_or(false), || {
-        Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
-            field_name: "merchant_id".to_string(),
-            expected_format: "merchant_id from merchant account".to_string()
-        })
-        .attach_printable("invalid merchant_id in request"))
-    })?;
+    payments::helpers::validate_merchant_id(
+        &merchant_account.merchant_id,
+        req.merchant_id.as_deref(),
+    )?;
 
     let refund = match validator::validate_uniqueness_of_refund_id_against_merchant_id(
         db,
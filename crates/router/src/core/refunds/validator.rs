@@ -23,7 +23,7 @@ pub enum RefundValidationError {
     RefundAmountExceedsPaymentAmount,
     #[error("The order has expired")]
     OrderExpired,
-    #[error("The maximum refund count for this payment attempt")]
+    #[error("The maximum refund count for this payment attempt has been reached")]
     MaxRefundCountReached,
     #[error("There is already another refund request for this payment attempt")]
     DuplicateRefund,
@@ -82,12 +82,14 @@ pub fn validate_payment_order_age(
     )
 }
 
+/// Refunds are limited to `refund_max_attempts` per payment attempt, irrespective of the amount
+/// which is left to be refunded
 #[instrument(skip_all)]
 pub fn validate_maximum_refund_against_payment_attempt(
     all_refunds: &[storage::Refund],
     refund_max_attempts: usize,
 ) -> CustomResult<(), RefundValidationError> {
-    utils::when(all_refunds.len() > refund_max_attempts, || {
+    utils::when(all_refunds.len() >= refund_max_attempts, || {
         Err(report!(RefundValidationError::MaxRefundCountReached))
     })
 }
@@ -181,3 +183,59 @@ pub fn validate_for_valid_refunds(
         _ => Ok(()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_refund(refund_id: &str, refund_amount: i64) -> storage::Refund {
+        storage::Refund {
+            id: 1,
+            internal_reference_id: format!("refid_{refund_id}"),
+            refund_id: refund_id.to_string(),
+            payment_id: "pay_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            connector_transaction_id: "connector_txn_1".to_string(),
+            connector: "stripe".to_string(),
+            connector_refund_id: None,
+            external_reference_id: None,
+            refund_type: enums::RefundType::InstantRefund,
+            total_amount: 1000,
+            currency: enums::Currency::USD,
+            refund_amount,
+            refund_status: enums::RefundStatus::Success,
+            sent_to_gateway: true,
+            refund_error_message: None,
+            metadata: None,
+            refund_arn: None,
+            created_at: common_utils::date_time::now(),
+            updated_at: common_utils::date_time::now(),
+            description: None,
+            attempt_id: "pay_1_1".to_string(),
+            refund_reason: None,
+            refund_error_code: None,
+            profile_id: None,
+        }
+    }
+
+    #[test]
+    fn test_refund_count_cap_is_enforced_when_amount_remains() {
+        let refund_max_attempts = 2;
+        let all_refunds = vec![get_refund("ref_1", 100)];
+        assert!(
+            validate_maximum_refund_against_payment_attempt(&all_refunds, refund_max_attempts)
+                .is_ok()
+        );
+
+        let all_refunds = vec![get_refund("ref_1", 100), get_refund("ref_2", 100)];
+        // The payment still has an amount left to be refunded
+        assert!(validate_refund_amount(1000, &all_refunds, 100).is_ok());
+        assert!(matches!(
+            validate_maximum_refund_against_payment_attempt(&all_refunds, refund_max_attempts)
+                .as_ref()
+                .map_err(|err| err.current_context()),
+            Err(RefundValidationError::MaxRefundCountReached)
+        ));
+    }
+}
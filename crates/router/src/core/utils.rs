@@ -184,6 +184,13 @@ pub async fn construct_payout_router_data<'a, F>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
 
@@ -334,6 +341,13 @@ pub async fn construct_refund_router_data<'a, F>(
         connector_api_version,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
 
@@ -388,8 +402,92 @@ pub fn validate_uuid(uuid: String, key: &str) -> Result<String, errors::ApiError
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
 
+    #[tokio::test]
+    async fn test_connector_request_reference_id_is_stable_across_retries() {
+        use data_models::payments::payment_attempt::PaymentAttemptInterface;
+
+        let conf = settings::Settings::default();
+        let mockdb = storage_impl::MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let connector_request_reference_id = generate_connector_request_reference_id(
+            &conf.connector_request_reference_id_config,
+            "merchant_1",
+            "pay_1",
+            "pay_1_1",
+        );
+        assert_eq!(connector_request_reference_id, "pay_1_1");
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    connector_request_reference_id: Some(connector_request_reference_id.clone()),
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            get_connector_request_reference_id(&conf, "merchant_1", &payment_attempt),
+            connector_request_reference_id
+        );
+
+        // The attempt is updated when the connector call is retried
+        let retried_payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::StatusUpdate {
+                    status: enums::AttemptStatus::Pending,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            get_connector_request_reference_id(&conf, "merchant_1", &retried_payment_attempt),
+            connector_request_reference_id
+        );
+    }
+
+    #[test]
+    fn test_attempt_id_prefix_is_added_for_merchant() {
+        let config = settings::ConnectorRequestReferenceIdConfig {
+            merchant_ids_send_payment_id_as_connector_request_id: Default::default(),
+            merchant_attempt_id_prefixes: std::collections::HashMap::from([(
+                "merchant_1".to_string(),
+                "acme".to_string(),
+            )]),
+        };
+
+        let first_attempt_id = add_merchant_attempt_id_prefix(
+            &config,
+            "merchant_1",
+            crate::utils::get_payment_attempt_id("pay_1", 1),
+        );
+        let second_attempt_id = add_merchant_attempt_id_prefix(
+            &config,
+            "merchant_1",
+            crate::utils::get_payment_attempt_id("pay_2", 1),
+        );
+        assert!(matches!(&first_attempt_id, Ok(attempt_id) if attempt_id == "acme_pay_1_1"));
+        assert!(matches!(&second_attempt_id, Ok(attempt_id) if attempt_id == "acme_pay_2_1"));
+        assert_ne!(first_attempt_id.ok(), second_attempt_id.ok());
+
+        let attempt_id_without_prefix = add_merchant_attempt_id_prefix(
+            &config,
+            "merchant_2",
+            crate::utils::get_payment_attempt_id("pay_1", 1),
+        );
+        assert!(matches!(attempt_id_without_prefix, Ok(attempt_id) if attempt_id == "pay_1_1"));
+    }
+
     #[test]
     fn validate_id_length_constraint() {
         let payment_id =
@@ -414,6 +512,22 @@ mod tests {
         let generated_id = generate_id(consts::ID_LENGTH, "ref");
         assert_eq!(generated_id.len(), consts::ID_LENGTH + 4)
     }
+
+    #[test]
+    fn test_amount_rounding_modes_on_boundary_amount() {
+        use settings::RoundingMode::{HalfEven, HalfUp};
+
+        assert_eq!(round_amount(12505, 10, HalfUp), Some(1251));
+        assert_eq!(round_amount(12505, 10, HalfEven), Some(1250));
+        assert_eq!(round_amount(12515, 10, HalfUp), Some(1252));
+        assert_eq!(round_amount(12515, 10, HalfEven), Some(1252));
+        assert_eq!(round_amount(-12505, 10, HalfUp), Some(-1251));
+        assert_eq!(round_amount(-12505, 10, HalfEven), Some(-1250));
+        assert_eq!(round_amount(12504, 10, HalfEven), Some(1250));
+        assert_eq!(round_amount(12506, 10, HalfEven), Some(1251));
+        assert_eq!(round_amount(1, 0, HalfUp), None);
+        assert_eq!(round_amount(i128::MAX, 1, HalfUp), None);
+    }
 }
 
 // Dispute Stage can move linearly from PreDispute -> Dispute -> PreArbitration
@@ -562,6 +676,13 @@ pub async fn construct_accept_dispute_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -648,6 +769,13 @@ pub async fn construct_submit_evidence_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -739,6 +867,13 @@ pub async fn construct_upload_file_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -828,6 +963,13 @@ pub async fn construct_defend_dispute_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -910,6 +1052,13 @@ pub async fn construct_retrieve_file_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -925,19 +1074,69 @@ pub fn is_merchant_enabled_for_payment_id_as_connector_request_id(
     config_map.contains(merchant_id)
 }
 
+/// Adds the prefix configured for the merchant to the attempt id. Since the prefix is added to
+/// an attempt id which is already unique, the prefixed attempt ids remain unique.
+pub fn add_merchant_attempt_id_prefix(
+    config: &settings::ConnectorRequestReferenceIdConfig,
+    merchant_id: &str,
+    attempt_id: String,
+) -> RouterResult<String> {
+    match config.merchant_attempt_id_prefixes.get(merchant_id) {
+        Some(prefix) => {
+            let prefixed_attempt_id = format!("{prefix}_{attempt_id}");
+            crate::utils::when(prefixed_attempt_id.len() > consts::MAX_ID_LENGTH, || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "payment_id is too long to add the attempt id prefix `{prefix}`, the attempt id cannot exceed {} characters",
+                        consts::MAX_ID_LENGTH
+                    ),
+                }))
+            })?;
+            Ok(prefixed_attempt_id)
+        }
+        None => Ok(attempt_id),
+    }
+}
+
+/// Generates the reference sent to the connector for an attempt, the reference is stored on the
+/// attempt when it is created. The attempt_id is sent by default, since webhooks and syncs find
+/// the attempt by the reference returned by the connector, unless the merchant is configured to
+/// send the payment_id as the reference.
+pub fn generate_connector_request_reference_id(
+    config: &settings::ConnectorRequestReferenceIdConfig,
+    merchant_id: &str,
+    payment_id: &str,
+    attempt_id: &str,
+) -> String {
+    if config
+        .merchant_ids_send_payment_id_as_connector_request_id
+        .contains(merchant_id)
+    {
+        payment_id.to_string()
+    } else {
+        attempt_id.to_string()
+    }
+}
+
+/// The reference stored on the attempt is sent to the connector, so that every request made for
+/// the attempt, including its retries, carries the same reference. Attempts created before the
+/// reference was stored fall back to the generated reference.
 pub fn get_connector_request_reference_id(
     conf: &settings::Settings,
     merchant_id: &str,
     payment_attempt: &data_models::payments::payment_attempt::PaymentAttempt,
 ) -> String {
-    let is_config_enabled_for_merchant =
-        is_merchant_enabled_for_payment_id_as_connector_request_id(conf, merchant_id);
-    // Send payment_id if config is enabled for a merchant, else send attempt_id
-    if is_config_enabled_for_merchant {
-        payment_attempt.payment_id.clone()
-    } else {
-        payment_attempt.attempt_id.clone()
-    }
+    payment_attempt
+        .connector_request_reference_id
+        .clone()
+        .unwrap_or_else(|| {
+            generate_connector_request_reference_id(
+                &conf.connector_request_reference_id_config,
+                merchant_id,
+                &payment_attempt.payment_id,
+                &payment_attempt.attempt_id,
+            )
+        })
 }
 
 /// Validate whether the profile_id exists and is associated with the merchant_id
@@ -1065,3 +1264,30 @@ pub fn get_flow_name<F>() -> RouterResult<String> {
         .attach_printable("Flow stringify failed")?
         .to_string())
 }
+
+/// Rounds the amount `numerator / denominator`, computed from a fractional value, to the lowest
+/// denomination of the currency using the rounding mode configured for the application. The
+/// division is done on integers so that no precision is lost. Returns `None` if the denominator
+/// is not positive or the rounded amount does not fit an `i64`.
+pub fn round_amount(
+    numerator: i128,
+    denominator: i128,
+    rounding_mode: settings::RoundingMode,
+) -> Option<i64> {
+    if denominator <= 0 {
+        return None;
+    }
+    let quotient = numerator / denominator;
+    let twice_remainder = (numerator % denominator).checked_abs()?.checked_mul(2)?;
+    let away_from_zero = quotient + numerator.signum();
+    let rounded_amount = match twice_remainder.cmp(&denominator) {
+        std::cmp::Ordering::Less => quotient,
+        std::cmp::Ordering::Greater => away_from_zero,
+        std::cmp::Ordering::Equal => match rounding_mode {
+            settings::RoundingMode::HalfUp => away_from_zero,
+            settings::RoundingMode::HalfEven if quotient % 2 == 0 => quotient,
+            settings::RoundingMode::HalfEven => away_from_zero,
+        },
+    };
+    i64::try_from(rounded_amount).ok()
+}
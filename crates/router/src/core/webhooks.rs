@@ -88,6 +88,7 @@ pub async fn payments_incoming_webhook_flow<
                         resource_id: id,
                         merchant_id: Some(merchant_account.merchant_id.clone()),
                         force_sync: true,
+                        force_refresh: true,
                         connector: None,
                         param: None,
                         merchant_connector_details: None,
@@ -120,6 +120,13 @@ pub async fn construct_webhook_router_data<'a>(
         connector_api_version: None,
         connector_http_status_code: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
         apple_pay_flow: None,
     };
     Ok(router_data)
@@ -637,7 +637,7 @@ impl AddressInterface for MockDb {
 
     async fn find_address_by_merchant_id_payment_id_address_id(
         &self,
-        _merchant_id: &str,
+        merchant_id: &str,
         _payment_id: &str,
         address_id: &str,
         key_store: &domain::MerchantKeyStore,
@@ -648,7 +648,7 @@ impl AddressInterface for MockDb {
             .lock()
             .await
             .iter()
-            .find(|address| address.address_id == address_id)
+            .find(|address| address.address_id == address_id && address.merchant_id == merchant_id)
         {
             Some(address) => address
                 .clone()
@@ -147,6 +147,12 @@ mod storage {
                             .clone(),
                         authentication_data: connector_response.authentication_data.clone(),
                         encoded_data: connector_response.encoded_data.clone(),
+                        connector_io_log: connector_response.connector_io_log.clone(),
+                        three_ds_version: connector_response.three_ds_version.clone(),
+                        liability_shift: connector_response.liability_shift,
+                        authorization_code: connector_response.authorization_code.clone(),
+                        settled_at: connector_response.settled_at,
+                        challenge_required: connector_response.challenge_required,
                     };
 
                     match kv_wrapper::<storage_type::ConnectorResponse, _, _>(
@@ -320,6 +326,12 @@ impl ConnectorResponseInterface for MockDb {
             connector_transaction_id: new.connector_transaction_id,
             authentication_data: new.authentication_data,
             encoded_data: new.encoded_data,
+            connector_io_log: new.connector_io_log,
+            three_ds_version: new.three_ds_version,
+            liability_shift: new.liability_shift,
+            authorization_code: new.authorization_code,
+            settled_at: new.settled_at,
+            challenge_required: new.challenge_required,
         };
         connector_response.push(response.clone());
         Ok(response)
@@ -328,13 +340,25 @@ impl ConnectorResponseInterface for MockDb {
     #[instrument(skip_all)]
     async fn find_connector_response_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_type::ConnectorResponse, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.connector_response
+            .lock()
+            .await
+            .iter()
+            .find(|connector_response| {
+                connector_response.payment_id == payment_id
+                    && connector_response.merchant_id == merchant_id
+                    && connector_response.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound("connector response not found".to_string())
+                    .into()
+            })
     }
 
     // safety: interface only used for testing
@@ -354,3 +378,225 @@ impl ConnectorResponseInterface for MockDb {
         Ok(response.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel_models::enums;
+
+    use crate::{
+        db::{connector_response::ConnectorResponseInterface, MockDb},
+        types::storage,
+    };
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_three_ds_result_is_persisted_on_connector_response() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        let now = common_utils::date_time::now();
+        let connector_response = mockdb
+            .insert_connector_response(
+                storage::ConnectorResponseNew {
+                    payment_id: "pay_three_ds".to_string(),
+                    merchant_id: "merchant_three_ds".to_string(),
+                    attempt_id: "pay_three_ds_1".to_string(),
+                    created_at: now,
+                    modified_at: now,
+                    connector_name: Some("adyen".to_string()),
+                    connector_transaction_id: None,
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        mockdb
+            .update_connector_response(
+                connector_response,
+                storage::ConnectorResponseUpdate::ResponseUpdate {
+                    connector_transaction_id: Some("connector_txn_id".to_string()),
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_name: Some("adyen".to_string()),
+                    connector_io_log: None,
+                    three_ds_version: Some("2.2.0".to_string()),
+                    liability_shift: Some(true),
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let connector_response = mockdb
+            .find_connector_response_by_payment_id_merchant_id_attempt_id(
+                "pay_three_ds",
+                "merchant_three_ds",
+                "pay_three_ds_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            connector_response.three_ds_version.as_deref(),
+            Some("2.2.0")
+        );
+        assert_eq!(connector_response.liability_shift, Some(true));
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_authorization_code_is_persisted_on_connector_response() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        let now = common_utils::date_time::now();
+        let connector_response = mockdb
+            .insert_connector_response(
+                storage::ConnectorResponseNew {
+                    payment_id: "pay_auth_code".to_string(),
+                    merchant_id: "merchant_auth_code".to_string(),
+                    attempt_id: "pay_auth_code_1".to_string(),
+                    created_at: now,
+                    modified_at: now,
+                    connector_name: Some("adyen".to_string()),
+                    connector_transaction_id: None,
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(connector_response.authorization_code, None);
+
+        mockdb
+            .update_connector_response(
+                connector_response,
+                storage::ConnectorResponseUpdate::ResponseUpdate {
+                    connector_transaction_id: Some("connector_txn_id".to_string()),
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_name: Some("adyen".to_string()),
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: Some("A1B2C3".to_string()),
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let connector_response = mockdb
+            .find_connector_response_by_payment_id_merchant_id_attempt_id(
+                "pay_auth_code",
+                "merchant_auth_code",
+                "pay_auth_code_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            connector_response.authorization_code.as_deref(),
+            Some("A1B2C3")
+        );
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_settlement_date_is_persisted_on_connector_response() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        let now = common_utils::date_time::now();
+        let connector_response = mockdb
+            .insert_connector_response(
+                storage::ConnectorResponseNew {
+                    payment_id: "pay_settled".to_string(),
+                    merchant_id: "merchant_settled".to_string(),
+                    attempt_id: "pay_settled_1".to_string(),
+                    created_at: now,
+                    modified_at: now,
+                    connector_name: Some("adyen".to_string()),
+                    connector_transaction_id: None,
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: None,
+                    settled_at: None,
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(connector_response.settled_at, None);
+
+        let settled_at = time::macros::datetime!(2023-10-26 10:11:12);
+        mockdb
+            .update_connector_response(
+                connector_response,
+                storage::ConnectorResponseUpdate::ResponseUpdate {
+                    connector_transaction_id: Some("connector_txn_id".to_string()),
+                    authentication_data: None,
+                    encoded_data: None,
+                    connector_name: Some("adyen".to_string()),
+                    connector_io_log: None,
+                    three_ds_version: None,
+                    liability_shift: None,
+                    authorization_code: Some("A1B2C3".to_string()),
+                    settled_at: Some(settled_at),
+                    challenge_required: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let connector_response = mockdb
+            .find_connector_response_by_payment_id_merchant_id_attempt_id(
+                "pay_settled",
+                "merchant_settled",
+                "pay_settled_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(connector_response.settled_at, Some(settled_at));
+        assert_eq!(
+            connector_response.authorization_code.as_deref(),
+            Some("A1B2C3")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use common_utils::ext_traits::AsyncExt;
+use diesel_models::errors::DatabaseError;
 use error_stack::{IntoReport, ResultExt};
 use futures::future::try_join_all;
 use masking::PeekInterface;
@@ -279,12 +280,15 @@ impl CustomerInterface for MockDb {
 
     async fn find_customer_by_customer_id_merchant_id(
         &self,
-        _customer_id: &str,
-        _merchant_id: &str,
-        _key_store: &domain::MerchantKeyStore,
+        customer_id: &str,
+        merchant_id: &str,
+        key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.find_customer_optional_by_customer_id_merchant_id(customer_id, merchant_id, key_store)
+            .await?
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
     }
 
     #[allow(clippy::panic)]
@@ -295,6 +299,15 @@ impl CustomerInterface for MockDb {
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         let mut customers = self.customers.lock().await;
 
+        if customers.iter().any(|customer| {
+            customer.customer_id == customer_data.customer_id
+                && customer.merchant_id == customer_data.merchant_id
+        }) {
+            Err(errors::StorageError::DatabaseError(
+                DatabaseError::UniqueViolation.into(),
+            ))?;
+        }
+
         let customer = Conversion::convert(customer_data)
             .await
             .change_context(errors::StorageError::EncryptionError)?;
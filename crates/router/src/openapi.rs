@@ -89,6 +89,10 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payments::payments_connector_session,
     // crate::routes::payments::payments_redirect_response,
         crate::routes::payments::payments_cancel,
+        crate::routes::payments::payments_reverse,
+        crate::routes::payments::payments_retry,
+        crate::routes::payments::payments_hosted_page,
+        crate::routes::payments::payments_status_polling,
         crate::routes::payments::payments_list,
         crate::routes::payment_methods::create_payment_method_api,
         crate::routes::payment_methods::list_payment_method_api,
@@ -102,6 +106,7 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::customers::customers_update,
         crate::routes::customers::customers_delete,
         crate::routes::customers::customers_list,
+        crate::routes::customers::get_customer_payments,
         // crate::routes::api_keys::api_key_create,
         // crate::routes::api_keys::api_key_retrieve,
         // crate::routes::api_keys::api_key_update,
@@ -140,6 +145,8 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::payment_methods::CardDetailFromLocker,
         crate::types::api::payment_methods::CardDetail,
         api_models::customers::CustomerResponse,
+        api_models::customers::CustomerPaymentSummary,
+        api_models::customers::CustomerPaymentsListResponse,
         api_models::admin::AcceptedCountries,
         api_models::admin::AcceptedCurrencies,
         api_models::enums::RoutingAlgorithm,
@@ -150,6 +157,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::PayoutConnectors,
         api_models::enums::Currency,
         api_models::enums::IntentStatus,
+        api_models::enums::PaymentRefundStatus,
         api_models::enums::CaptureMethod,
         api_models::enums::FutureUsage,
         api_models::enums::AuthenticationType,
@@ -158,6 +166,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::PaymentMethodIssuerCode,
         api_models::enums::MandateStatus,
         api_models::enums::PaymentExperience,
+        api_models::enums::PaymentChannel,
         api_models::enums::BankNames,
         api_models::enums::CardNetwork,
         api_models::enums::DisputeStage,
@@ -240,9 +249,11 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::PaymentsResponse,
         api_models::payments::PaymentsStartRequest,
         api_models::payments::PaymentRetrieveBody,
+        api_models::payments::PaymentRetrieveExpand,
         api_models::payments::PaymentsRetrieveRequest,
         api_models::payments::PaymentIdType,
         api_models::payments::PaymentsCaptureRequest,
+        api_models::payments::DccOffer,
         api_models::payments::PaymentsSessionRequest,
         api_models::payments::PaymentsSessionResponse,
         api_models::payments::SessionToken,
@@ -275,8 +286,14 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::ApplePayWalletData,
         api_models::payments::ApplepayPaymentMethod,
         api_models::payments::PaymentsCancelRequest,
+        api_models::payments::PaymentsRetryRequest,
+        api_models::payments::PaymentsStatusPollingRequest,
         api_models::payments::PaymentListConstraints,
         api_models::payments::PaymentListResponse,
+        api_models::payments::HostedPaymentPageResponse,
+        api_models::payments::ErrorDetails,
+        api_models::payments::SdkInfo,
+        api_models::payments::DeclineCategory,
         api_models::payments::CashappQr,
         api_models::payments::BankTransferData,
         api_models::payments::BankTransferNextStepsData,
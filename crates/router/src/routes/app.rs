@@ -249,6 +249,20 @@ impl Payments {
                 .service(
                     web::resource("/{payment_id}/cancel").route(web::post().to(payments_cancel)),
                 )
+                .service(
+                    web::resource("/{payment_id}/reverse").route(web::post().to(payments_reverse)),
+                )
+                .service(
+                    web::resource("/{payment_id}/retry").route(web::post().to(payments_retry)),
+                )
+                .service(
+                    web::resource("/{payment_id}/hosted_page")
+                        .route(web::get().to(payments_hosted_page)),
+                )
+                .service(
+                    web::resource("/{payment_id}/poll_status")
+                        .route(web::get().to(payments_status_polling)),
+                )
                 .service(
                     web::resource("/{payment_id}/capture").route(web::post().to(payments_capture)),
                 )
@@ -291,6 +305,10 @@ impl Customers {
                     web::resource("/{customer_id}/mandates")
                         .route(web::get().to(get_customer_mandates)),
                 )
+                .service(
+                    web::resource("/{customer_id}/payments")
+                        .route(web::get().to(get_customer_payments)),
+                )
                 .service(web::resource("/list").route(web::get().to(customers_list)))
         }
 
@@ -214,3 +214,54 @@ pub async fn get_customer_mandates(
     )
     .await
 }
+
+/// List payments of a Customer
+///
+/// To list the payments made by a customer, the most recent payment comes first
+#[utoipa::path(
+    get,
+    path = "/customers/{customer_id}/payments",
+    params (
+        ("customer_id" = String, Path, description = "The unique identifier for the Customer"),
+        ("limit" = Option<u32>, Query, description = "The maximum number of payments to be returned"),
+        ("offset" = Option<u32>, Query, description = "The number of payments to skip"),
+    ),
+    responses(
+        (status = 200, description = "Payments of the Customer retrieved", body = CustomerPaymentsListResponse),
+        (status = 404, description = "Customer was not found")
+    ),
+    tag = "Customers",
+    operation_id = "List all Payments for a Customer",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomersGetPayments))]
+pub async fn get_customer_payments(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<customers::CustomerPaymentsListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::CustomersGetPayments;
+    let customer_id = customers::CustomerId {
+        customer_id: path.into_inner(),
+    };
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (customer_id, query_payload.into_inner()),
+        |state, auth, (customer_id, constraints)| {
+            list_customer_payments(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                customer_id,
+                constraints,
+            )
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -13,6 +13,7 @@ use crate::{
         payment_methods::{Oss, PaymentMethodRetrieve},
         payments::{self, PaymentRedirectFlow},
     },
+    headers,
     openapi::examples::{
         PAYMENTS_CREATE, PAYMENTS_CREATE_MINIMUM_FIELDS, PAYMENTS_CREATE_WITH_ADDRESS,
         PAYMENTS_CREATE_WITH_CUSTOMER_DATA, PAYMENTS_CREATE_WITH_FORCED_3DS,
@@ -100,6 +101,11 @@ pub async fn payments_create(
     };
 
     let locking_action = payload.get_locking_input(flow.clone());
+    let idempotency_key = req
+        .headers()
+        .get(headers::IDEMPOTENCY_KEY)
+        .and_then(|idempotency_key| idempotency_key.to_str().ok())
+        .map(ToOwned::to_owned);
 
     api::server_wrap(
         flow,
@@ -107,15 +113,28 @@ pub async fn payments_create(
         &req,
         payload,
         |state, auth, req| {
-            authorize_verify_select::<_, Oss>(
-                payments::PaymentCreate,
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payment_types::HeaderPayload::default(),
-                req,
-                api::AuthFlow::Merchant,
-            )
+            let idempotency_key = idempotency_key.clone();
+            async move {
+                let merchant_id = auth.merchant_account.merchant_id.clone();
+                let db = state.store.clone();
+                let request = req.clone();
+                payments::idempotency::create_with_idempotency(
+                    &*db,
+                    &merchant_id,
+                    idempotency_key.as_deref(),
+                    &request,
+                    authorize_verify_select::<_, Oss>(
+                        payments::PaymentCreate,
+                        state,
+                        auth.merchant_account,
+                        auth.key_store,
+                        payment_types::HeaderPayload::default(),
+                        req,
+                        api::AuthFlow::Merchant,
+                    ),
+                )
+                .await
+            }
         },
         &auth::ApiKeyAuth,
         locking_action,
@@ -216,9 +235,10 @@ pub async fn payments_retrieve(
         resource_id: payment_types::PaymentIdType::PaymentIntentId(path.to_string()),
         merchant_id: json_payload.merchant_id.clone(),
         force_sync: json_payload.force_sync.unwrap_or(false),
+        force_refresh: json_payload.force_refresh.unwrap_or(false),
         client_secret: json_payload.client_secret.clone(),
-        expand_attempts: json_payload.expand_attempts,
-        expand_captures: json_payload.expand_captures,
+        expand_attempts: json_payload.get_expand_attempts(),
+        expand_captures: json_payload.get_expand_captures(),
         ..Default::default()
     };
     let (auth_type, auth_flow) =
@@ -781,6 +801,193 @@ pub async fn payments_cancel(
     )
     .await
 }
+/// Payments - Reverse
+///
+/// A captured payment can be reversed before it is settled, the payment is refunded instead if the connector doesn't support reversing captures
+#[utoipa::path(
+    post,
+    path = "/payments/{payment_id}/reverse",
+    request_body=PaymentsCancelRequest,
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "Payment reversed"),
+        (status = 400, description = "Missing mandatory fields")
+    ),
+    tag = "Payments",
+    operation_id = "Reverse a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsReverse))]
+// #[post("/{payment_id}/reverse")]
+pub async fn payments_reverse(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsCancelRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsReverse;
+    let mut payload = json_payload.into_inner();
+    let payment_id = path.into_inner();
+    payload.payment_id = payment_id;
+    let locking_action = payload.get_locking_input(flow.clone());
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            payments::payments_reverse_core::<Oss>(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+        locking_action,
+    )
+    .await
+}
+/// Payments - Retry
+///
+/// A failed payment can be retried on the connector of its failed attempt with the same payment method, a new attempt is made under the same payment
+#[utoipa::path(
+    post,
+    path = "/payments/{payment_id}/retry",
+    request_body=PaymentsRetryRequest,
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "Payment retried", body = PaymentsResponse),
+        (status = 400, description = "Missing mandatory fields")
+    ),
+    tag = "Payments",
+    operation_id = "Retry a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsRetry))]
+// #[post("/{payment_id}/retry")]
+pub async fn payments_retry(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsRetryRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsRetry;
+    let mut payload = json_payload.into_inner();
+    let payment_id = path.into_inner();
+    payload.payment_id = payment_id;
+    let locking_action = payload.get_locking_input(flow.clone());
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            payments::payments_retry_core::<Oss>(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+        locking_action,
+    )
+    .await
+}
+/// Payments - Hosted Page
+///
+/// To get the url of the hosted checkout page of a payment, where the customer can complete the payment without the merchant having a payment page
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/hosted_page",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "Hosted payment page url created", body = HostedPaymentPageResponse),
+        (status = 404, description = "No payment found")
+    ),
+    tag = "Payments",
+    operation_id = "Get the Hosted Page of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsHostedPage))]
+// #[get("/{payment_id}/hosted_page")]
+pub async fn payments_hosted_page(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsHostedPage;
+    let payment_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payment_id,
+        |state, auth, payment_id| {
+            payments::hosted_payment_page::get_hosted_payment_page(
+                state,
+                auth.merchant_account,
+                payment_id,
+            )
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Payments - Poll Status
+///
+/// To sync the status of a payment being processed by the connector until the connector completes it, with a backoff between the syncs. The payment is returned once its status no longer changes by syncing or the polls are exhausted
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/poll_status",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment"),
+        ("max_polls" = Option<u32>, Query, description = "Maximum number of times the payment is synced with the connector"),
+        ("interval_in_milliseconds" = Option<u64>, Query, description = "Delay before the second sync with the connector, doubled after every subsequent sync")
+    ),
+    responses(
+        (status = 200, description = "Gets the payment with the status after polling", body = PaymentsResponse),
+        (status = 404, description = "No payment found")
+    ),
+    tag = "Payments",
+    operation_id = "Poll the Status of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsStatusPolling))]
+// #[get("/{payment_id}/poll_status")]
+pub async fn payments_status_polling(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    query_payload: web::Query<payment_types::PaymentsStatusPollingRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsStatusPolling;
+    let mut payload = query_payload.into_inner();
+    payload.payment_id = path.into_inner();
+    let locking_action = payload.get_locking_input(flow.clone());
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            payments::status_polling::payments_status_polling_core::<Oss>(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+        locking_action,
+    )
+    .await
+}
+
 /// Payments - List
 ///
 /// To list the payments
@@ -1053,6 +1260,38 @@ impl GetLockingInput for payment_types::PaymentsCancelRequest {
     }
 }
 
+impl GetLockingInput for payment_types::PaymentsRetryRequest {
+    fn get_locking_input<F>(&self, flow: F) -> api_locking::LockAction
+    where
+        F: types::FlowMetric,
+        lock_utils::ApiIdentifier: From<F>,
+    {
+        api_locking::LockAction::Hold {
+            input: api_locking::LockingInput {
+                unique_locking_key: self.payment_id.to_owned(),
+                api_identifier: lock_utils::ApiIdentifier::from(flow),
+                override_lock_retries: None,
+            },
+        }
+    }
+}
+
+impl GetLockingInput for payment_types::PaymentsStatusPollingRequest {
+    fn get_locking_input<F>(&self, flow: F) -> api_locking::LockAction
+    where
+        F: types::FlowMetric,
+        lock_utils::ApiIdentifier: From<F>,
+    {
+        api_locking::LockAction::Hold {
+            input: api_locking::LockingInput {
+                unique_locking_key: self.payment_id.to_owned(),
+                api_identifier: lock_utils::ApiIdentifier::from(flow),
+                override_lock_retries: None,
+            },
+        }
+    }
+}
+
 impl GetLockingInput for payment_types::PaymentsCaptureRequest {
     fn get_locking_input<F>(&self, flow: F) -> api_locking::LockAction
     where
@@ -290,10 +290,50 @@ pub struct RouterData<Flow, Request, Response> {
     pub test_mode: Option<bool>,
     pub connector_http_status_code: Option<u16>,
     pub external_latency: Option<u128>,
+    /// Whether the raw connector request and response need to be logged for debugging
+    pub debug_connector_io: bool,
+    /// Contains the redacted raw connector request and response, if logging them is enabled
+    pub connector_io_log: Option<serde_json::Value>,
+    /// Amount the connector reports as authorized, for connectors which return it
+    pub connector_authorized_amount: Option<i64>,
+    /// Result of the address verification of the card, as the code returned by the issuer
+    pub avs_result: Option<String>,
+    /// Result of the verification of the card security code, as the code returned by the issuer
+    pub cvv_result: Option<String>,
+    /// Fields of the connector response which are added to the metadata of the payment intent
+    pub connector_response_metadata: Option<ConnectorResponseMetadata>,
+    /// Outcome of the 3DS authentication, for connectors which return it
+    pub three_ds_authentication_result: Option<ThreeDsAuthenticationResult>,
     /// Contains apple pay flow type simplified or manual
     pub apple_pay_flow: Option<storage_enums::ApplePayFlow>,
 }
 
+/// Fields returned by the connector which are useful to the merchant, these are added to the
+/// metadata of the payment intent
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ConnectorResponseMetadata {
+    /// Authorization code of the issuer
+    pub authorization_code: Option<String>,
+    /// Time at which the connector settled the payment, reported on sync or through a webhook
+    #[serde(
+        with = "common_utils::custom_serde::iso8601::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub settled_at: Option<time::PrimitiveDateTime>,
+}
+
+/// Outcome of the 3DS authentication of a payment as reported by the connector
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreeDsAuthenticationResult {
+    /// The 3DS protocol version negotiated for the authentication, for example `2.2.0`
+    pub three_ds_version: Option<String>,
+    /// Whether the liability for fraud chargebacks has shifted to the issuer
+    pub liability_shift: Option<bool>,
+    /// Whether the issuer mandated a challenge of the customer, `false` if the authentication is
+    /// frictionless
+    pub challenge_required: Option<bool>,
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 pub enum PaymentMethodToken {
     Token(String),
@@ -361,6 +401,7 @@ pub struct PaymentsAuthorizeData {
     pub confirm: bool,
     pub statement_descriptor_suffix: Option<String>,
     pub statement_descriptor: Option<String>,
+    pub statement_descriptor_city: Option<String>,
     pub capture_method: Option<storage_enums::CaptureMethod>,
     pub router_return_url: Option<String>,
     pub webhook_url: Option<String>,
@@ -380,6 +421,16 @@ pub struct PaymentsAuthorizeData {
     pub payment_method_type: Option<storage_enums::PaymentMethodType>,
     pub surcharge_details: Option<api_models::payment_methods::SurchargeDetailsResponse>,
     pub customer_id: Option<String>,
+    /// Risk data from an external fraud tool, only set for connectors which support it
+    pub risk_data: Option<serde_json::Value>,
+    /// Details of the 3DS SDK of the customer's app, for app based 3DS authentication
+    pub sdk_information: Option<api_models::payments::SdkInfo>,
+    /// Number of installments the customer pays the amount in, only set for connectors which
+    /// support installments
+    pub installments: Option<api_models::payments::InstallmentData>,
+    /// Minimum 3DS version the customer can be authenticated with, only set for payments with
+    /// `three_ds` authentication through connectors which have a minimum version configured
+    pub minimum_three_ds_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -478,6 +529,9 @@ pub struct PaymentsSyncData {
     pub connector_meta: Option<serde_json::Value>,
     pub sync_type: SyncRequestType,
     pub mandate_id: Option<api_models::payments::MandateIds>,
+    pub force_refresh: bool,
+    /// Amount of the payment which is captured when the connector reports the payment as charged
+    pub amount: i64,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -571,7 +625,11 @@ impl Capturable for PaymentsCancelData {}
 impl Capturable for PaymentsApproveData {}
 impl Capturable for PaymentsRejectData {}
 impl Capturable for PaymentsSessionData {}
-impl Capturable for PaymentsSyncData {}
+impl Capturable for PaymentsSyncData {
+    fn get_capture_amount(&self) -> Option<i64> {
+        Some(self.amount)
+    }
+}
 
 pub struct AddAccessTokenResult {
     pub access_token_result: Result<Option<AccessToken>, ErrorResponse>,
@@ -584,7 +642,7 @@ pub struct AccessToken {
     pub expires: i64,
 }
 
-#[derive(serde::Serialize, Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct MandateReference {
     pub connector_mandate_id: Option<String>,
     pub payment_method_id: Option<String>,
@@ -676,7 +734,7 @@ pub enum PreprocessingResponseId {
     ConnectorTransactionId(String),
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub enum ResponseId {
     ConnectorTransactionId(String),
     EncodedData(String),
@@ -940,6 +998,50 @@ impl ErrorResponse {
     }
 }
 
+/// Outcome of a payment at the connector, every connector response is mapped into this so that
+/// the trackers of the payment are updated the same way irrespective of the connector
+#[derive(Clone, Debug)]
+pub struct ConnectorPaymentOutcome {
+    pub status: storage_enums::IntentStatus,
+    pub connector_transaction_id: Option<String>,
+    pub error: Option<ErrorResponse>,
+}
+
+/// Maps the payment response of a connector into a [`ConnectorPaymentOutcome`]
+pub trait FromConnectorResponse<R> {
+    fn from_connector_response(response: R) -> Self;
+}
+
+impl<F, T> FromConnectorResponse<&RouterData<F, T, PaymentsResponseData>>
+    for ConnectorPaymentOutcome
+{
+    fn from_connector_response(router_data: &RouterData<F, T, PaymentsResponseData>) -> Self {
+        let (connector_transaction_id, error) = match &router_data.response {
+            Ok(PaymentsResponseData::TransactionResponse { resource_id, .. })
+            | Ok(PaymentsResponseData::TransactionUnresolvedResponse { resource_id, .. }) => {
+                let connector_transaction_id = match resource_id {
+                    ResponseId::NoResponseId => None,
+                    ResponseId::ConnectorTransactionId(id) | ResponseId::EncodedData(id) => {
+                        Some(id.clone())
+                    }
+                };
+                (connector_transaction_id, None)
+            }
+            Ok(PaymentsResponseData::PreProcessingResponse {
+                pre_processing_id: PreprocessingResponseId::ConnectorTransactionId(id),
+                ..
+            }) => (Some(id.clone()), None),
+            Ok(_) => (None, None),
+            Err(error) => (None, Some(error.clone())),
+        };
+        Self {
+            status: transformers::ForeignFrom::foreign_from(router_data.status),
+            connector_transaction_id,
+            error,
+        }
+    }
+}
+
 impl TryFrom<ConnectorAuthType> for AccessTokenRequestData {
     type Error = errors::ApiErrorResponse;
     fn try_from(connector_auth: ConnectorAuthType) -> Result<Self, Self::Error> {
@@ -1065,6 +1167,7 @@ impl From<&SetupMandateRouterData> for PaymentsAuthorizeData {
             email: data.request.email.clone(),
             amount: 0,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             capture_method: None,
             webhook_url: None,
             complete_authorize_url: None,
@@ -1078,6 +1181,10 @@ impl From<&SetupMandateRouterData> for PaymentsAuthorizeData {
             payment_method_type: None,
             customer_id: None,
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         }
     }
 }
@@ -1124,6 +1231,13 @@ impl<F1, F2, T1, T2> From<(&RouterData<F1, T1, PaymentsResponseData>, T2)>
             connector_api_version: data.connector_api_version.clone(),
             connector_http_status_code: data.connector_http_status_code,
             external_latency: data.external_latency,
+            debug_connector_io: data.debug_connector_io,
+            connector_io_log: data.connector_io_log.clone(),
+            connector_authorized_amount: data.connector_authorized_amount,
+            avs_result: data.avs_result.clone(),
+            cvv_result: data.cvv_result.clone(),
+            connector_response_metadata: data.connector_response_metadata.clone(),
+            three_ds_authentication_result: data.three_ds_authentication_result.clone(),
             apple_pay_flow: data.apple_pay_flow.clone(),
         }
     }
@@ -1179,6 +1293,13 @@ impl<F1, F2>
             connector_api_version: None,
             connector_http_status_code: data.connector_http_status_code,
             external_latency: data.external_latency,
+            debug_connector_io: data.debug_connector_io,
+            connector_io_log: data.connector_io_log.clone(),
+            connector_authorized_amount: data.connector_authorized_amount,
+            avs_result: data.avs_result.clone(),
+            cvv_result: data.cvv_result.clone(),
+            connector_response_metadata: data.connector_response_metadata.clone(),
+            three_ds_authentication_result: data.three_ds_authentication_result.clone(),
             apple_pay_flow: None,
         }
     }
@@ -30,6 +30,7 @@ impl TryFrom<domain::MerchantAccount> for MerchantAccountResponse {
             enable_payment_response_hash: item.enable_payment_response_hash,
             payment_response_hash_key: item.payment_response_hash_key,
             redirect_to_merchant_with_http_post: item.redirect_to_merchant_with_http_post,
+            debug_connector_io: item.debug_connector_io,
             merchant_details: item.merchant_details,
             webhook_details: item.webhook_details,
             routing_algorithm: item.routing_algorithm,
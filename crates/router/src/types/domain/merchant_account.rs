@@ -44,6 +44,7 @@ pub struct MerchantAccount {
     pub is_recon_enabled: bool,
     pub default_profile: Option<String>,
     pub recon_status: diesel_models::enums::ReconStatus,
+    pub debug_connector_io: bool,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -68,6 +69,7 @@ pub enum MerchantAccountUpdate {
         frm_routing_algorithm: Option<serde_json::Value>,
         payout_routing_algorithm: Option<serde_json::Value>,
         default_profile: Option<Option<String>>,
+        debug_connector_io: Option<bool>,
     },
     StorageSchemeUpdate {
         storage_scheme: MerchantStorageScheme,
@@ -100,6 +102,7 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 frm_routing_algorithm,
                 payout_routing_algorithm,
                 default_profile,
+                debug_connector_io,
             } => Self {
                 merchant_name: merchant_name.map(Encryption::from),
                 merchant_details: merchant_details.map(Encryption::from),
@@ -120,6 +123,7 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 intent_fulfillment_time,
                 payout_routing_algorithm,
                 default_profile,
+                debug_connector_io,
                 ..Default::default()
             },
             MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme } => Self {
@@ -173,6 +177,7 @@ impl super::behaviour::Conversion for MerchantAccount {
             is_recon_enabled: self.is_recon_enabled,
             default_profile: self.default_profile,
             recon_status: self.recon_status,
+            debug_connector_io: self.debug_connector_io,
         })
     }
 
@@ -217,6 +222,7 @@ impl super::behaviour::Conversion for MerchantAccount {
                 is_recon_enabled: item.is_recon_enabled,
                 default_profile: item.default_profile,
                 recon_status: item.recon_status,
+                debug_connector_io: item.debug_connector_io,
             })
         }
         .await
@@ -252,6 +258,7 @@ impl super::behaviour::Conversion for MerchantAccount {
             is_recon_enabled: self.is_recon_enabled,
             default_profile: self.default_profile,
             recon_status: self.recon_status,
+            debug_connector_io: self.debug_connector_io,
         })
     }
 }
@@ -30,6 +30,12 @@ impl ConnectorResponseExt for ConnectorResponse {
             connector_transaction_id: None,
             authentication_data: None,
             encoded_data: None,
+            connector_io_log: None,
+            three_ds_version: None,
+            liability_shift: None,
+            authorization_code: None,
+            settled_at: None,
+            challenge_required: None,
         }
     }
 }
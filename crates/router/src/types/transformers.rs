@@ -737,6 +737,8 @@ impl ForeignFrom<storage::Capture> for api_models::payments::CaptureResponse {
             error_code: capture.error_code,
             error_reason: capture.error_reason,
             reference_id: capture.connector_response_reference_id,
+            created_at: Some(capture.created_at),
+            modified_at: Some(capture.modified_at),
         }
     }
 }
@@ -154,6 +154,26 @@ pub fn get_payment_attempt_id(payment_id: impl std::fmt::Display, attempt_count:
     format!("{payment_id}_{attempt_count}")
 }
 
+/// Formats an amount in the lowest denomination of the currency as a human readable amount in the
+/// base unit of the currency, for example 6540 is formatted as `65.40` for USD, as `6540` for JPY
+/// and as `6.540` for KWD
+pub fn format_amount_in_currency(amount: i64, currency: api_models::enums::Currency) -> String {
+    let (minor_units_per_unit, decimal_places) = if currency.is_zero_decimal_currency() {
+        return amount.to_string();
+    } else if currency.is_three_decimal_currency() {
+        (1000, 3)
+    } else {
+        (100, 2)
+    };
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    format!(
+        "{sign}{}.{:0decimal_places$}",
+        amount / minor_units_per_unit,
+        amount % minor_units_per_unit,
+    )
+}
+
 #[derive(Debug)]
 pub struct QrImage {
     pub data: String,
@@ -195,6 +215,30 @@ mod tests {
         let qr_image_data_source_url = utils::QrImage::new_from_data("Hyperswitch".to_string());
         assert!(qr_image_data_source_url.is_ok());
     }
+
+    #[test]
+    fn test_format_amount_in_currency() {
+        use api_models::enums::Currency;
+
+        assert_eq!(
+            utils::format_amount_in_currency(6540, Currency::USD),
+            "65.40"
+        );
+        assert_eq!(utils::format_amount_in_currency(5, Currency::USD), "0.05");
+        assert_eq!(
+            utils::format_amount_in_currency(6540, Currency::JPY),
+            "6540"
+        );
+        assert_eq!(
+            utils::format_amount_in_currency(6540, Currency::KWD),
+            "6.540"
+        );
+        assert_eq!(utils::format_amount_in_currency(65, Currency::KWD), "0.065");
+        assert_eq!(
+            utils::format_amount_in_currency(-6540, Currency::USD),
+            "-65.40"
+        );
+    }
 }
 
 pub async fn find_payment_intent_from_payment_id_type(
@@ -729,6 +773,7 @@ where
             &state.conf.server,
             &operation,
             &state.conf.connector_request_reference_id_config,
+            &state.conf.response_masking_policy,
             None,
             None,
             None,
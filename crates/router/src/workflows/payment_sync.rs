@@ -12,7 +12,7 @@ use crate::{
     core::{
         errors::StorageErrorExt,
         payment_methods::Oss,
-        payments::{self as payment_flows, operations},
+        payments::{self as payment_flows, helpers, operations},
     },
     db::StorageInterface,
     errors,
@@ -108,7 +108,8 @@ impl ProcessTrackerWorkflow<AppState> for PaymentsSyncWorkflow {
                 .await?;
 
                 // If the payment status is still processing and there is no connector transaction_id
-                // then change the payment status to failed if all retries exceeded
+                // then change the payment status to failed if all retries exceeded, unless the
+                // payment intent has already moved to a status which cannot become failed
                 if is_last_retry
                     && payment_data.payment_attempt.status == enums::AttemptStatus::Pending
                     && payment_data
@@ -116,6 +117,10 @@ impl ProcessTrackerWorkflow<AppState> for PaymentsSyncWorkflow {
                         .connector_transaction_id
                         .as_ref()
                         .is_none()
+                    && helpers::is_valid_transition(
+                        payment_data.payment_intent.status,
+                        api_models::enums::IntentStatus::Failed,
+                    )
                 {
                     let payment_intent_update = data_models::payments::payment_intent::PaymentIntentUpdate::PGStatusUpdate { status: api_models::enums::IntentStatus::Failed };
                     let payment_attempt_update =
@@ -128,6 +133,7 @@ impl ProcessTrackerWorkflow<AppState> for PaymentsSyncWorkflow {
                                 consts::REQUEST_TIMEOUT_ERROR_MESSAGE_FROM_PSYNC.to_string(),
                             )),
                             amount_capturable: Some(0),
+                            latency_ms: None,
                         };
 
                     payment_data.payment_attempt = db
@@ -203,6 +209,18 @@ pub async fn get_sync_process_schedule_time(
     merchant_id: &str,
     retry_count: i32,
 ) -> Result<Option<time::PrimitiveDateTime>, errors::ProcessTrackerError> {
+    let mapping = get_connector_pt_mapping(db, connector).await;
+    let time_delta = scheduler_utils::get_schedule_time(mapping, merchant_id, retry_count + 1);
+
+    Ok(scheduler_utils::get_time_from_delta(time_delta))
+}
+
+/// Returns the sync schedule configured for the connector, or the default schedule if the
+/// connector has none
+pub async fn get_connector_pt_mapping(
+    db: &dyn StorageInterface,
+    connector: &str,
+) -> process_data::ConnectorPTMapping {
     let mapping: common_utils::errors::CustomResult<
         process_data::ConnectorPTMapping,
         errors::StorageError,
@@ -215,16 +233,13 @@ pub async fn get_sync_process_schedule_time(
                 .parse_struct("ConnectorPTMapping")
                 .change_context(errors::StorageError::DeserializationFailed)
         });
-    let mapping = match mapping {
+    match mapping {
         Ok(x) => x,
         Err(err) => {
             logger::info!("Redis Mapping Error: {}", err);
             process_data::ConnectorPTMapping::default()
         }
-    };
-    let time_delta = scheduler_utils::get_schedule_time(mapping, merchant_id, retry_count + 1);
-
-    Ok(scheduler_utils::get_time_from_delta(time_delta))
+    }
 }
 
 /// Schedule the task for retry
@@ -271,4 +286,39 @@ mod tests {
             vec![cpt_default.start_after, cpt_default.frequency[0]]
         );
     }
+
+    #[tokio::test]
+    async fn test_connector_pt_mapping_is_read_from_the_config_of_the_connector() {
+        use storage_impl::MockDb;
+
+        use crate::db::configs::ConfigInterface;
+
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        mock_db
+            .insert_config(storage::ConfigNew {
+                key: "pt_mapping_adyen".to_string(),
+                config: r#"{"defaultMapping":{"start_after":30,"frequency":[60],"count":[3]},"customMerchantMapping":{},"maxRetriesCount":3}"#
+                    .to_string(),
+            })
+            .await
+            .unwrap();
+
+        let adyen_mapping = get_connector_pt_mapping(&mock_db, "adyen").await;
+        assert_eq!(
+            scheduler_utils::get_schedule_time(adyen_mapping, "merchant_1", 0),
+            Some(30)
+        );
+        // Connectors without a sync schedule of their own use the default one
+        let stripe_mapping = get_connector_pt_mapping(&mock_db, "stripe").await;
+        assert_eq!(
+            scheduler_utils::get_schedule_time(stripe_mapping, "merchant_1", 0),
+            Some(
+                process_data::ConnectorPTMapping::default()
+                    .default_mapping
+                    .start_after
+            )
+        );
+    }
 }
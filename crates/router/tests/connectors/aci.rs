@@ -50,6 +50,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             confirm: true,
             statement_descriptor_suffix: None,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -69,6 +70,10 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             complete_authorize_url: None,
             customer_id: None,
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
@@ -94,6 +99,13 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         connector_http_status_code: None,
         apple_pay_flow: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
     }
 }
 
@@ -152,6 +164,13 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         connector_http_status_code: None,
         apple_pay_flow: None,
         external_latency: None,
+        debug_connector_io: false,
+        connector_io_log: None,
+        connector_authorized_amount: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_metadata: None,
+        three_ds_authentication_result: None,
     }
 }
 
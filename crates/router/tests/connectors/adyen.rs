@@ -135,6 +135,7 @@ impl AdyenTest {
             confirm: true,
             statement_descriptor_suffix: None,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -154,6 +155,10 @@ impl AdyenTest {
             complete_authorize_url: None,
             customer_id: None,
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         })
     }
 }
@@ -108,6 +108,8 @@ async fn should_sync_authorized_payment() {
                 capture_method: Some(diesel_models::enums::CaptureMethod::Manual),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta: None,
+                force_refresh: false,
+                amount: 100,
             }),
             None,
         )
@@ -222,6 +224,8 @@ async fn should_sync_auto_captured_payment() {
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta: None,
+                force_refresh: false,
+                amount: 100,
             }),
             None,
         )
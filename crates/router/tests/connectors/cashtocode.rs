@@ -47,6 +47,7 @@ impl CashtocodeTest {
             confirm: true,
             statement_descriptor_suffix: None,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -66,6 +67,10 @@ impl CashtocodeTest {
             complete_authorize_url: None,
             customer_id: Some("John Doe".to_owned()),
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         })
     }
 
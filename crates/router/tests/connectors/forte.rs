@@ -152,6 +152,8 @@ async fn should_sync_authorized_payment() {
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta: None,
+                force_refresh: false,
+                amount: 100,
                 mandate_id: None,
             }),
             get_default_payment_info(),
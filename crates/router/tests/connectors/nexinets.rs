@@ -122,6 +122,8 @@ async fn should_sync_authorized_payment() {
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta,
+                force_refresh: false,
+                amount: 100,
                 mandate_id: None,
             }),
             None,
@@ -72,6 +72,7 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         confirm: true,
         statement_descriptor_suffix: None,
         statement_descriptor: None,
+        statement_descriptor_city: None,
         setup_future_usage: None,
         mandate_id: None,
         off_session: None,
@@ -92,6 +93,10 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         capture_method: None,
         customer_id: None,
         surcharge_details: None,
+        risk_data: None,
+        sdk_information: None,
+        installments: None,
+        minimum_three_ds_version: None,
     })
 }
 
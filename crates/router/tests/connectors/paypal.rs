@@ -140,6 +140,8 @@ async fn should_sync_authorized_payment() {
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta,
+                force_refresh: false,
+                amount: 100,
             }),
             get_default_payment_info(),
         )
@@ -336,6 +338,8 @@ async fn should_sync_auto_captured_payment() {
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta,
+                force_refresh: false,
+                amount: 100,
             }),
             get_default_payment_info(),
         )
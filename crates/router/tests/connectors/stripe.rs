@@ -1,6 +1,6 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 use router::types::{self, api, storage::enums};
 
 use crate::{
@@ -44,6 +44,37 @@ fn get_payment_authorize_data() -> Option<types::PaymentsAuthorizeData> {
     })
 }
 
+#[test]
+fn should_map_amount_and_currency_in_connector_request() {
+    let request_body = Stripe {}
+        .get_authorize_request_body(
+            Some(types::PaymentsAuthorizeData {
+                amount: 1050,
+                currency: enums::Currency::EUR,
+                ..get_payment_authorize_data().unwrap()
+            }),
+            None,
+            types::ConnectorAuthType::HeaderKey {
+                api_key: Secret::new("sk_test_dummy".to_string()),
+            },
+        )
+        .unwrap()
+        .unwrap();
+    let request_body: HashMap<String, String> =
+        serde_urlencoded::from_str(request_body.peek()).unwrap();
+    assert_eq!(request_body.get("amount").map(String::as_str), Some("1050"));
+    assert_eq!(
+        request_body.get("currency").map(String::as_str),
+        Some("EUR")
+    );
+    assert_eq!(
+        request_body
+            .get("payment_method_data[card][number]")
+            .map(String::as_str),
+        Some("4242424242424242")
+    );
+}
+
 #[actix_web::test]
 async fn should_only_authorize_payment() {
     let response = Stripe {}
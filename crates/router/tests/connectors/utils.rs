@@ -75,6 +75,32 @@ pub trait ConnectorActions: Connector {
         call_connector(request, integration).await
     }
 
+    /// Builds the authorize request of the connector without calling the connector, to assert the
+    /// field mapping of the connector. The connector authentication is passed in, so that no
+    /// authentication configuration is required. The serialized request body is returned as a
+    /// secret, since it contains the payment method details.
+    fn get_authorize_request_body(
+        &self,
+        payment_data: Option<types::PaymentsAuthorizeData>,
+        payment_info: Option<PaymentInfo>,
+        connector_auth_type: types::ConnectorAuthType,
+    ) -> Result<Option<Secret<String>>, Report<ConnectorError>> {
+        let integration: services::BoxedConnectorIntegration<
+            '_,
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+            types::PaymentsResponseData,
+        > = self.get_data().connector.get_connector_integration();
+        let request = self.generate_data_with_auth_type(
+            payment_data.unwrap_or(PaymentAuthorizeType::default().0),
+            payment_info,
+            connector_auth_type,
+        );
+        Ok(integration
+            .get_request_body(&request)?
+            .map(types::RequestBody::get_inner_value))
+    }
+
     async fn create_connector_customer(
         &self,
         payment_data: Option<types::ConnectorCustomerData>,
@@ -459,6 +485,15 @@ pub trait ConnectorActions: Connector {
         &self,
         req: Req,
         info: Option<PaymentInfo>,
+    ) -> RouterData<Flow, Req, Res> {
+        self.generate_data_with_auth_type(req, info, self.get_auth_token())
+    }
+
+    fn generate_data_with_auth_type<Flow, Req: From<Req>, Res>(
+        &self,
+        req: Req,
+        info: Option<PaymentInfo>,
+        connector_auth_type: types::ConnectorAuthType,
     ) -> RouterData<Flow, Req, Res> {
         RouterData {
             flow: PhantomData,
@@ -475,7 +510,7 @@ pub trait ConnectorActions: Connector {
                         .map_or(enums::AuthenticationType::NoThreeDs, |a| a)
                 }),
             payment_method: enums::PaymentMethod::Card,
-            connector_auth_type: self.get_auth_token(),
+            connector_auth_type,
             description: Some("This is a test".to_string()),
             return_url: info.clone().and_then(|a| a.return_url),
             request: req,
@@ -511,6 +546,13 @@ pub trait ConnectorActions: Connector {
             connector_http_status_code: None,
             apple_pay_flow: None,
             external_latency: None,
+            debug_connector_io: false,
+            connector_io_log: None,
+            connector_authorized_amount: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_metadata: None,
+            three_ds_authentication_result: None,
         }
     }
 
@@ -864,6 +906,7 @@ impl Default for PaymentAuthorizeType {
             confirm: true,
             statement_descriptor_suffix: None,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             capture_method: None,
             setup_future_usage: None,
             mandate_id: None,
@@ -883,6 +926,10 @@ impl Default for PaymentAuthorizeType {
             webhook_url: None,
             customer_id: None,
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         };
         Self(data)
     }
@@ -939,6 +986,8 @@ impl Default for PaymentSyncType {
             capture_method: None,
             sync_type: types::SyncRequestType::SinglePaymentSync,
             connector_meta: None,
+            force_refresh: false,
+            amount: 100,
         };
         Self(data)
     }
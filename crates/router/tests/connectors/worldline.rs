@@ -82,6 +82,7 @@ impl WorldlineTest {
             confirm: true,
             statement_descriptor_suffix: None,
             statement_descriptor: None,
+            statement_descriptor_city: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -101,6 +102,10 @@ impl WorldlineTest {
             complete_authorize_url: None,
             customer_id: None,
             surcharge_details: None,
+            risk_data: None,
+            sdk_information: None,
+            installments: None,
+            minimum_three_ds_version: None,
         })
     }
 }
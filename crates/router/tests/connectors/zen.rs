@@ -101,6 +101,8 @@ async fn should_sync_authorized_payment() {
                 capture_method: None,
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta: None,
+                force_refresh: false,
+                amount: 100,
                 mandate_id: None,
             }),
             None,
@@ -215,6 +217,8 @@ async fn should_sync_auto_captured_payment() {
                 capture_method: Some(enums::CaptureMethod::Automatic),
                 sync_type: types::SyncRequestType::SinglePaymentSync,
                 connector_meta: None,
+                force_refresh: false,
+                amount: 100,
                 mandate_id: None,
             }),
             None,
@@ -345,6 +345,7 @@ async fn payments_create_core() {
 
     let expected_response = api::PaymentsResponse {
         payment_id: Some("pay_mbabizu24mvu3mela5njyhpit10".to_string()),
+        merchant_id: Some(merchant_account.merchant_id.clone()),
         status: api_enums::IntentStatus::Succeeded,
         amount: 6540,
         amount_capturable: None,
@@ -515,6 +516,7 @@ async fn payments_create_core_adyen_no_redirect() {
     let expected_response = services::ApplicationResponse::JsonWithHeaders((
         api::PaymentsResponse {
             payment_id: Some(payment_id.clone()),
+            merchant_id: Some(merchant_account.merchant_id.clone()),
             status: api_enums::IntentStatus::Processing,
             amount: 6540,
             amount_capturable: None,
@@ -545,3 +547,365 @@ async fn payments_create_core_adyen_no_redirect() {
         .unwrap();
     assert_eq!(expected_response, actual_response);
 }
+
+#[actix_rt::test]
+#[ignore]
+async fn payments_retry_core_stripe() {
+    use configs::settings::Settings;
+    use router::types::storage;
+
+    let conf = Settings::new().expect("invalid settings");
+    let tx: oneshot::Sender<()> = oneshot::channel().0;
+    let state = routes::AppState::with_storage(
+        conf,
+        StorageImpl::PostgresqlTest,
+        tx,
+        Box::new(services::MockApiClient),
+    )
+    .await;
+
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            "juspay_merchant",
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .unwrap();
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id("juspay_merchant", &key_store)
+        .await
+        .unwrap();
+    let storage_scheme = merchant_account.storage_scheme;
+
+    let payment_id = format!("pay_{}", Uuid::new_v4().simple());
+    let card = api::PaymentMethodData::Card(api::Card {
+        card_number: "4242424242424242".to_string().try_into().unwrap(),
+        card_exp_month: "10".to_string().into(),
+        card_exp_year: "35".to_string().into(),
+        card_holder_name: "Arun Raj".to_string().into(),
+        card_cvc: "123".to_string().into(),
+        card_issuer: None,
+        card_network: None,
+        card_type: None,
+        card_issuing_country: None,
+        bank_code: None,
+        nick_name: None,
+    });
+    let req = api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(payment_id.clone())),
+        amount: Some(6540.into()),
+        currency: Some(api_enums::Currency::USD),
+        capture_method: Some(api_enums::CaptureMethod::Automatic),
+        confirm: Some(false),
+        authentication_type: Some(api_enums::AuthenticationType::NoThreeDs),
+        payment_method: Some(api_enums::PaymentMethod::Card),
+        ..Default::default()
+    };
+    payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Oss>(
+        state.clone(),
+        merchant_account.clone(),
+        key_store.clone(),
+        payments::PaymentCreate,
+        req,
+        services::AuthFlow::Merchant,
+        payments::CallConnectorAction::Trigger,
+        api::HeaderPayload::default(),
+    )
+    .await
+    .unwrap();
+
+    // Fail the payment on stripe, with the card of the payment stored in the temporary locker
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+    let payment_attempt = state
+        .store
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt.get_id(),
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+    let payment_token = payments::helpers::store_in_vault_and_generate_ppmt(
+        &state,
+        &card,
+        &payment_intent,
+        &payment_attempt,
+        api_enums::PaymentMethod::Card,
+    )
+    .await
+    .unwrap();
+    let payment_attempt = state
+        .store
+        .update_payment_attempt_with_attempt_id(
+            payment_attempt,
+            storage::PaymentAttemptUpdate::UpdateTrackers {
+                payment_token: Some(payment_token),
+                connector: Some("stripe".to_string()),
+                straight_through_algorithm: None,
+                amount_capturable: None,
+                payment_method_fingerprint: None,
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+    state
+        .store
+        .update_payment_attempt_with_attempt_id(
+            payment_attempt,
+            storage::PaymentAttemptUpdate::StatusUpdate {
+                status: api_enums::AttemptStatus::Failure,
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+    state
+        .store
+        .update_payment_intent(
+            payment_intent,
+            storage::PaymentIntentUpdate::PGStatusUpdate {
+                status: api_enums::IntentStatus::Failed,
+            },
+            storage_scheme,
+        )
+        .await
+        .unwrap();
+
+    let actual_response = payments::payments_retry_core::<Oss>(
+        state,
+        merchant_account.clone(),
+        key_store,
+        api::PaymentsRetryRequest {
+            payment_id: payment_id.clone(),
+        },
+    )
+    .await
+    .unwrap();
+    // The retry is made through a new attempt of the same payment
+    assert!(matches!(
+        actual_response,
+        services::ApplicationResponse::JsonWithHeaders((response, _))
+            if response.payment_id == Some(payment_id)
+                && response.merchant_id == Some(merchant_account.merchant_id)
+                && response.status == api_enums::IntentStatus::Succeeded
+                && response.attempt_count == 2
+                && response.connector == Some("stripe".to_string())
+    ));
+}
+
+#[actix_rt::test]
+#[ignore]
+async fn payments_status_polling_core_stripe() {
+    use configs::settings::Settings;
+
+    let conf = Settings::new().expect("invalid settings");
+    let tx: oneshot::Sender<()> = oneshot::channel().0;
+    let state = routes::AppState::with_storage(
+        conf,
+        StorageImpl::PostgresqlTest,
+        tx,
+        Box::new(services::MockApiClient),
+    )
+    .await;
+
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            "juspay_merchant",
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .unwrap();
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id("juspay_merchant", &key_store)
+        .await
+        .unwrap();
+
+    let payment_id = format!("pay_{}", Uuid::new_v4().simple());
+    let req = api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(payment_id.clone())),
+        amount: Some(6540.into()),
+        currency: Some(api_enums::Currency::USD),
+        capture_method: Some(api_enums::CaptureMethod::Automatic),
+        confirm: Some(true),
+        authentication_type: Some(api_enums::AuthenticationType::NoThreeDs),
+        payment_method: Some(api_enums::PaymentMethod::Card),
+        payment_method_data: Some(api::PaymentMethodData::Card(api::Card {
+            card_number: "4242424242424242".to_string().try_into().unwrap(),
+            card_exp_month: "10".to_string().into(),
+            card_exp_year: "35".to_string().into(),
+            card_holder_name: "Arun Raj".to_string().into(),
+            card_cvc: "123".to_string().into(),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        })),
+        ..Default::default()
+    };
+    payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Oss>(
+        state.clone(),
+        merchant_account.clone(),
+        key_store.clone(),
+        payments::PaymentCreate,
+        req,
+        services::AuthFlow::Merchant,
+        payments::CallConnectorAction::Trigger,
+        api::HeaderPayload::default(),
+    )
+    .await
+    .unwrap();
+
+    let actual_response = payments::status_polling::payments_status_polling_core::<Oss>(
+        state,
+        merchant_account,
+        key_store,
+        api::PaymentsStatusPollingRequest {
+            payment_id: payment_id.clone(),
+            max_polls: Some(3),
+            interval_in_milliseconds: Some(100),
+        },
+    )
+    .await
+    .unwrap();
+    // A payment which is no longer processing is returned after a single sync
+    assert!(matches!(
+        actual_response,
+        services::ApplicationResponse::JsonWithHeaders((response, _))
+            if response.payment_id == Some(payment_id)
+                && response.status == api_enums::IntentStatus::Succeeded
+    ));
+}
+
+#[actix_rt::test]
+#[ignore]
+async fn payments_create_core_stripe_test_cards_in_test_mode() {
+    use configs::settings::Settings;
+    use router::types::domain;
+
+    let conf = Settings::new().expect("invalid settings");
+    let tx: oneshot::Sender<()> = oneshot::channel().0;
+    let state = routes::AppState::with_storage(
+        conf,
+        StorageImpl::PostgresqlTest,
+        tx,
+        Box::new(services::MockApiClient),
+    )
+    .await;
+
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            "juspay_merchant",
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .unwrap();
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id("juspay_merchant", &key_store)
+        .await
+        .unwrap();
+
+    let merchant_connector_account = state
+        .store
+        .find_merchant_connector_account_by_merchant_id_connector_name(
+            &merchant_account.merchant_id,
+            "stripe",
+            &key_store,
+        )
+        .await
+        .unwrap();
+    state
+        .store
+        .update_merchant_connector_account(
+            merchant_connector_account,
+            domain::MerchantConnectorAccountUpdate::Update {
+                merchant_id: None,
+                connector_type: None,
+                connector_name: None,
+                connector_account_details: None,
+                test_mode: Some(true),
+                disabled: None,
+                merchant_connector_id: None,
+                payment_methods_enabled: None,
+                metadata: None,
+                frm_configs: None,
+                connector_webhook_details: None,
+                applepay_verified_domains: None,
+                pm_auth_config: None,
+            }
+            .into(),
+            &key_store,
+        )
+        .await
+        .unwrap();
+
+    let get_request = |payment_id: &str, card_number: &str| api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(payment_id.to_string())),
+        amount: Some(6540.into()),
+        currency: Some(api_enums::Currency::USD),
+        capture_method: Some(api_enums::CaptureMethod::Automatic),
+        confirm: Some(true),
+        connector: Some(vec![api_enums::Connector::Stripe]),
+        authentication_type: Some(api_enums::AuthenticationType::NoThreeDs),
+        payment_method: Some(api_enums::PaymentMethod::Card),
+        payment_method_data: Some(api::PaymentMethodData::Card(api::Card {
+            card_number: card_number.to_string().try_into().unwrap(),
+            card_exp_month: "10".to_string().into(),
+            card_exp_year: "35".to_string().into(),
+            card_holder_name: "Arun Raj".to_string().into(),
+            card_cvc: "123".to_string().into(),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        })),
+        ..Default::default()
+    };
+
+    // The connector is never called for declining test cards, the mock api client would fail the
+    // payment
+    let declined_payment_id = format!("pay_{}", Uuid::new_v4().simple());
+    let declined_response =
+        payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Oss>(
+            state,
+            merchant_account,
+            key_store,
+            payments::PaymentCreate,
+            get_request(&declined_payment_id, "4000000000000002"),
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+            api::HeaderPayload::default(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        declined_response,
+        services::ApplicationResponse::JsonWithHeaders((response, _))
+            if response.payment_id == Some(declined_payment_id)
+                && response.status == api_enums::IntentStatus::Failed
+                && response.error_code == Some("card_declined".to_string())
+                && response.connector_transaction_id.is_none()
+    ));
+}
@@ -88,6 +88,8 @@ pub enum Flow {
     CustomersDelete,
     /// Customers get mandates flow.
     CustomersGetMandates,
+    /// Customers get payments flow.
+    CustomersGetPayments,
     /// Create an Ephemeral Key.
     EphemeralKeyCreate,
     /// Delete an Ephemeral Key.
@@ -124,6 +126,14 @@ pub enum Flow {
     PaymentsCapture,
     /// Payments cancel flow.
     PaymentsCancel,
+    /// Payments reverse flow.
+    PaymentsReverse,
+    /// Payments retry flow.
+    PaymentsRetry,
+    /// Payments hosted page flow.
+    PaymentsHostedPage,
+    /// Payments status polling flow.
+    PaymentsStatusPolling,
     /// Payments approve flow.
     PaymentsApprove,
     /// Payments reject flow.
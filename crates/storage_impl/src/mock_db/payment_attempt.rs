@@ -1,4 +1,6 @@
-use api_models::enums::{AuthenticationType, Connector, PaymentMethod, PaymentMethodType};
+use api_models::enums::{
+    AuthenticationType, Connector, PaymentMethod, PaymentMethodType, ReviewStatus,
+};
 use common_utils::errors::CustomResult;
 use data_models::{
     errors::StorageError,
@@ -15,13 +17,24 @@ use crate::DataModelExt;
 impl PaymentAttemptInterface for MockDb {
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        Ok(payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.payment_id == payment_id
+                    && payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                StorageError::ValueNotFound(format!("Payment attempt {attempt_id} not found"))
+            })?)
     }
 
     async fn get_filters_for_payments(
@@ -47,6 +60,92 @@ impl PaymentAttemptInterface for MockDb {
         Err(StorageError::MockDbError)?
     }
 
+    async fn get_total_count_of_attempts_by_payment_method_fingerprint(
+        &self,
+        merchant_id: &str,
+        payment_method_fingerprint: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<i64, StorageError> {
+        let payment_attempts = self.payment_attempts.lock().await;
+        let count = payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.payment_method_fingerprint.as_deref()
+                        == Some(payment_method_fingerprint)
+            })
+            .count();
+        #[allow(clippy::as_conversions)]
+        Ok(count as i64)
+    }
+
+    async fn find_attempts_pending_review_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, StorageError> {
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        Ok(payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.review_status == Some(ReviewStatus::PendingReview)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn decrement_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_capture: i64,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, StorageError> {
+        let mut payment_attempts = self.payment_attempts.lock().await;
+
+        let payment_attempt = payment_attempts
+            .iter_mut()
+            .find(|payment_attempt| {
+                payment_attempt.attempt_id == this.attempt_id
+                    && payment_attempt.merchant_id == this.merchant_id
+                    && payment_attempt.amount_capturable >= amount_to_capture
+            })
+            .ok_or_else(|| {
+                StorageError::ValueNotFound(format!(
+                    "Payment attempt {} with amount capturable of at least {amount_to_capture}",
+                    this.attempt_id
+                ))
+            })?;
+        payment_attempt.amount_capturable -= amount_to_capture;
+        payment_attempt.modified_at = common_utils::date_time::now();
+
+        Ok(payment_attempt.clone())
+    }
+
+    async fn increment_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_release: i64,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, StorageError> {
+        let mut payment_attempts = self.payment_attempts.lock().await;
+
+        let payment_attempt = payment_attempts
+            .iter_mut()
+            .find(|payment_attempt| {
+                payment_attempt.attempt_id == this.attempt_id
+                    && payment_attempt.merchant_id == this.merchant_id
+            })
+            .ok_or_else(|| {
+                StorageError::ValueNotFound(format!("Payment attempt {}", this.attempt_id))
+            })?;
+        payment_attempt.amount_capturable += amount_to_release;
+        payment_attempt.modified_at = common_utils::date_time::now();
+
+        Ok(payment_attempt.clone())
+    }
+
     async fn find_payment_attempt_by_attempt_id_merchant_id(
         &self,
         _attempt_id: &str,
@@ -79,12 +178,20 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn find_attempts_by_merchant_id_payment_id(
         &self,
-        _merchant_id: &str,
-        _payment_id: &str,
+        merchant_id: &str,
+        payment_id: &str,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<PaymentAttempt>, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        Ok(payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.payment_id == payment_id
+            })
+            .cloned()
+            .collect())
     }
 
     #[allow(clippy::panic)]
@@ -141,6 +248,19 @@ impl PaymentAttemptInterface for MockDb {
             connector_response_reference_id: None,
             amount_capturable: payment_attempt.amount_capturable,
             surcharge_metadata: payment_attempt.surcharge_metadata,
+            dcc_details: payment_attempt.dcc_details,
+            connector_request_reference_id: payment_attempt.connector_request_reference_id,
+            latency_ms: payment_attempt.latency_ms,
+            connector_amount: payment_attempt.connector_amount,
+            amount_mismatch: payment_attempt.amount_mismatch,
+            avs_result: payment_attempt.avs_result,
+            cvv_result: payment_attempt.cvv_result,
+            payment_method_fingerprint: payment_attempt.payment_method_fingerprint,
+            review_status: payment_attempt.review_status,
+            sdk_information: payment_attempt.sdk_information,
+            payment_channel: payment_attempt.payment_channel,
+            installments: payment_attempt.installments,
+            used_saved_method: payment_attempt.used_saved_method,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -201,3 +321,500 @@ impl PaymentAttemptInterface for MockDb {
             .unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use diesel_models::enums::{AttemptStatus, CaptureMethod, Currency, PaymentChannel};
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_avs_and_cvv_results_are_stored_on_the_attempt() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Pending,
+                    amount: 1000,
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.avs_result, None);
+
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::ResponseUpdate {
+                    status: AttemptStatus::Charged,
+                    connector: Some("adyen".to_string()),
+                    connector_transaction_id: Some("adyen_txn_1".to_string()),
+                    authentication_type: None,
+                    payment_method_id: None,
+                    mandate_id: None,
+                    connector_metadata: None,
+                    payment_token: None,
+                    error_code: None,
+                    error_message: None,
+                    error_reason: None,
+                    connector_response_reference_id: None,
+                    amount_capturable: Some(0),
+                    latency_ms: None,
+                    connector_amount: None,
+                    amount_mismatch: None,
+                    avs_result: Some("Y".to_string()),
+                    cvv_result: Some("M".to_string()),
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(payment_attempt.status, AttemptStatus::Charged);
+        assert_eq!(payment_attempt.avs_result.as_deref(), Some("Y"));
+        assert_eq!(payment_attempt.cvv_result.as_deref(), Some("M"));
+    }
+
+    #[tokio::test]
+    async fn test_attempt_history_records_capture_method_of_each_attempt() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        for (attempt_id, capture_method, amount) in [
+            ("payment_1_1", CaptureMethod::Automatic, 1000),
+            ("payment_1_2", CaptureMethod::Manual, 1500),
+        ] {
+            mockdb
+                .insert_payment_attempt(
+                    PaymentAttemptNew {
+                        payment_id: "payment_1".to_string(),
+                        merchant_id: "merchant_1".to_string(),
+                        attempt_id: attempt_id.to_string(),
+                        status: AttemptStatus::Started,
+                        amount,
+                        capture_method: Some(capture_method),
+                        ..Default::default()
+                    },
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+        }
+
+        let attempts = mockdb
+            .find_attempts_by_merchant_id_payment_id(
+                "merchant_1",
+                "payment_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            attempts
+                .iter()
+                .map(|attempt| (
+                    attempt.attempt_id.as_str(),
+                    attempt.capture_method,
+                    attempt.amount
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("payment_1_1", Some(CaptureMethod::Automatic), 1000),
+                ("payment_1_2", Some(CaptureMethod::Manual), 1500),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attempt_flagged_for_review_is_listed() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let mut payment_attempts = Vec::new();
+        for (payment_id, attempt_id) in [("payment_1", "payment_1_1"), ("payment_2", "payment_2_1")]
+        {
+            let payment_attempt = mockdb
+                .insert_payment_attempt(
+                    PaymentAttemptNew {
+                        payment_id: payment_id.to_string(),
+                        merchant_id: "merchant_1".to_string(),
+                        attempt_id: attempt_id.to_string(),
+                        status: AttemptStatus::Authorized,
+                        amount: 1000,
+                        ..Default::default()
+                    },
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+            payment_attempts.push(payment_attempt);
+        }
+        assert!(mockdb
+            .find_attempts_pending_review_by_merchant_id(
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly
+            )
+            .await
+            .unwrap()
+            .is_empty());
+
+        let flagged_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempts.remove(1),
+                PaymentAttemptUpdate::ReviewStatusUpdate {
+                    review_status: ReviewStatus::PendingReview,
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        // Flagging the attempt for review does not fail it
+        assert_eq!(flagged_attempt.status, AttemptStatus::Authorized);
+
+        let attempts_pending_review = mockdb
+            .find_attempts_pending_review_by_merchant_id(
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            attempts_pending_review
+                .iter()
+                .map(|attempt| attempt.attempt_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["payment_2_1"]
+        );
+        assert!(mockdb
+            .find_attempts_pending_review_by_merchant_id(
+                "merchant_2",
+                MerchantStorageScheme::PostgresOnly
+            )
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_captures_cannot_exceed_amount_capturable() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Authorized,
+                    amount: 1000,
+                    amount_capturable: 1000,
+                    capture_method: Some(CaptureMethod::ManualMultiple),
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        // Both captures read the attempt before either of them reserved the amount
+        let (first_capture, second_capture) = tokio::join!(
+            mockdb.decrement_amount_capturable_with_attempt_id(
+                payment_attempt.clone(),
+                600,
+                MerchantStorageScheme::PostgresOnly,
+            ),
+            mockdb.decrement_amount_capturable_with_attempt_id(
+                payment_attempt,
+                700,
+                MerchantStorageScheme::PostgresOnly,
+            ),
+        );
+
+        assert_eq!(first_capture.unwrap().amount_capturable, 400);
+        assert!(matches!(
+            second_capture.as_ref().map_err(|err| err.current_context()),
+            Err(StorageError::ValueNotFound(_))
+        ));
+        let payment_attempt = mockdb
+            .find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
+                "payment_1",
+                "merchant_1",
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.amount_capturable, 400);
+    }
+
+    #[tokio::test]
+    async fn test_payment_channel_of_pos_payment_is_kept_on_confirm() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Started,
+                    amount: 1000,
+                    payment_channel: Some(PaymentChannel::Pos),
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.payment_channel, Some(PaymentChannel::Pos));
+
+        // The confirm request did not specify the channel
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::ConfirmUpdate {
+                    amount: 1000,
+                    currency: Currency::USD,
+                    status: AttemptStatus::Pending,
+                    authentication_type: None,
+                    payment_method: Some(PaymentMethod::Card),
+                    browser_info: None,
+                    connector: Some("adyen".to_string()),
+                    payment_token: None,
+                    payment_method_data: None,
+                    payment_method_type: None,
+                    payment_experience: None,
+                    business_sub_label: None,
+                    straight_through_algorithm: None,
+                    error_code: None,
+                    error_message: None,
+                    amount_capturable: Some(1000),
+                    capture_method: None,
+                    capture_on: None,
+                    payment_method_fingerprint: None,
+                    sdk_information: None,
+                    payment_channel: None,
+                    installments: None,
+                    used_saved_method: None,
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(payment_attempt.status, AttemptStatus::Pending);
+        assert_eq!(payment_attempt.payment_channel, Some(PaymentChannel::Pos));
+    }
+
+    #[tokio::test]
+    async fn test_amount_of_failed_capture_is_released() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Authorized,
+                    amount: 1000,
+                    amount_capturable: 1000,
+                    capture_method: Some(CaptureMethod::ManualMultiple),
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let payment_attempt = mockdb
+            .decrement_amount_capturable_with_attempt_id(
+                payment_attempt,
+                600,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.amount_capturable, 400);
+
+        let payment_attempt = mockdb
+            .increment_amount_capturable_with_attempt_id(
+                payment_attempt,
+                600,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.amount_capturable, 1000);
+
+        // The released amount can be captured again
+        let payment_attempt = mockdb
+            .decrement_amount_capturable_with_attempt_id(
+                payment_attempt,
+                1000,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.amount_capturable, 0);
+    }
+
+    #[tokio::test]
+    async fn test_used_saved_method_is_kept_after_confirm() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Started,
+                    amount: 1000,
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::ConfirmUpdate {
+                    amount: 1000,
+                    currency: Currency::USD,
+                    status: AttemptStatus::Pending,
+                    authentication_type: None,
+                    payment_method: Some(PaymentMethod::Card),
+                    browser_info: None,
+                    connector: Some("adyen".to_string()),
+                    payment_token: Some("token_1".to_string()),
+                    payment_method_data: None,
+                    payment_method_type: None,
+                    payment_experience: None,
+                    business_sub_label: None,
+                    straight_through_algorithm: None,
+                    error_code: None,
+                    error_message: None,
+                    amount_capturable: Some(1000),
+                    capture_method: None,
+                    capture_on: None,
+                    payment_method_fingerprint: None,
+                    sdk_information: None,
+                    payment_channel: None,
+                    installments: None,
+                    used_saved_method: Some(true),
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        // The status, capture and cancel flows read the flag back from the attempt
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::StatusUpdate {
+                    status: AttemptStatus::Charged,
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(payment_attempt.status, AttemptStatus::Charged);
+        assert_eq!(payment_attempt.used_saved_method, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_installments_of_updated_payment_are_kept_on_confirm() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+        let installments = serde_json::json!({ "number_of_installments": 3 });
+
+        let payment_attempt = mockdb
+            .insert_payment_attempt(
+                PaymentAttemptNew {
+                    payment_id: "payment_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "payment_1_1".to_string(),
+                    status: AttemptStatus::Started,
+                    amount: 1000,
+                    ..Default::default()
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.installments, None);
+
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::Update {
+                    amount: 1000,
+                    currency: Currency::USD,
+                    status: AttemptStatus::ConfirmationAwaited,
+                    authentication_type: None,
+                    payment_method: Some(PaymentMethod::Card),
+                    payment_token: None,
+                    payment_method_data: None,
+                    payment_method_type: None,
+                    payment_experience: None,
+                    business_sub_label: None,
+                    amount_to_capture: None,
+                    capture_method: None,
+                    capture_on: None,
+                    installments: Some(installments.clone()),
+                    used_saved_method: None,
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment_attempt.installments, Some(installments.clone()));
+
+        // The confirm request did not specify the installments
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                PaymentAttemptUpdate::ConfirmUpdate {
+                    amount: 1000,
+                    currency: Currency::USD,
+                    status: AttemptStatus::Pending,
+                    authentication_type: None,
+                    payment_method: Some(PaymentMethod::Card),
+                    browser_info: None,
+                    connector: Some("dlocal".to_string()),
+                    payment_token: None,
+                    payment_method_data: None,
+                    payment_method_type: None,
+                    payment_experience: None,
+                    business_sub_label: None,
+                    straight_through_algorithm: None,
+                    error_code: None,
+                    error_message: None,
+                    amount_capturable: Some(1000),
+                    capture_method: None,
+                    capture_on: None,
+                    payment_method_fingerprint: None,
+                    sdk_information: None,
+                    payment_channel: None,
+                    installments: None,
+                    used_saved_method: None,
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(payment_attempt.status, AttemptStatus::Pending);
+        assert_eq!(payment_attempt.installments, Some(installments));
+    }
+}
@@ -9,6 +9,7 @@ use data_models::{
     MerchantStorageScheme,
 };
 use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
 
 use super::MockDb;
 
@@ -103,6 +104,13 @@ impl PaymentIntentInterface for MockDb {
             merchant_decision: new.merchant_decision,
             payment_link_id: new.payment_link_id,
             payment_confirm_source: new.payment_confirm_source,
+            connector_routing_trail: new.connector_routing_trail,
+            statement_descriptor_city: new.statement_descriptor_city,
+            shipping_amount: new.shipping_amount,
+            handling_amount: new.handling_amount,
+            client_secret_expiry: new.client_secret_expiry,
+            success_url: new.success_url,
+            failure_url: new.failure_url,
         };
         payment_intents.push(payment_intent.clone());
         Ok(payment_intent)
@@ -144,6 +152,91 @@ impl PaymentIntentInterface for MockDb {
             .unwrap())
     }
 
+    async fn find_payment_intents_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentIntent>, StorageError> {
+        let payment_intents = self.payment_intents.lock().await;
+
+        let mut customer_payment_intents = payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                payment_intent.customer_id.as_deref() == Some(customer_id)
+                    && payment_intent.merchant_id == merchant_id
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        customer_payment_intents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let offset = offset
+            .map(usize::try_from)
+            .transpose()
+            .into_report()
+            .change_context(StorageError::MockDbError)?
+            .unwrap_or(0);
+        let limit = limit
+            .map(usize::try_from)
+            .transpose()
+            .into_report()
+            .change_context(StorageError::MockDbError)?
+            .unwrap_or(usize::MAX);
+
+        Ok(customer_payment_intents
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
+
+    async fn get_open_payment_intents_count_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<i64, StorageError> {
+        let payment_intents = self.payment_intents.lock().await;
+
+        let open_payment_intents_count = payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                payment_intent.customer_id.as_deref() == Some(customer_id)
+                    && payment_intent.merchant_id == merchant_id
+                    && payment_intent.created_at >= created_after
+                    && !payment_intent.status.is_terminal_status()
+            })
+            .count();
+        i64::try_from(open_payment_intents_count)
+            .into_report()
+            .change_context(StorageError::MockDbError)
+    }
+
+    async fn invalidate_expired_client_secrets(
+        &self,
+        expired_before: PrimitiveDateTime,
+    ) -> CustomResult<Vec<PaymentIntent>, StorageError> {
+        let mut payment_intents = self.payment_intents.lock().await;
+
+        Ok(payment_intents
+            .iter_mut()
+            .filter(|payment_intent| {
+                payment_intent.client_secret.is_some()
+                    && payment_intent
+                        .client_secret_expiry
+                        .map_or(false, |expiry| expiry < expired_before)
+            })
+            .map(|payment_intent| {
+                payment_intent.client_secret = None;
+                payment_intent.modified_at = common_utils::date_time::now();
+                payment_intent.clone()
+            })
+            .collect())
+    }
+
     async fn get_active_payment_attempt(
         &self,
         payment: &mut PaymentIntent,
@@ -164,3 +257,197 @@ impl PaymentIntentInterface for MockDb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use diesel_models::enums::{Currency, IntentStatus};
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    fn create_payment_intent_new(payment_id: &str, customer_id: &str) -> PaymentIntentNew {
+        PaymentIntentNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            status: IntentStatus::Succeeded,
+            amount: 1000,
+            currency: Some(Currency::USD),
+            amount_captured: Some(1000),
+            customer_id: Some(customer_id.to_string()),
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: None,
+            modified_at: None,
+            last_synced: None,
+            setup_future_usage: None,
+            off_session: None,
+            client_secret: None,
+            active_attempt: data_models::RemoteStorageObject::ForeignID(format!("{payment_id}_1")),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            profile_id: None,
+            merchant_decision: None,
+            payment_link_id: None,
+            payment_confirm_source: None,
+            connector_routing_trail: None,
+            statement_descriptor_city: None,
+            shipping_amount: None,
+            handling_amount: None,
+            client_secret_expiry: None,
+            success_url: None,
+            failure_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_payment_intents_by_customer_id_merchant_id() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        for payment_id in ["payment_1", "payment_2"] {
+            mockdb
+                .insert_payment_intent(
+                    create_payment_intent_new(payment_id, "customer_1"),
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+        }
+
+        let customer_payments = mockdb
+            .find_payment_intents_by_customer_id_merchant_id(
+                "customer_1",
+                "merchant_1",
+                Some(10),
+                None,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(customer_payments.len(), 2);
+
+        let other_customer_payments = mockdb
+            .find_payment_intents_by_customer_id_merchant_id(
+                "customer_2",
+                "merchant_1",
+                Some(10),
+                None,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert!(other_customer_payments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_open_payment_intents_count_by_customer_id_merchant_id() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let payment_intents = [
+            ("payment_1", IntentStatus::Succeeded),
+            ("payment_2", IntentStatus::RequiresPaymentMethod),
+            ("payment_3", IntentStatus::RequiresCapture),
+            ("payment_4", IntentStatus::Failed),
+        ];
+        for (payment_id, status) in payment_intents {
+            mockdb
+                .insert_payment_intent(
+                    PaymentIntentNew {
+                        status,
+                        ..create_payment_intent_new(payment_id, "customer_1")
+                    },
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+        }
+        // Open payments created before the window are not counted
+        let created_after = common_utils::date_time::now() - time::Duration::hours(1);
+        mockdb
+            .insert_payment_intent(
+                PaymentIntentNew {
+                    status: IntentStatus::RequiresPaymentMethod,
+                    created_at: Some(created_after - time::Duration::minutes(1)),
+                    ..create_payment_intent_new("payment_5", "customer_1")
+                },
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let open_payment_intents_count = mockdb
+            .get_open_payment_intents_count_by_customer_id_merchant_id(
+                "customer_1",
+                "merchant_1",
+                created_after,
+                MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(open_payment_intents_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_expired_client_secrets() {
+        let mockdb = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        let now = common_utils::date_time::now();
+        let payment_intents = [
+            ("payment_1", Some(now - time::Duration::minutes(5))),
+            ("payment_2", Some(now + time::Duration::minutes(5))),
+            ("payment_3", None),
+        ];
+        for (payment_id, client_secret_expiry) in payment_intents {
+            mockdb
+                .insert_payment_intent(
+                    PaymentIntentNew {
+                        client_secret: Some(format!("{payment_id}_secret")),
+                        client_secret_expiry,
+                        ..create_payment_intent_new(payment_id, "customer_1")
+                    },
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+        }
+
+        let invalidated_payment_intents =
+            mockdb.invalidate_expired_client_secrets(now).await.unwrap();
+        assert_eq!(invalidated_payment_intents.len(), 1);
+        assert_eq!(invalidated_payment_intents[0].payment_id, "payment_1");
+
+        for (payment_id, has_client_secret) in [
+            ("payment_1", false),
+            ("payment_2", true),
+            ("payment_3", true),
+        ] {
+            let payment_intent = mockdb
+                .find_payment_intent_by_payment_id_merchant_id(
+                    payment_id,
+                    "merchant_1",
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+            assert_eq!(payment_intent.client_secret.is_some(), has_client_secret);
+        }
+
+        // Secrets which were already removed are not invalidated again
+        assert!(mockdb
+            .invalidate_expired_client_secrets(now)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -22,17 +22,28 @@ use diesel_models::{
     reverse_lookup::{ReverseLookup, ReverseLookupNew},
 };
 use error_stack::{IntoReport, ResultExt};
-use redis_interface::HsetnxReply;
-use router_env::{instrument, tracing};
+use redis_interface::{HsetnxReply, SetnxReply};
+use router_env::{instrument, logger, tracing};
 
 use crate::{
     diesel_error_to_data_error,
     lookup::ReverseLookupInterface,
-    redis::kv_store::{kv_wrapper, KvOperation, PartitionKey},
+    redis::kv_store::{kv_wrapper, KvOperation, PartitionKey, RedisConnInterface},
     utils::{pg_connection_read, pg_connection_write, try_redis_get_else_try_database_get},
     DataModelExt, DatabaseStore, KVRouterStore, RouterStore,
 };
 
+/// Time after which the lock taken to update the amount capturable of an attempt expires, in case
+/// the holder of the lock fails to release it
+const AMOUNT_CAPTURABLE_LOCK_TTL: i64 = 10;
+
+/// Number of times an update of the amount capturable waits for the lock held by a concurrent
+/// update, before failing
+const AMOUNT_CAPTURABLE_LOCK_RETRIES: u8 = 5;
+
+/// Time waited between the attempts to take the lock, in milliseconds
+const AMOUNT_CAPTURABLE_LOCK_RETRY_INTERVAL: u64 = 20;
+
 #[async_trait::async_trait]
 impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
     #[instrument(skip_all)]
@@ -290,6 +301,85 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
             er.change_context(new_err)
         })
     }
+
+    async fn get_total_count_of_attempts_by_payment_method_fingerprint(
+        &self,
+        merchant_id: &str,
+        payment_method_fingerprint: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<i64, errors::StorageError> {
+        let conn = self
+            .db_store
+            .get_replica_pool()
+            .get()
+            .await
+            .into_report()
+            .change_context(errors::StorageError::DatabaseConnectionError)?;
+        DieselPaymentAttempt::get_total_count_of_attempts_by_payment_method_fingerprint(
+            &conn,
+            merchant_id,
+            payment_method_fingerprint,
+        )
+        .await
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+    }
+
+    async fn find_attempts_pending_review_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+        let conn = pg_connection_read(self).await?;
+        DieselPaymentAttempt::find_pending_review_by_merchant_id(&conn, merchant_id)
+            .await
+            .map_err(|er| {
+                let new_err = diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+            .map(|attempts| {
+                attempts
+                    .into_iter()
+                    .map(PaymentAttempt::from_storage_model)
+                    .collect()
+            })
+    }
+
+    async fn decrement_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_capture: i64,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, errors::StorageError> {
+        let conn = pg_connection_write(self).await?;
+        this.to_storage_model()
+            .decrement_amount_capturable_with_attempt_id(&conn, amount_to_capture)
+            .await
+            .map_err(|er| {
+                let new_err = diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+            .map(PaymentAttempt::from_storage_model)
+    }
+
+    async fn increment_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_release: i64,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<PaymentAttempt, errors::StorageError> {
+        let conn = pg_connection_write(self).await?;
+        this.to_storage_model()
+            .increment_amount_capturable_with_attempt_id(&conn, amount_to_release)
+            .await
+            .map_err(|er| {
+                let new_err = diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+            .map(PaymentAttempt::from_storage_model)
+    }
 }
 
 #[async_trait::async_trait]
@@ -359,6 +449,21 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     connector_response_reference_id: None,
                     amount_capturable: payment_attempt.amount_capturable,
                     surcharge_metadata: payment_attempt.surcharge_metadata.clone(),
+                    dcc_details: payment_attempt.dcc_details.clone(),
+                    connector_request_reference_id: payment_attempt
+                        .connector_request_reference_id
+                        .clone(),
+                    latency_ms: payment_attempt.latency_ms,
+                    connector_amount: payment_attempt.connector_amount,
+                    amount_mismatch: payment_attempt.amount_mismatch,
+                    avs_result: payment_attempt.avs_result.clone(),
+                    cvv_result: payment_attempt.cvv_result.clone(),
+                    payment_method_fingerprint: payment_attempt.payment_method_fingerprint.clone(),
+                    review_status: payment_attempt.review_status,
+                    sdk_information: payment_attempt.sdk_information.clone(),
+                    payment_channel: payment_attempt.payment_channel,
+                    installments: payment_attempt.installments.clone(),
+                    used_saved_method: payment_attempt.used_saved_method,
                 };
 
                 let field = format!("pa_{}", created_attempt.attempt_id);
@@ -429,110 +534,20 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     .await
             }
             MerchantStorageScheme::RedisKv => {
-                let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
-                let old_connector_transaction_id = &this.connector_transaction_id;
-                let old_preprocessing_id = &this.preprocessing_step_id;
-                let updated_attempt = PaymentAttempt::from_storage_model(
-                    payment_attempt
-                        .clone()
-                        .to_storage_model()
-                        .apply_changeset(this.clone().to_storage_model()),
-                );
-                // Check for database presence as well Maybe use a read replica here ?
-                let redis_value = serde_json::to_string(&updated_attempt)
-                    .into_report()
-                    .change_context(errors::StorageError::KVError)?;
-                let field = format!("pa_{}", updated_attempt.attempt_id);
-
-                kv_wrapper::<(), _, _>(
-                    self,
-                    KvOperation::Hset::<PaymentAttempt>((&field, redis_value)),
-                    &key,
-                )
-                .await
-                .change_context(errors::StorageError::KVError)?
-                .try_into_hset()
-                .change_context(errors::StorageError::KVError)?;
-
-                match (
-                    old_connector_transaction_id,
-                    &updated_attempt.connector_transaction_id,
-                ) {
-                    (None, Some(connector_transaction_id)) => {
-                        add_connector_txn_id_to_reverse_lookup(
-                            self,
-                            key.as_str(),
-                            this.merchant_id.as_str(),
-                            updated_attempt.attempt_id.as_str(),
-                            connector_transaction_id.as_str(),
-                            storage_scheme,
-                        )
-                        .await?;
-                    }
-                    (Some(old_connector_transaction_id), Some(connector_transaction_id)) => {
-                        if old_connector_transaction_id.ne(connector_transaction_id) {
-                            add_connector_txn_id_to_reverse_lookup(
-                                self,
-                                key.as_str(),
-                                this.merchant_id.as_str(),
-                                updated_attempt.attempt_id.as_str(),
-                                connector_transaction_id.as_str(),
-                                storage_scheme,
-                            )
+                // Updates of the amount capturable are serialized with the reservations of the
+                // amount for captures, so that neither of them overwrites the other
+                if payment_attempt.get_amount_capturable().is_some() {
+                    let lock =
+                        acquire_amount_capturable_lock(self, &this, AMOUNT_CAPTURABLE_LOCK_RETRIES)
                             .await?;
-                        }
-                    }
-                    (_, _) => {}
+                    let updated_attempt =
+                        update_payment_attempt_in_kv(self, this, payment_attempt, storage_scheme)
+                            .await;
+                    release_amount_capturable_lock(self, lock).await;
+                    updated_attempt
+                } else {
+                    update_payment_attempt_in_kv(self, this, payment_attempt, storage_scheme).await
                 }
-
-                match (old_preprocessing_id, &updated_attempt.preprocessing_step_id) {
-                    (None, Some(preprocessing_id)) => {
-                        add_preprocessing_id_to_reverse_lookup(
-                            self,
-                            key.as_str(),
-                            this.merchant_id.as_str(),
-                            updated_attempt.attempt_id.as_str(),
-                            preprocessing_id.as_str(),
-                            storage_scheme,
-                        )
-                        .await?;
-                    }
-                    (Some(old_preprocessing_id), Some(preprocessing_id)) => {
-                        if old_preprocessing_id.ne(preprocessing_id) {
-                            add_preprocessing_id_to_reverse_lookup(
-                                self,
-                                key.as_str(),
-                                this.merchant_id.as_str(),
-                                updated_attempt.attempt_id.as_str(),
-                                preprocessing_id.as_str(),
-                                storage_scheme,
-                            )
-                            .await?;
-                        }
-                    }
-                    (_, _) => {}
-                }
-
-                let redis_entry = kv::TypedSql {
-                    op: kv::DBOperation::Update {
-                        updatable: kv::Updateable::PaymentAttemptUpdate(
-                            kv::PaymentAttemptUpdateMems {
-                                orig: this.to_storage_model(),
-                                update_data: payment_attempt.to_storage_model(),
-                            },
-                        ),
-                    },
-                };
-                self.push_to_drainer_stream::<DieselPaymentAttempt>(
-                    redis_entry,
-                    PartitionKey::MerchantIdPaymentId {
-                        merchant_id: &updated_attempt.merchant_id,
-                        payment_id: &updated_attempt.payment_id,
-                    },
-                )
-                .await
-                .change_context(errors::StorageError::KVError)?;
-                Ok(updated_attempt)
             }
         }
     }
@@ -606,7 +621,13 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                         payment_attempts.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
                         payment_attempts
                             .iter()
-                            .find(|&pa| pa.status == api_models::enums::AttemptStatus::Charged)
+                            .find(|&pa| {
+                                matches!(
+                                    pa.status,
+                                    api_models::enums::AttemptStatus::Charged
+                                        | api_models::enums::AttemptStatus::PartialCharged
+                                )
+                            })
                             .cloned()
                             .ok_or(error_stack::report!(
                                 redis_interface::errors::RedisError::NotFound
@@ -866,6 +887,77 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
             )
             .await
     }
+
+    async fn get_total_count_of_attempts_by_payment_method_fingerprint(
+        &self,
+        merchant_id: &str,
+        payment_method_fingerprint: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<i64, errors::StorageError> {
+        self.router_store
+            .get_total_count_of_attempts_by_payment_method_fingerprint(
+                merchant_id,
+                payment_method_fingerprint,
+                storage_scheme,
+            )
+            .await
+    }
+
+    async fn find_attempts_pending_review_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+        self.router_store
+            .find_attempts_pending_review_by_merchant_id(merchant_id, storage_scheme)
+            .await
+    }
+
+    async fn decrement_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_capture: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
+        match storage_scheme {
+            MerchantStorageScheme::PostgresOnly => {
+                self.router_store
+                    .decrement_amount_capturable_with_attempt_id(
+                        this,
+                        amount_to_capture,
+                        storage_scheme,
+                    )
+                    .await
+            }
+            MerchantStorageScheme::RedisKv => {
+                update_amount_capturable_with_lock(self, this, -amount_to_capture, storage_scheme)
+                    .await
+            }
+        }
+    }
+
+    async fn increment_amount_capturable_with_attempt_id(
+        &self,
+        this: PaymentAttempt,
+        amount_to_release: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
+        match storage_scheme {
+            MerchantStorageScheme::PostgresOnly => {
+                self.router_store
+                    .increment_amount_capturable_with_attempt_id(
+                        this,
+                        amount_to_release,
+                        storage_scheme,
+                    )
+                    .await
+            }
+            MerchantStorageScheme::RedisKv => {
+                update_amount_capturable_with_lock(self, this, amount_to_release, storage_scheme)
+                    .await
+            }
+        }
+    }
 }
 
 impl DataModelExt for MandateAmountData {
@@ -965,6 +1057,19 @@ impl DataModelExt for PaymentAttempt {
             connector_response_reference_id: self.connector_response_reference_id,
             amount_capturable: self.amount_capturable,
             surcharge_metadata: self.surcharge_metadata,
+            dcc_details: self.dcc_details,
+            connector_request_reference_id: self.connector_request_reference_id,
+            latency_ms: self.latency_ms,
+            connector_amount: self.connector_amount,
+            amount_mismatch: self.amount_mismatch,
+            avs_result: self.avs_result,
+            cvv_result: self.cvv_result,
+            payment_method_fingerprint: self.payment_method_fingerprint,
+            review_status: self.review_status,
+            sdk_information: self.sdk_information,
+            payment_channel: self.payment_channel,
+            installments: self.installments,
+            used_saved_method: self.used_saved_method,
         }
     }
 
@@ -1014,6 +1119,19 @@ impl DataModelExt for PaymentAttempt {
             connector_response_reference_id: storage_model.connector_response_reference_id,
             amount_capturable: storage_model.amount_capturable,
             surcharge_metadata: storage_model.surcharge_metadata,
+            dcc_details: storage_model.dcc_details,
+            connector_request_reference_id: storage_model.connector_request_reference_id,
+            latency_ms: storage_model.latency_ms,
+            connector_amount: storage_model.connector_amount,
+            amount_mismatch: storage_model.amount_mismatch,
+            avs_result: storage_model.avs_result,
+            cvv_result: storage_model.cvv_result,
+            payment_method_fingerprint: storage_model.payment_method_fingerprint,
+            review_status: storage_model.review_status,
+            sdk_information: storage_model.sdk_information,
+            payment_channel: storage_model.payment_channel,
+            installments: storage_model.installments,
+            used_saved_method: storage_model.used_saved_method,
         }
     }
 }
@@ -1063,6 +1181,19 @@ impl DataModelExt for PaymentAttemptNew {
             multiple_capture_count: self.multiple_capture_count,
             amount_capturable: self.amount_capturable,
             surcharge_metadata: self.surcharge_metadata,
+            dcc_details: self.dcc_details,
+            connector_request_reference_id: self.connector_request_reference_id,
+            latency_ms: self.latency_ms,
+            connector_amount: self.connector_amount,
+            amount_mismatch: self.amount_mismatch,
+            avs_result: self.avs_result,
+            cvv_result: self.cvv_result,
+            payment_method_fingerprint: self.payment_method_fingerprint,
+            review_status: self.review_status,
+            sdk_information: self.sdk_information,
+            payment_channel: self.payment_channel,
+            installments: self.installments,
+            used_saved_method: self.used_saved_method,
         }
     }
 
@@ -1110,6 +1241,19 @@ impl DataModelExt for PaymentAttemptNew {
             multiple_capture_count: storage_model.multiple_capture_count,
             amount_capturable: storage_model.amount_capturable,
             surcharge_metadata: storage_model.surcharge_metadata,
+            dcc_details: storage_model.dcc_details,
+            connector_request_reference_id: storage_model.connector_request_reference_id,
+            latency_ms: storage_model.latency_ms,
+            connector_amount: storage_model.connector_amount,
+            amount_mismatch: storage_model.amount_mismatch,
+            avs_result: storage_model.avs_result,
+            cvv_result: storage_model.cvv_result,
+            payment_method_fingerprint: storage_model.payment_method_fingerprint,
+            review_status: storage_model.review_status,
+            sdk_information: storage_model.sdk_information,
+            payment_channel: storage_model.payment_channel,
+            installments: storage_model.installments,
+            used_saved_method: storage_model.used_saved_method,
         }
     }
 }
@@ -1132,6 +1276,9 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
             } => DieselPaymentAttemptUpdate::Update {
                 amount,
                 currency,
@@ -1145,17 +1292,22 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
             },
             Self::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
             } => DieselPaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
             },
             Self::AuthenticationTypeUpdate {
                 authentication_type,
@@ -1179,6 +1331,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
             } => DieselPaymentAttemptUpdate::ConfirmUpdate {
                 amount,
                 currency,
@@ -1196,6 +1355,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
             },
             Self::VoidUpdate {
                 status,
@@ -1218,6 +1384,11 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
             } => DieselPaymentAttemptUpdate::ResponseUpdate {
                 status,
                 connector,
@@ -1232,6 +1403,11 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
             },
             Self::UnresolvedResponseUpdate {
                 status,
@@ -1260,6 +1436,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_message,
                 error_reason,
                 amount_capturable,
+                latency_ms,
             } => DieselPaymentAttemptUpdate::ErrorUpdate {
                 connector,
                 status,
@@ -1267,6 +1444,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_message,
                 error_reason,
                 amount_capturable,
+                latency_ms,
             },
             Self::MultipleCaptureCountUpdate {
                 multiple_capture_count,
@@ -1307,6 +1485,12 @@ impl DataModelExt for PaymentAttemptUpdate {
             Self::SurchargeMetadataUpdate { surcharge_metadata } => {
                 DieselPaymentAttemptUpdate::SurchargeMetadataUpdate { surcharge_metadata }
             }
+            Self::DccDetailsUpdate { dcc_details } => {
+                DieselPaymentAttemptUpdate::DccDetailsUpdate { dcc_details }
+            }
+            Self::ReviewStatusUpdate { review_status } => {
+                DieselPaymentAttemptUpdate::ReviewStatusUpdate { review_status }
+            }
             Self::SurchargeAmountUpdate {
                 surcharge_amount,
                 tax_amount,
@@ -1332,6 +1516,9 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
             } => Self::Update {
                 amount,
                 currency,
@@ -1345,17 +1532,22 @@ impl DataModelExt for PaymentAttemptUpdate {
                 business_sub_label,
                 amount_to_capture,
                 capture_method,
+                capture_on,
+                installments,
+                used_saved_method,
             },
             DieselPaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
             } => Self::UpdateTrackers {
                 payment_token,
                 connector,
                 straight_through_algorithm,
                 amount_capturable,
+                payment_method_fingerprint,
             },
             DieselPaymentAttemptUpdate::AuthenticationTypeUpdate {
                 authentication_type,
@@ -1379,6 +1571,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
             } => Self::ConfirmUpdate {
                 amount,
                 currency,
@@ -1396,6 +1595,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_code,
                 error_message,
                 amount_capturable,
+                capture_method,
+                capture_on,
+                payment_method_fingerprint,
+                sdk_information,
+                payment_channel,
+                installments,
+                used_saved_method,
             },
             DieselPaymentAttemptUpdate::VoidUpdate {
                 status,
@@ -1418,6 +1624,11 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
             } => Self::ResponseUpdate {
                 status,
                 connector,
@@ -1432,6 +1643,11 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_reason,
                 connector_response_reference_id,
                 amount_capturable,
+                latency_ms,
+                connector_amount,
+                amount_mismatch,
+                avs_result,
+                cvv_result,
             },
             DieselPaymentAttemptUpdate::UnresolvedResponseUpdate {
                 status,
@@ -1460,6 +1676,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_message,
                 error_reason,
                 amount_capturable,
+                latency_ms,
             } => Self::ErrorUpdate {
                 connector,
                 status,
@@ -1467,6 +1684,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 error_message,
                 error_reason,
                 amount_capturable,
+                latency_ms,
             },
             DieselPaymentAttemptUpdate::MultipleCaptureCountUpdate {
                 multiple_capture_count,
@@ -1507,6 +1725,12 @@ impl DataModelExt for PaymentAttemptUpdate {
             DieselPaymentAttemptUpdate::SurchargeMetadataUpdate { surcharge_metadata } => {
                 Self::SurchargeMetadataUpdate { surcharge_metadata }
             }
+            DieselPaymentAttemptUpdate::DccDetailsUpdate { dcc_details } => {
+                Self::DccDetailsUpdate { dcc_details }
+            }
+            DieselPaymentAttemptUpdate::ReviewStatusUpdate { review_status } => {
+                Self::ReviewStatusUpdate { review_status }
+            }
             DieselPaymentAttemptUpdate::SurchargeAmountUpdate {
                 surcharge_amount,
                 tax_amount,
@@ -1518,6 +1742,231 @@ impl DataModelExt for PaymentAttemptUpdate {
     }
 }
 
+/// Writes the update of the attempt to redis and queues it to be drained to the database
+async fn update_payment_attempt_in_kv<T: DatabaseStore>(
+    store: &KVRouterStore<T>,
+    this: PaymentAttempt,
+    payment_attempt: PaymentAttemptUpdate,
+    storage_scheme: MerchantStorageScheme,
+) -> CustomResult<PaymentAttempt, errors::StorageError> {
+    let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+    let old_connector_transaction_id = &this.connector_transaction_id;
+    let old_preprocessing_id = &this.preprocessing_step_id;
+    let updated_attempt = PaymentAttempt::from_storage_model(
+        payment_attempt
+            .clone()
+            .to_storage_model()
+            .apply_changeset(this.clone().to_storage_model()),
+    );
+    // Check for database presence as well Maybe use a read replica here ?
+    let redis_value = serde_json::to_string(&updated_attempt)
+        .into_report()
+        .change_context(errors::StorageError::KVError)?;
+    let field = format!("pa_{}", updated_attempt.attempt_id);
+
+    kv_wrapper::<(), _, _>(
+        store,
+        KvOperation::Hset::<PaymentAttempt>((&field, redis_value)),
+        &key,
+    )
+    .await
+    .change_context(errors::StorageError::KVError)?
+    .try_into_hset()
+    .change_context(errors::StorageError::KVError)?;
+
+    match (
+        old_connector_transaction_id,
+        &updated_attempt.connector_transaction_id,
+    ) {
+        (None, Some(connector_transaction_id)) => {
+            add_connector_txn_id_to_reverse_lookup(
+                store,
+                key.as_str(),
+                this.merchant_id.as_str(),
+                updated_attempt.attempt_id.as_str(),
+                connector_transaction_id.as_str(),
+                storage_scheme,
+            )
+            .await?;
+        }
+        (Some(old_connector_transaction_id), Some(connector_transaction_id)) => {
+            if old_connector_transaction_id.ne(connector_transaction_id) {
+                add_connector_txn_id_to_reverse_lookup(
+                    store,
+                    key.as_str(),
+                    this.merchant_id.as_str(),
+                    updated_attempt.attempt_id.as_str(),
+                    connector_transaction_id.as_str(),
+                    storage_scheme,
+                )
+                .await?;
+            }
+        }
+        (_, _) => {}
+    }
+
+    match (old_preprocessing_id, &updated_attempt.preprocessing_step_id) {
+        (None, Some(preprocessing_id)) => {
+            add_preprocessing_id_to_reverse_lookup(
+                store,
+                key.as_str(),
+                this.merchant_id.as_str(),
+                updated_attempt.attempt_id.as_str(),
+                preprocessing_id.as_str(),
+                storage_scheme,
+            )
+            .await?;
+        }
+        (Some(old_preprocessing_id), Some(preprocessing_id)) => {
+            if old_preprocessing_id.ne(preprocessing_id) {
+                add_preprocessing_id_to_reverse_lookup(
+                    store,
+                    key.as_str(),
+                    this.merchant_id.as_str(),
+                    updated_attempt.attempt_id.as_str(),
+                    preprocessing_id.as_str(),
+                    storage_scheme,
+                )
+                .await?;
+            }
+        }
+        (_, _) => {}
+    }
+
+    let redis_entry = kv::TypedSql {
+        op: kv::DBOperation::Update {
+            updatable: kv::Updateable::PaymentAttemptUpdate(kv::PaymentAttemptUpdateMems {
+                orig: this.to_storage_model(),
+                update_data: payment_attempt.to_storage_model(),
+            }),
+        },
+    };
+    store
+        .push_to_drainer_stream::<DieselPaymentAttempt>(
+            redis_entry,
+            PartitionKey::MerchantIdPaymentId {
+                merchant_id: &updated_attempt.merchant_id,
+                payment_id: &updated_attempt.payment_id,
+            },
+        )
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    Ok(updated_attempt)
+}
+
+/// Lock on the amount capturable of an attempt, identified by a token unique to its holder
+struct AmountCapturableLock {
+    key: String,
+    token: String,
+}
+
+/// Takes the lock, waiting for it up to `retries` times if it is held by a concurrent update
+async fn acquire_amount_capturable_lock<T: DatabaseStore>(
+    store: &KVRouterStore<T>,
+    this: &PaymentAttempt,
+    retries: u8,
+) -> CustomResult<AmountCapturableLock, errors::StorageError> {
+    let redis_conn = store
+        .get_redis_conn()
+        .change_context(errors::StorageError::KVError)?;
+    let lock = AmountCapturableLock {
+        key: format!(
+            "mid_{}_pid_{}_pa_{}_amount_capturable_lock",
+            this.merchant_id, this.payment_id, this.attempt_id
+        ),
+        token: common_utils::generate_id_with_default_len("lock"),
+    };
+    for retry in 0..=retries {
+        if retry > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                AMOUNT_CAPTURABLE_LOCK_RETRY_INTERVAL,
+            ))
+            .await;
+        }
+        match redis_conn
+            .set_key_if_not_exists_with_expiry(
+                &lock.key,
+                lock.token.as_str(),
+                Some(AMOUNT_CAPTURABLE_LOCK_TTL),
+            )
+            .await
+            .change_context(errors::StorageError::KVError)?
+        {
+            SetnxReply::KeySet => return Ok(lock),
+            SetnxReply::KeyNotSet => {}
+        }
+    }
+    Err(errors::StorageError::DuplicateValue {
+        entity: "amount capturable lock",
+        key: Some(lock.key),
+    })
+    .into_report()
+}
+
+/// Releases the lock, unless it has expired and is held by someone else by now
+async fn release_amount_capturable_lock<T: DatabaseStore>(
+    store: &KVRouterStore<T>,
+    lock: AmountCapturableLock,
+) {
+    let release = async {
+        let redis_conn = store.get_redis_conn()?;
+        let token = redis_conn.get_key::<Option<String>>(&lock.key).await?;
+        if token.as_deref() == Some(lock.token.as_str()) {
+            redis_conn.delete_key(&lock.key).await?;
+        }
+        Ok::<_, error_stack::Report<redis_interface::errors::RedisError>>(())
+    };
+    release
+        .await
+        .map_err(|error| logger::error!(?error, "Failed to release lock {}", lock.key))
+        .ok();
+}
+
+/// Adds `amount` to the amount capturable of the attempt stored in redis, unless the amount
+/// capturable would become negative. Redis has no conditional update of a hash field, so the
+/// attempt is read and written back while holding a lock on it.
+async fn update_amount_capturable_with_lock<T: DatabaseStore>(
+    store: &KVRouterStore<T>,
+    this: PaymentAttempt,
+    amount: i64,
+    storage_scheme: MerchantStorageScheme,
+) -> CustomResult<PaymentAttempt, errors::StorageError> {
+    let lock = acquire_amount_capturable_lock(store, &this, 0).await?;
+
+    let updated_attempt = async {
+        let payment_attempt = store
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &this.attempt_id,
+                &this.merchant_id,
+                storage_scheme,
+            )
+            .await?;
+        let amount_capturable = payment_attempt.amount_capturable + amount;
+        if amount_capturable < 0 {
+            return Err(errors::StorageError::ValueNotFound(format!(
+                "Payment attempt {} with amount capturable of at least {}",
+                this.attempt_id, -amount
+            )))
+            .into_report();
+        }
+        let status = payment_attempt.status;
+        update_payment_attempt_in_kv(
+            store,
+            payment_attempt,
+            PaymentAttemptUpdate::AmountToCaptureUpdate {
+                status,
+                amount_capturable,
+            },
+            storage_scheme,
+        )
+        .await
+    }
+    .await;
+
+    release_amount_capturable_lock(store, lock).await;
+    updated_attempt
+}
+
 #[inline]
 async fn add_connector_txn_id_to_reverse_lookup<T: DatabaseStore>(
     store: &KVRouterStore<T>,
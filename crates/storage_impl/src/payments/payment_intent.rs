@@ -32,6 +32,7 @@ use redis_interface::HsetnxReply;
 #[cfg(feature = "olap")]
 use router_env::logger;
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use crate::{
     diesel_error_to_data_error,
@@ -92,6 +93,13 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     merchant_decision: new.merchant_decision.clone(),
                     payment_link_id: new.payment_link_id.clone(),
                     payment_confirm_source: new.payment_confirm_source,
+                    connector_routing_trail: new.connector_routing_trail.clone(),
+                    statement_descriptor_city: new.statement_descriptor_city.clone(),
+                    shipping_amount: new.shipping_amount,
+                    handling_amount: new.handling_amount,
+                    client_secret_expiry: new.client_secret_expiry,
+                    success_url: new.success_url.clone(),
+                    failure_url: new.failure_url.clone(),
                 };
                 let diesel_intent = created_intent.clone().to_storage_model();
 
@@ -259,6 +267,98 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         }
     }
 
+    async fn find_payment_intents_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
+        self.router_store
+            .find_payment_intents_by_customer_id_merchant_id(
+                customer_id,
+                merchant_id,
+                limit,
+                offset,
+                storage_scheme,
+            )
+            .await
+    }
+
+    async fn get_open_payment_intents_count_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<i64, StorageError> {
+        self.router_store
+            .get_open_payment_intents_count_by_customer_id_merchant_id(
+                customer_id,
+                merchant_id,
+                created_after,
+                storage_scheme,
+            )
+            .await
+    }
+
+    #[instrument(skip_all)]
+    async fn invalidate_expired_client_secrets(
+        &self,
+        expired_before: PrimitiveDateTime,
+    ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
+        let invalidated_intents = self
+            .router_store
+            .invalidate_expired_client_secrets(expired_before)
+            .await?;
+
+        // The payment intents of merchants on the KV store are read from redis before the
+        // database, so the client secret is removed from the copy in redis as well
+        for payment_intent in &invalidated_intents {
+            let key = format!(
+                "mid_{}_pid_{}",
+                payment_intent.merchant_id, payment_intent.payment_id
+            );
+            let field = format!("pi_{}", payment_intent.payment_id);
+
+            let redis_intent = match kv_wrapper::<DieselPaymentIntent, _, _>(
+                self,
+                KvOperation::<DieselPaymentIntent>::HGet(&field),
+                &key,
+            )
+            .await
+            .and_then(|result| result.try_into_hget())
+            {
+                Ok(redis_intent) => redis_intent,
+                Err(redis_error) => match redis_error.current_context() {
+                    // The payment intent is not cached in redis
+                    redis_interface::errors::RedisError::NotFound => continue,
+                    _ => return Err(redis_error.change_context(StorageError::KVError)),
+                },
+            };
+
+            let redis_value =
+                Encode::<DieselPaymentIntent>::encode_to_string_of_json(&DieselPaymentIntent {
+                    client_secret: None,
+                    ..redis_intent
+                })
+                .change_context(StorageError::SerializationFailed)?;
+
+            kv_wrapper::<(), _, _>(
+                self,
+                KvOperation::<DieselPaymentIntent>::Hset((&field, redis_value)),
+                &key,
+            )
+            .await
+            .change_context(StorageError::KVError)?
+            .try_into_hset()
+            .change_context(StorageError::KVError)?;
+        }
+
+        Ok(invalidated_intents)
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -395,6 +495,78 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         }
     }
 
+    #[instrument(skip_all)]
+    async fn find_payment_intents_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
+        let conn = pg_connection_read(self).await?;
+        DieselPaymentIntent::find_by_customer_id_merchant_id(
+            &conn,
+            customer_id,
+            merchant_id,
+            limit,
+            offset,
+        )
+        .await
+        .map(|payment_intents| {
+            payment_intents
+                .into_iter()
+                .map(PaymentIntent::from_storage_model)
+                .collect()
+        })
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+    }
+
+    #[instrument(skip_all)]
+    async fn get_open_payment_intents_count_by_customer_id_merchant_id(
+        &self,
+        customer_id: &str,
+        merchant_id: &str,
+        created_after: PrimitiveDateTime,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<i64, StorageError> {
+        let conn = pg_connection_read(self).await?;
+        DieselPaymentIntent::count_open_by_customer_id_merchant_id(
+            &conn,
+            customer_id,
+            merchant_id,
+            created_after,
+        )
+        .await
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+    }
+
+    #[instrument(skip_all)]
+    async fn invalidate_expired_client_secrets(
+        &self,
+        expired_before: PrimitiveDateTime,
+    ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
+        let conn = pg_connection_write(self).await?;
+        DieselPaymentIntent::invalidate_expired_client_secrets(&conn, expired_before)
+            .await
+            .map(|payment_intents| {
+                payment_intents
+                    .into_iter()
+                    .map(PaymentIntent::from_storage_model)
+                    .collect()
+            })
+            .map_err(|er| {
+                let new_err = diesel_error_to_data_error(er.current_context());
+                er.change_context(new_err)
+            })
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -757,6 +929,13 @@ impl DataModelExt for PaymentIntentNew {
             merchant_decision: self.merchant_decision,
             payment_link_id: self.payment_link_id,
             payment_confirm_source: self.payment_confirm_source,
+            connector_routing_trail: self.connector_routing_trail,
+            statement_descriptor_city: self.statement_descriptor_city,
+            shipping_amount: self.shipping_amount,
+            handling_amount: self.handling_amount,
+            client_secret_expiry: self.client_secret_expiry,
+            success_url: self.success_url,
+            failure_url: self.failure_url,
         }
     }
 
@@ -795,6 +974,13 @@ impl DataModelExt for PaymentIntentNew {
             merchant_decision: storage_model.merchant_decision,
             payment_link_id: storage_model.payment_link_id,
             payment_confirm_source: storage_model.payment_confirm_source,
+            connector_routing_trail: storage_model.connector_routing_trail,
+            statement_descriptor_city: storage_model.statement_descriptor_city,
+            shipping_amount: storage_model.shipping_amount,
+            handling_amount: storage_model.handling_amount,
+            client_secret_expiry: storage_model.client_secret_expiry,
+            success_url: storage_model.success_url,
+            failure_url: storage_model.failure_url,
         }
     }
 }
@@ -838,6 +1024,13 @@ impl DataModelExt for PaymentIntent {
             merchant_decision: self.merchant_decision,
             payment_link_id: self.payment_link_id,
             payment_confirm_source: self.payment_confirm_source,
+            connector_routing_trail: self.connector_routing_trail,
+            statement_descriptor_city: self.statement_descriptor_city,
+            shipping_amount: self.shipping_amount,
+            handling_amount: self.handling_amount,
+            client_secret_expiry: self.client_secret_expiry,
+            success_url: self.success_url,
+            failure_url: self.failure_url,
         }
     }
 
@@ -877,6 +1070,13 @@ impl DataModelExt for PaymentIntent {
             merchant_decision: storage_model.merchant_decision,
             payment_link_id: storage_model.payment_link_id,
             payment_confirm_source: storage_model.payment_confirm_source,
+            connector_routing_trail: storage_model.connector_routing_trail,
+            statement_descriptor_city: storage_model.statement_descriptor_city,
+            shipping_amount: storage_model.shipping_amount,
+            handling_amount: storage_model.handling_amount,
+            client_secret_expiry: storage_model.client_secret_expiry,
+            success_url: storage_model.success_url,
+            failure_url: storage_model.failure_url,
         }
     }
 }
@@ -890,10 +1090,12 @@ impl DataModelExt for PaymentIntentUpdate {
                 status,
                 amount_captured,
                 return_url,
+                metadata,
             } => DieselPaymentIntentUpdate::ResponseUpdate {
                 status,
                 amount_captured,
                 return_url,
+                metadata,
             },
             Self::MetadataUpdate { metadata } => {
                 DieselPaymentIntentUpdate::MetadataUpdate { metadata }
@@ -938,6 +1140,12 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
             } => DieselPaymentIntentUpdate::Update {
                 amount,
                 currency,
@@ -955,6 +1163,12 @@ impl DataModelExt for PaymentIntentUpdate {
                 order_details,
                 metadata,
                 payment_confirm_source,
+                connector_routing_trail,
+                statement_descriptor_city,
+                shipping_amount,
+                handling_amount,
+                success_url,
+                failure_url,
             },
             Self::PaymentAttemptAndAttemptCountUpdate {
                 active_attempt_id,
@@ -982,6 +1196,11 @@ impl DataModelExt for PaymentIntentUpdate {
                 status,
                 merchant_decision,
             },
+            Self::RoutingTrailUpdate {
+                connector_routing_trail,
+            } => DieselPaymentIntentUpdate::RoutingTrailUpdate {
+                connector_routing_trail,
+            },
         }
     }
 
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_account
+DROP COLUMN debug_connector_io;
+
+ALTER TABLE connector_response
+DROP COLUMN connector_io_log;
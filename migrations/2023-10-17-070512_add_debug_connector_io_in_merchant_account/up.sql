@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE merchant_account
+ADD COLUMN IF NOT EXISTS debug_connector_io BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE connector_response
+ADD COLUMN IF NOT EXISTS connector_io_log JSON DEFAULT NULL;
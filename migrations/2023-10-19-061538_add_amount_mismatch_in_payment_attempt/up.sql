@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS connector_amount BIGINT,
+ADD COLUMN IF NOT EXISTS amount_mismatch BOOLEAN;
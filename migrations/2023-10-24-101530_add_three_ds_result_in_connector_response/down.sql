@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE connector_response
+DROP COLUMN three_ds_version,
+DROP COLUMN liability_shift;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE connector_response
+ADD COLUMN IF NOT EXISTS three_ds_version VARCHAR(16),
+ADD COLUMN IF NOT EXISTS liability_shift BOOLEAN;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS shipping_amount BIGINT DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS handling_amount BIGINT DEFAULT NULL;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE connector_response
+DROP COLUMN authorization_code;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE connector_response
+ADD COLUMN IF NOT EXISTS authorization_code VARCHAR(64);
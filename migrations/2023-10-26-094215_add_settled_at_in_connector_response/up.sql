@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE connector_response
+ADD COLUMN IF NOT EXISTS settled_at TIMESTAMP;
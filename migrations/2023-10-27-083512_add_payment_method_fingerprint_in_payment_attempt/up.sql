@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS payment_method_fingerprint VARCHAR(64) DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS payment_attempt_merchant_id_payment_method_fingerprint_index ON payment_attempt (merchant_id, payment_method_fingerprint);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_attempt_pending_review_index;
+
+ALTER TABLE payment_attempt
+DROP COLUMN review_status;
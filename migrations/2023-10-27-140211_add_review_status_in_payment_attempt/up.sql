@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS review_status VARCHAR(32) DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS payment_attempt_pending_review_index ON payment_attempt (merchant_id)
+WHERE review_status = 'pending_review';
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS success_url VARCHAR(255) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS failure_url VARCHAR(255) DEFAULT NULL;